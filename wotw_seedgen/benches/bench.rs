@@ -157,7 +157,7 @@ fn reach_checking(c: &mut Criterion) {
         b.iter(|| {
             let world_settings = WorldSettings::default();
             let mut world = World::new_spawn(&graph, &world_settings);
-            world.player.inventory = Pool::preset().inventory;
+            world.player.inventory = Pool::preset(&world_settings, &graph).inventory;
             world.player.inventory.grant(Item::SpiritLight(1), 10000);
             let spawn = world.graph.find_spawn("MarshSpawn.Main").unwrap();
             world
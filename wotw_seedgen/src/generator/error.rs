@@ -0,0 +1,474 @@
+use std::{
+    error::Error,
+    fmt::{self, Display},
+};
+
+use serde::{Deserialize, Serialize};
+
+/// Reasons why seed generation may fail
+///
+/// Errors that relate to a specific world carry its `world_index`
+///
+/// # Examples
+///
+/// [`GenerationError`]s can be serialized to provide machine-readable output
+///
+/// ```
+/// # use wotw_seedgen::generator::GenerationError;
+/// #
+/// let error = GenerationError::InvalidSpawn {
+///     world_index: 0,
+///     message: "Spawn Nowhere not found".to_string(),
+/// };
+///
+/// let json = serde_json::to_string(&error).unwrap();
+/// assert_eq!(json, "{\"InvalidSpawn\":{\"worldIndex\":0,\"message\":\"Spawn Nowhere not found\"}}");
+/// assert_eq!(error.to_string(), "(World 0): Spawn Nowhere not found");
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all_fields = "camelCase")]
+pub enum GenerationError {
+    /// The requested generation algorithm version is not supported by this release of the generator
+    UnsupportedAlgorithmVersion { version: u32, supported: u32 },
+    /// An inline header used a name that is already in use by a header file
+    AmbiguousHeaderName { world_index: usize, name: String },
+    /// Multiple different values were provided for the same header configuration parameter
+    ConflictingHeaderConfig {
+        world_index: usize,
+        header_name: String,
+        config_name: String,
+        first_value: String,
+        second_value: String,
+    },
+    /// A header could not be read
+    ReadHeader {
+        world_index: usize,
+        header_name: String,
+        message: String,
+    },
+    /// A header contained invalid syntax
+    HeaderParse {
+        world_index: usize,
+        header_name: String,
+        message: String,
+    },
+    /// A header could not be built with the provided configuration parameters
+    HeaderBuild {
+        world_index: usize,
+        header_name: String,
+        message: String,
+    },
+    /// A header excluded another header that was active as well
+    IncompatibleHeaders {
+        world_index: usize,
+        header_name: String,
+        excluded_by: String,
+    },
+    /// Multiple headers tried to customize the same item
+    ConflictingItemDetails { world_index: usize, item: String },
+    /// A plando placement referenced a location that doesn't exist or can't hold items
+    UnknownPlandoLocation {
+        world_index: usize,
+        location: String,
+    },
+    /// A plando placement targeted a location that already holds an item from a header or another plando placement
+    ConflictingPlandoPlacement {
+        world_index: usize,
+        location: String,
+    },
+    /// A plando placement can never be collected, e.g. because the location requires the pinned item
+    UnreachablePlandoPlacement {
+        world_index: usize,
+        location: String,
+        item: String,
+    },
+    /// The [`PlandoPlacement`](crate::settings::PlandoPlacement)s lock locations needed to reach the goals behind themselves
+    UnbeatablePlando {
+        world_index: usize,
+        locations: Vec<String>,
+    },
+    /// An excluded location doesn't exist or can't hold items
+    UnknownExcludedLocation {
+        world_index: usize,
+        location: String,
+    },
+    /// The [`ItemPoolSettings`](crate::settings::ItemPoolSettings), [`WorldSettings::starting_items`](crate::settings::WorldSettings::starting_items) or [`WorldSettings::plando`](crate::settings::WorldSettings::plando) removed more of an item than the item pool contained
+    ItemPoolUnderflow {
+        world_index: usize,
+        item: String,
+        removed: u32,
+        available: u32,
+    },
+    /// The amount of an item changed by the [`ItemPoolSettings`](crate::settings::ItemPoolSettings) doesn't fit into the item pool
+    ItemPoolOverflow { world_index: usize, item: String },
+    /// The [`ItemPoolSettings`](crate::settings::ItemPoolSettings) removed items that logic requires to reach some locations
+    ///
+    /// Contains the removed items that may be required and the locations that can no longer be reached
+    RequiredItemsRemoved {
+        world_index: usize,
+        items: Vec<String>,
+        locations: Vec<String>,
+    },
+    /// An item was placed somewhere its [`PlacementConstraint`](crate::settings::PlacementConstraint) doesn't allow
+    ConstraintViolated {
+        world_index: usize,
+        constraint: String,
+        location: String,
+        step: usize,
+    },
+    /// No location was left that would satisfy a [`PlacementConstraint`](crate::settings::PlacementConstraint)
+    UnsatisfiableConstraint {
+        world_index: usize,
+        constraint: String,
+    },
+    /// The shop price multipliers don't form a valid range
+    InvalidPriceRange {
+        world_index: usize,
+        min: f32,
+        max: f32,
+    },
+    /// The cap of a [`SpiritLightDistribution::Capped`](crate::settings::SpiritLightDistribution::Capped) is too low to fit the Spirit Light from the item pool
+    InsufficientSpiritLightCap {
+        world_index: usize,
+        cap: u32,
+        spirit_light: u32,
+        slots: usize,
+    },
+    /// Too few zones of a [`RelicRules`](crate::settings::RelicRules) goal had suitable locations to place the minimum amount of Relics
+    InsufficientRelicLocations {
+        world_index: usize,
+        placed: usize,
+        min: usize,
+    },
+    /// The item pool doesn't hold enough Spirit Light to place at least 1 on every pickup meant for Spirit Light
+    InsufficientSpiritLight {
+        world_index: usize,
+        spirit_light: u32,
+        slots: usize,
+    },
+    /// An item category was configured to be both local and shared in the [`MultiworldSettings`](crate::settings::MultiworldSettings)
+    ConflictingItemDistribution { category: String },
+    /// The progression balance in the [`MultiworldSettings`](crate::settings::MultiworldSettings) was outside of the range 0 to 1
+    InvalidProgressionBalance { balance: f32 },
+    /// The spawn settings did not result in a usable spawn location
+    InvalidSpawn { world_index: usize, message: String },
+    /// Some locations can never be reached on these settings, but the [`Accessibility`](crate::settings::Accessibility) requires all locations to be reachable
+    InaccessibleLocations {
+        world_index: usize,
+        locations: Vec<String>,
+    },
+    /// Nothing could be reached from the spawn locations
+    NothingReachable { spawns: Vec<String> },
+    /// Some locations could not be reached
+    ///
+    /// Contains the identifiers of the unreached locations for each world
+    UnreachableLocations { unreached: Vec<Vec<String>> },
+    /// There were not enough free locations to place forced progression
+    NotEnoughSlots { world_index: usize, item: String },
+    /// A shop location has no entry in the shop prices table
+    MissingShopPrice {
+        world_index: usize,
+        location: String,
+    },
+    /// A shop price overflowed after randomizing it
+    ShopPriceOverflow { world_index: usize, item: String },
+    /// Failed to choose between the progression options
+    ProgressionChoice { world_index: usize, message: String },
+    /// A [`GenerationObserver`](super::GenerationObserver) cancelled the generation
+    Cancelled,
+    /// Every attempt to generate placements failed
+    ///
+    /// Contains the spawns and cause of failure for each attempt
+    AttemptsExhausted { attempts: Vec<FailedAttempt> },
+    /// The time budget ran out before an attempt to generate placements succeeded
+    ///
+    /// Contains the spawns and cause of failure for each attempt that was made
+    TimeBudgetExceeded {
+        time_budget_ms: u64,
+        attempts: Vec<FailedAttempt>,
+    },
+    /// Postprocessing commands such as `$WHEREIS` failed, e.g. because of an invalid regex
+    Postprocess { message: String },
+}
+
+impl Display for GenerationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GenerationError::UnsupportedAlgorithmVersion { version, supported } => write!(
+                f,
+                "Algorithm version {version} is not supported, this generator only supports algorithm version {supported}"
+            ),
+            GenerationError::AmbiguousHeaderName { world_index, name } => write!(
+                f,
+                "(World {world_index}): Ambiguous name: {name} used both as a file header and an inline header"
+            ),
+            GenerationError::ConflictingHeaderConfig {
+                world_index,
+                header_name,
+                config_name,
+                first_value,
+                second_value,
+            } => write!(
+                f,
+                "(World {world_index}): provided multiple values for configuration parameter {config_name} for header {header_name} ({first_value} and {second_value})"
+            ),
+            GenerationError::ReadHeader {
+                world_index,
+                header_name,
+                message,
+            } => write!(
+                f,
+                "(World {world_index}): Failed to read header {header_name}: {message}"
+            ),
+            GenerationError::HeaderParse {
+                world_index,
+                header_name,
+                message,
+            } => write!(
+                f,
+                "(World {world_index}): Error in header {header_name}:\n{message}"
+            ),
+            GenerationError::HeaderBuild {
+                world_index,
+                header_name,
+                message,
+            } => write!(
+                f,
+                "(World {world_index}): Failed to build header {header_name}: {message}"
+            ),
+            GenerationError::IncompatibleHeaders {
+                world_index,
+                header_name,
+                excluded_by,
+            } => write!(
+                f,
+                "(World {world_index}): headers {excluded_by} and {header_name} are incompatible"
+            ),
+            GenerationError::ConflictingItemDetails { world_index, item } => write!(
+                f,
+                "(World {world_index}): multiple headers tried to customize the item {item}"
+            ),
+            GenerationError::UnknownPlandoLocation {
+                world_index,
+                location,
+            } => write!(
+                f,
+                "(World {world_index}): Cannot place items on {location}, no such location exists"
+            ),
+            GenerationError::ConflictingPlandoPlacement {
+                world_index,
+                location,
+            } => write!(
+                f,
+                "(World {world_index}): Cannot place items on {location}, it already holds an item"
+            ),
+            GenerationError::UnreachablePlandoPlacement {
+                world_index,
+                location,
+                item,
+            } => write!(
+                f,
+                "(World {world_index}): {item} was placed on {location}, but it can never be collected there"
+            ),
+            GenerationError::UnbeatablePlando {
+                world_index,
+                locations,
+            } => write!(
+                f,
+                "(World {world_index}): The plando placements make the goals unreachable, {} locations can't be reached anymore: {}",
+                locations.len(),
+                locations.join(", ")
+            ),
+            GenerationError::UnknownExcludedLocation {
+                world_index,
+                location,
+            } => write!(
+                f,
+                "(World {world_index}): Cannot exclude {location}, no such location exists"
+            ),
+            GenerationError::ItemPoolUnderflow {
+                world_index,
+                item,
+                removed,
+                available,
+            } => write!(
+                f,
+                "(World {world_index}): Cannot remove {removed} {item} from the item pool, it only contains {available}"
+            ),
+            GenerationError::ItemPoolOverflow { world_index, item } => write!(
+                f,
+                "(World {world_index}): Changing the amount of {item} overflows the item pool"
+            ),
+            GenerationError::RequiredItemsRemoved {
+                world_index,
+                items,
+                locations,
+            } => write!(
+                f,
+                "(World {world_index}): Removing {} from the item pool makes {} locations unreachable: {}",
+                items.join(", "),
+                locations.len(),
+                locations.join(", ")
+            ),
+            GenerationError::ConstraintViolated {
+                world_index,
+                constraint,
+                location,
+                step,
+            } => write!(
+                f,
+                "(World {world_index}): Placing on {location} in step {step} violates the placement constraint {constraint}"
+            ),
+            GenerationError::UnsatisfiableConstraint {
+                world_index,
+                constraint,
+            } => write!(
+                f,
+                "(World {world_index}): No locations left to satisfy the placement constraint {constraint}"
+            ),
+            GenerationError::InvalidPriceRange {
+                world_index,
+                min,
+                max,
+            } => write!(
+                f,
+                "(World {world_index}): The shop price multipliers {min} to {max} don't form a valid range"
+            ),
+            GenerationError::InsufficientSpiritLightCap {
+                world_index,
+                cap,
+                spirit_light,
+                slots,
+            } => write!(
+                f,
+                "(World {world_index}): Capping Spirit Light pickups at {cap} can't fit {spirit_light} Spirit Light into the estimated {slots} slots"
+            ),
+            GenerationError::InsufficientRelicLocations {
+                world_index,
+                placed,
+                min,
+            } => write!(
+                f,
+                "(World {world_index}): Only found suitable locations for {placed} Relics, but at least {min} are required"
+            ),
+            GenerationError::InsufficientSpiritLight {
+                world_index,
+                spirit_light,
+                slots,
+            } => write!(
+                f,
+                "(World {world_index}): {spirit_light} Spirit Light isn't enough to place at least 1 on each of the {slots} pickups for Spirit Light"
+            ),
+            GenerationError::ConflictingItemDistribution { category } => write!(
+                f,
+                "{category} items can't be both local and shared in the multiworld settings"
+            ),
+            GenerationError::InvalidProgressionBalance { balance } => write!(
+                f,
+                "The progression balance has to be between 0 and 1, but was {balance}"
+            ),
+            GenerationError::InvalidSpawn {
+                world_index,
+                message,
+            } => write!(f, "(World {world_index}): {message}"),
+            GenerationError::InaccessibleLocations {
+                world_index,
+                locations,
+            } => write!(
+                f,
+                "(World {world_index}): All locations are required to be reachable, but {} can never be reached on these settings: {}",
+                locations.len(),
+                locations.join(", ")
+            ),
+            GenerationError::NothingReachable { spawns } => write!(
+                f,
+                "Failed to reach anything from spawn location {}",
+                spawns.join(", ")
+            ),
+            GenerationError::UnreachableLocations { unreached } => {
+                write!(f, "Failed to reach all locations")?;
+                for (world_index, unreached) in unreached.iter().enumerate() {
+                    if !unreached.is_empty() {
+                        write!(
+                            f,
+                            "\n(World {world_index}): {} unreached locations",
+                            unreached.len()
+                        )?;
+                    }
+                }
+                Ok(())
+            }
+            GenerationError::NotEnoughSlots { world_index, item } => write!(
+                f,
+                "(World {world_index}): Not enough slots to place forced progression {item}"
+            ),
+            GenerationError::MissingShopPrice {
+                world_index,
+                location,
+            } => write!(
+                f,
+                "(World {world_index}): {location} claims to be a shop location, but doesn't have an entry in the shop prices table!"
+            ),
+            GenerationError::ShopPriceOverflow { world_index, item } => write!(
+                f,
+                "(World {world_index}): Overflowed shop price for {item} after adding a random amount to it"
+            ),
+            GenerationError::ProgressionChoice {
+                world_index,
+                message,
+            } => write!(
+                f,
+                "(World {world_index}): Error choosing progression: {message}"
+            ),
+            GenerationError::Cancelled => write!(f, "Generation was cancelled"),
+            GenerationError::AttemptsExhausted { attempts } => {
+                write!(
+                    f,
+                    "All {} attempts to generate a seed failed :(",
+                    attempts.len()
+                )?;
+                write_attempts(f, attempts)
+            }
+            GenerationError::TimeBudgetExceeded {
+                time_budget_ms,
+                attempts,
+            } => {
+                write!(
+                    f,
+                    "Failed to generate a seed within {time_budget_ms}ms after {} attempts :(",
+                    attempts.len()
+                )?;
+                write_attempts(f, attempts)
+            }
+            GenerationError::Postprocess { message } => {
+                write!(f, "Failed to postprocess seed: {message}")
+            }
+        }
+    }
+}
+impl Error for GenerationError {}
+
+fn write_attempts(f: &mut fmt::Formatter, attempts: &[FailedAttempt]) -> fmt::Result {
+    for (index, attempt) in attempts.iter().enumerate() {
+        write!(f, "\nAttempt {}: {attempt}", index + 1)?;
+    }
+    Ok(())
+}
+
+/// Diagnostics about an unsuccessful attempt to generate placements
+///
+/// These can be used to tell which settings combinations are fragile
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FailedAttempt {
+    /// The identifiers of the spawn locations used for each world during this attempt
+    pub spawns: Vec<String>,
+    /// The reason why the attempt failed
+    pub error: GenerationError,
+}
+
+impl Display for FailedAttempt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "(Spawning on {}) {}", self.spawns.join(", "), self.error)
+    }
+}
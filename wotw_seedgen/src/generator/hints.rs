@@ -0,0 +1,302 @@
+use std::fmt::{self, Display};
+
+use rand::{seq::SliceRandom, Rng};
+use rustc_hash::FxHashSet;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    header::CodeDisplay,
+    item::{Item, Message},
+    log,
+    util::Zone,
+    world::{graph::Node, World},
+};
+
+use super::{replay::Replay, SeedSpoiler};
+
+/// A hint written into the seed, see [`HintSettings`](crate::settings::HintSettings)
+#[derive(Debug, Clone)]
+pub struct Hint<'graph> {
+    /// The location that reveals the hint when collected
+    pub node: &'graph Node,
+    /// What the hint reveals
+    pub kind: HintKind,
+}
+/// The information revealed by a [`Hint`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all_fields = "camelCase")]
+pub enum HintKind {
+    /// The zone holds at least one item required to reach the goals
+    WayOfTheHero { zone: Zone },
+    /// The goals can be reached without collecting anything in the zone
+    Barren { zone: Zone },
+    /// A required item is placed on this location
+    ItemLocation {
+        item_name: String,
+        /// The world receiving the item, if it differs from the world holding the location
+        target_world_index: Option<usize>,
+        location: String,
+    },
+}
+
+impl Hint<'_> {
+    pub fn code(&self) -> CodeDisplay<'_, Hint<'_>> {
+        CodeDisplay::new(self, |s, f| {
+            // Hints are only placed on locations, which always have a trigger
+            let trigger = s.node.trigger().unwrap();
+            let message = Item::Message(Message::new(s.kind.to_string()));
+            write!(f, "{}|{}", trigger.code(), message.code())
+        })
+    }
+}
+
+impl Display for HintKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HintKind::WayOfTheHero { zone } => write!(f, "{zone} is on the Way of the Hero"),
+            HintKind::Barren { zone } => write!(f, "{zone} is barren"),
+            HintKind::ItemLocation {
+                item_name,
+                target_world_index,
+                location,
+            } => match target_world_index {
+                Some(target_world_index) => write!(
+                    f,
+                    "World {target_world_index}'s {item_name} is at {location}"
+                ),
+                None => write!(f, "{item_name} is at {location}"),
+            },
+        }
+    }
+}
+
+/// Generates the hints for each world according to its [`HintSettings`](crate::settings::HintSettings)
+///
+/// `worlds` have to be the worlds as they were passed to the generator
+pub(super) fn generate_hints<'graph>(
+    worlds: &[World<'graph, '_>],
+    spawns: Vec<&'graph Node>,
+    spoiler: &SeedSpoiler,
+    rng: &mut impl Rng,
+) -> Vec<Vec<Hint<'graph>>> {
+    let mut hints = vec![Vec::new(); worlds.len()];
+    if !worlds
+        .iter()
+        .any(|world| world.player.settings.hints.is_enabled())
+    {
+        return hints;
+    }
+
+    let replay = Replay::new(worlds, spawns, spoiler);
+    let result = replay.run(&FxHashSet::default());
+    if !result.beatable {
+        log::warning!("Skipping hint generation because the placements didn't reach the goals when replaying them");
+        return hints;
+    }
+
+    // Anything collected after the goals were reached can't be required
+    let required = result
+        .spheres
+        .iter()
+        .flatten()
+        .copied()
+        .filter(|&index| replay.is_progression(index) && replay.is_required(index))
+        .collect::<Vec<_>>();
+    log::trace!("Found {} required placements", required.len());
+
+    for (world_index, (world, world_hints)) in worlds.iter().zip(&mut hints).enumerate() {
+        let settings = &world.player.settings.hints;
+        if !settings.is_enabled() {
+            continue;
+        }
+
+        let mut slots = world
+            .graph
+            .nodes
+            .iter()
+            .filter(|node| node.can_place() && settings.placement.matches(node.identifier()))
+            .collect::<Vec<_>>();
+        if slots.is_empty() {
+            log::warning!(
+                "(World {}): No locations for {} hints found in the logic",
+                world_index,
+                settings.placement
+            );
+            continue;
+        }
+        slots.shuffle(rng);
+
+        let world_placements = replay
+            .placements
+            .iter()
+            .enumerate()
+            .filter(|(_, placement)| placement.origin_world_index == world_index);
+        let mut zones = world_placements
+            .clone()
+            .filter_map(|(_, placement)| placement.node.zone())
+            .filter(|zone| !matches!(zone, Zone::Spawn | Zone::Void))
+            .collect::<Vec<_>>();
+        zones.sort_unstable();
+        zones.dedup();
+        zones.shuffle(rng);
+        let mut required_locations = world_placements
+            .clone()
+            .filter(|(index, _)| required.contains(index))
+            .map(|(_, placement)| placement)
+            .collect::<Vec<_>>();
+        required_locations.shuffle(rng);
+
+        let (woth_zones, barren_candidates): (Vec<_>, Vec<_>) =
+            zones.into_iter().partition(|&zone| {
+                required_locations
+                    .iter()
+                    .any(|placement| placement.node.zone() == Some(zone))
+            });
+        let woth_zones = woth_zones
+            .into_iter()
+            .take(settings.way_of_the_hero)
+            .collect::<Vec<_>>();
+        // A zone without required items may still be needed, for instance if it holds one of two interchangeable items or Spirit Light for a shop.
+        // So every barren zone is checked by skipping its placements together with those of the zones already hinted barren
+        let mut barren_zones = Vec::new();
+        let mut barren_placements = FxHashSet::default();
+        for zone in barren_candidates {
+            if barren_zones.len() == settings.barren {
+                break;
+            }
+            // The player will always have to visit the goal zone
+            if zone == Zone::Willow {
+                continue;
+            }
+            let mut skipped = barren_placements.clone();
+            skipped.extend(
+                world_placements
+                    .clone()
+                    .filter(|(_, placement)| placement.node.zone() == Some(zone))
+                    .map(|(index, _)| index),
+            );
+            if replay.run(&skipped).beatable {
+                barren_zones.push(zone);
+                barren_placements = skipped;
+            }
+        }
+
+        let mut hinted_locations = FxHashSet::default();
+        required_locations.retain(|placement| hinted_locations.insert(placement.node.index()));
+        // Prefer unique items over resources, and zones that weren't already hinted since those would reveal less new information
+        required_locations.sort_by_key(|placement| {
+            (
+                matches!(placement.item, Item::Resource(_)),
+                placement
+                    .node
+                    .zone()
+                    .is_some_and(|zone| woth_zones.contains(&zone)),
+            )
+        });
+        let item_locations = required_locations
+            .into_iter()
+            .take(settings.item_locations)
+            .map(|placement| HintKind::ItemLocation {
+                item_name: world
+                    .custom_items
+                    .get(&placement.item)
+                    .and_then(|details| details.name.clone())
+                    .unwrap_or_else(|| placement.item.to_string()),
+                target_world_index: if placement.target_world_index == world_index {
+                    None
+                } else {
+                    Some(placement.target_world_index)
+                },
+                location: placement.node.identifier().to_string(),
+            })
+            .collect::<Vec<_>>();
+
+        let missing = settings.way_of_the_hero.saturating_sub(woth_zones.len())
+            + settings.barren.saturating_sub(barren_zones.len())
+            + settings.item_locations.saturating_sub(item_locations.len());
+        if missing > 0 {
+            log::warning!(
+                "(World {}): Not enough truthful hints available, {} hints will be missing",
+                world_index,
+                missing
+            );
+        }
+
+        let kinds = woth_zones
+            .into_iter()
+            .map(|zone| HintKind::WayOfTheHero { zone })
+            .chain(
+                barren_zones
+                    .into_iter()
+                    .map(|zone| HintKind::Barren { zone }),
+            )
+            .chain(item_locations);
+        world_hints.extend(
+            kinds
+                .zip(slots.iter().cycle())
+                .map(|(kind, &node)| Hint { node, kind }),
+        );
+
+        log::trace!(
+            "(World {}): Generated {} hints",
+            world_index,
+            world_hints.len()
+        );
+    }
+
+    hints
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        settings::{HintSettings, UniverseSettings, WorldSettings},
+        test_util::test_seed,
+    };
+
+    #[test]
+    fn barren_hints_are_truthful() {
+        let universe_settings = UniverseSettings {
+            seed: "barren hints".to_string(),
+            world_settings: vec![WorldSettings {
+                hints: HintSettings {
+                    barren: 12,
+                    ..HintSettings::default()
+                },
+                ..WorldSettings::default()
+            }],
+            ..UniverseSettings::default()
+        };
+        let (_, seed) = test_seed(universe_settings);
+
+        let barren_zones = seed.worlds[0]
+            .hints
+            .iter()
+            .filter_map(|hint| match hint.kind {
+                HintKind::Barren { zone } => Some(zone),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert!(!barren_zones.is_empty());
+        assert!(!barren_zones.contains(&Zone::Willow));
+
+        // Skipping everything in all barren zones at once, including Spirit Light, has to leave the seed beatable
+        let spawns = seed.worlds.iter().map(|world| world.spawn).collect();
+        let replay = Replay::new(&seed.initial_worlds, spawns, &seed.spoiler);
+        let skipped = replay
+            .placements
+            .iter()
+            .enumerate()
+            .filter(|(_, placement)| {
+                placement
+                    .node
+                    .zone()
+                    .is_some_and(|zone| barren_zones.contains(&zone))
+            })
+            .map(|(index, _)| index)
+            .collect::<FxHashSet<_>>();
+        assert!(!skipped.is_empty());
+        assert!(replay.run(&skipped).beatable);
+    }
+}
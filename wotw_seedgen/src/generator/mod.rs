@@ -1,85 +1,203 @@
+mod error;
+mod hints;
+mod observer;
 mod placement;
+mod replay;
 mod seed;
 mod spoiler;
+mod spoiler_diff;
 
+pub use error::*;
+pub use hints::*;
+pub use observer::*;
 pub use placement::*;
 pub use seed::*;
 pub use spoiler::*;
+pub use spoiler_diff::*;
 
 use std::{cmp::Ordering, fmt::Write};
 
-use rand::{prelude::StdRng, Rng};
+use rand::Rng;
+use rand_chacha::ChaCha12Rng;
 use rand_seeder::Seeder;
 use rustc_hash::{FxHashMap, FxHashSet};
 
 use crate::files::FileAccess;
-use crate::header::{self, Header, HeaderBuild};
-use crate::item::{Item, Message, UberStateOperator};
+use crate::header::{self, Header, HeaderBuild, ItemDetails};
+use crate::item::{Item, Message, Resource, UberStateItem, UberStateOperator, UberStateValue};
 use crate::log;
-use crate::settings::{Goal, HeaderConfig, InlineHeader, UniverseSettings};
-use crate::uber_state::UberStateTrigger;
-use crate::world::{Graph, Pool, World};
-
-use placement::generate_placements;
+use crate::settings::{
+    Accessibility, Goal, HeaderConfig, InlineHeader, ItemPoolChange, KeystoneMode,
+    LocationSelector, MultiworldSettings, PlandoPlacement, UniverseSettings,
+};
+use crate::uber_state::{UberStateComparator, UberStateCondition, UberStateTrigger, UberType};
+use crate::util::constants::{ALGORITHM_VERSION, KEYSTONE_DOORS};
+use crate::world::{graph::Node, Graph, Pool, World};
+
+use hints::generate_hints;
+use placement::{find_location, generate_placements, validate_item_pool, validate_plando};
+
+/// The random number generator used to generate seeds
+///
+/// Unlike [`StdRng`](rand::rngs::StdRng), this algorithm is pinned and won't change between releases of `rand`
+pub type SeedRng = ChaCha12Rng;
+
+/// Creates the [`SeedRng`] that generates seeds for the given seed string and algorithm version
+///
+/// Returns an [`GenerationError::UnsupportedAlgorithmVersion`] if this release of the generator doesn't know the algorithm version
+///
+/// # Examples
+///
+/// ```
+/// # use wotw_seedgen::generator::seed_rng;
+/// use rand::Rng;
+///
+/// let mut rng = seed_rng("seed", 1).unwrap();
+/// let mut same_rng = seed_rng("seed", 1).unwrap();
+/// assert_eq!(rng.gen::<u64>(), same_rng.gen::<u64>());
+///
+/// assert!(seed_rng("seed", 0).is_err());
+/// ```
+pub fn seed_rng(seed: &str, algorithm_version: u32) -> Result<SeedRng, GenerationError> {
+    match algorithm_version {
+        // ChaCha12 seeded from a SipHash of the seed string, like rand 0.8's StdRng which the generator used before algorithm versions existed
+        1 => Ok(Seeder::from(seed).make_rng()),
+        _ => Err(GenerationError::UnsupportedAlgorithmVersion {
+            version: algorithm_version,
+            supported: ALGORITHM_VERSION,
+        }),
+    }
+}
 
 pub fn generate_seed<'graph, 'settings>(
     graph: &'graph Graph,
     file_access: &impl FileAccess,
     settings: &'settings UniverseSettings,
-) -> Result<Seed<'graph, 'settings>, String> {
-    let mut rng: StdRng = Seeder::from(&settings.seed).make_rng();
-    log::trace!("Seeded RNG with {}", settings.seed);
+) -> Result<Seed<'graph, 'settings>, GenerationError> {
+    generate_seed_with_observer(graph, file_access, settings, &mut |_: GenerationEvent| {
+        GenerationControl::Continue
+    })
+}
+
+/// Like [`generate_seed`], but reports progress to the `observer`, which may cancel the generation
+///
+/// # Examples
+///
+/// ```
+/// # use wotw_seedgen::generator::{generate_seed_with_observer, GenerationControl, GenerationError, GenerationEvent};
+/// use wotw_seedgen::files::{self, FILE_SYSTEM_ACCESS};
+/// use wotw_seedgen::logic;
+/// use wotw_seedgen::settings::UniverseSettings;
+///
+/// let mut settings = UniverseSettings::default();
+/// settings.seed = "seed".to_string();
+/// let areas = files::read_file("areas", "wotw", "logic").unwrap();
+/// let locations = files::read_file("loc_data", "csv", "logic").unwrap();
+/// let states = files::read_file("state_data", "csv", "logic").unwrap();
+/// let graph = logic::parse_logic(&areas, &locations, &states, &settings, false).unwrap();
+///
+/// let mut steps = 0;
+/// let result = generate_seed_with_observer(&graph, &FILE_SYSTEM_ACCESS, &settings, &mut |event: GenerationEvent| {
+///     if let GenerationEvent::SpoilerGroupFinalized { .. } = event {
+///         steps += 1;
+///     }
+///     if steps < 3 {
+///         GenerationControl::Continue
+///     } else {
+///         GenerationControl::Cancel
+///     }
+/// });
+///
+/// assert!(matches!(result, Err(GenerationError::Cancelled)));
+/// assert_eq!(steps, 3);
+/// ```
+pub fn generate_seed_with_observer<'graph, 'settings>(
+    graph: &'graph Graph,
+    file_access: &impl FileAccess,
+    settings: &'settings UniverseSettings,
+    observer: &mut dyn GenerationObserver,
+) -> Result<Seed<'graph, 'settings>, GenerationError> {
+    let mut rng = seed_rng(&settings.seed, settings.algorithm_version)?;
+    log::trace!(
+        "Seeded RNG with {} using algorithm version {}",
+        settings.seed,
+        settings.algorithm_version
+    );
+
+    validate_multiworld_settings(&settings.multiworld)?;
 
     let (worlds, (flags, headers)): (Vec<_>, (Vec<_>, Vec<_>)) = settings
         .world_settings
         .iter()
-        .map(|world_settings| {
+        .enumerate()
+        .map(|(world_index, world_settings)| {
             let mut world = World::new_spawn(graph, world_settings);
-            world.pool = Pool::preset();
+            world.pool = Pool::preset(world_settings, graph);
+            let keystone_doors = prepare_keystone_doors(&mut world);
 
-            let (goals, flags, headers) = parse_headers(&mut world, file_access, &mut rng)?;
+            let (goals, flags, mut headers) =
+                parse_headers(world_index, &mut world, file_access, &mut rng)?;
+            headers.push_str(&keystone_doors);
             world.goals = goals;
+            apply_item_pool(world_index, &mut world)?;
+            grant_starting_items(world_index, &mut world)?;
+            preplace_plando(world_index, &mut world)?;
+            validate_excluded_locations(world_index, &world)?;
+            validate_placement_constraints(world_index, &world)?;
+            validate_shop_prices(world_index, &world)?;
 
             Ok((world, (flags, headers)))
         })
-        .collect::<Result<Vec<_>, String>>()?
+        .collect::<Result<Vec<_>, GenerationError>>()?
         .into_iter()
         .unzip();
 
-    let (mut worlds, spoiler) = generate_placements(graph, &worlds, &mut rng)?;
+    let (mut seed_worlds, spoiler, failed_attempts) =
+        generate_placements(graph, &worlds, settings, observer, &mut rng)?;
 
-    for ((world, flags), headers) in worlds.iter_mut().zip(flags).zip(headers) {
+    let spawns = seed_worlds.iter().map(|world| world.spawn).collect();
+    let hints = generate_hints(&worlds, spawns, &spoiler, &mut rng);
+
+    for (((world, flags), headers), hints) in
+        seed_worlds.iter_mut().zip(flags).zip(headers).zip(hints)
+    {
         world.flags = flags;
         world.headers = headers;
+        world.hints = hints;
     }
 
     Ok(Seed {
-        worlds,
+        worlds: seed_worlds,
         graph,
         settings,
         spoiler,
+        failed_attempts,
+        initial_worlds: worlds,
     })
 }
 
 fn parse_headers(
+    world_index: usize,
     world: &mut World,
     file_access: &impl FileAccess,
     rng: &mut impl Rng,
-) -> Result<(Vec<Goal>, Vec<String>, String), String> {
+) -> Result<(Vec<Goal>, Vec<String>, String), GenerationError> {
     validate_header_names(
+        world_index,
         &world.player.settings.headers,
         &world.player.settings.inline_headers,
     )?;
 
-    let mut config_map = build_config_map(&world.player.settings.header_config)?;
+    let mut config_map = build_config_map(world_index, &world.player.settings.header_config)?;
 
     let mut headers = vec![];
     let mut includes = FxHashSet::default();
     includes.extend(world.player.settings.headers.iter().cloned());
 
     for header_name in &world.player.settings.headers {
-        let header = file_access.read_header(header_name)?;
+        let header = read_header(world_index, header_name, file_access)?;
         parse_header(
+            world_index,
             header_name.clone(),
             header,
             &mut headers,
@@ -98,6 +216,7 @@ fn parse_headers(
             .unwrap_or_else(|| "Anonymous Header".to_string());
         let header = inline_header.content.clone();
         parse_header(
+            world_index,
             header_name,
             header,
             &mut headers,
@@ -124,6 +243,12 @@ fn parse_headers(
     if world.player.settings.hard {
         flags.push("Hard".to_string());
     }
+    if world.player.settings.keystone_mode != KeystoneMode::KeysAnywhere {
+        flags.push(world.player.settings.keystone_mode.to_string());
+    }
+    if world.player.settings.accessibility == Accessibility::Beatable {
+        flags.push(world.player.settings.accessibility.to_string());
+    }
 
     let header_names = headers
         .into_iter()
@@ -162,9 +287,10 @@ fn parse_headers(
             for (item, details) in header.item_details {
                 let display = item.to_string();
                 if world.custom_items.insert(item, details).is_some() {
-                    return Err(format!(
-                        "multiple headers tried to customize the item {display}"
-                    ));
+                    return Err(GenerationError::ConflictingItemDetails {
+                        world_index,
+                        item: display,
+                    });
                 }
             }
 
@@ -176,9 +302,11 @@ fn parse_headers(
 
     for header_name in &header_names {
         if let Some(other) = excludes.get(header_name) {
-            return Err(format!(
-                "headers {other} and {header_name} are incompatible"
-            ));
+            return Err(GenerationError::IncompatibleHeaders {
+                world_index,
+                header_name: header_name.clone(),
+                excluded_by: other.clone(),
+            });
         }
     }
     for header_with_parameters in config_map.keys() {
@@ -208,7 +336,22 @@ fn parse_headers(
     Ok((goals, flags, header_block))
 }
 
+fn read_header(
+    world_index: usize,
+    header_name: &str,
+    file_access: &impl FileAccess,
+) -> Result<String, GenerationError> {
+    file_access
+        .read_header(header_name)
+        .map_err(|message| GenerationError::ReadHeader {
+            world_index,
+            header_name: header_name.to_string(),
+            message,
+        })
+}
+
 fn parse_header(
+    world_index: usize,
     header_name: String,
     header: String,
     headers: &mut Vec<(String, HeaderBuild)>,
@@ -216,25 +359,29 @@ fn parse_header(
     config_map: &mut FxHashMap<String, FxHashMap<String, String>>,
     file_access: &impl FileAccess,
     rng: &mut impl Rng,
-) -> Result<(), String> {
+) -> Result<(), GenerationError> {
     log::trace!("Parsing header {header_name}");
 
     let header_config = config_map.remove(&header_name).unwrap_or_default();
 
     let header = Header::parse(header, rng)
-        .map_err(|err| {
-            format!(
-                "Error in header {}:\n{}",
-                header_name,
-                err.verbose_display()
-            )
+        .map_err(|err| GenerationError::HeaderParse {
+            world_index,
+            header_name: header_name.clone(),
+            message: err.verbose_display(),
         })?
-        .build(header_config)?;
+        .build(header_config)
+        .map_err(|message| GenerationError::HeaderBuild {
+            world_index,
+            header_name: header_name.clone(),
+            message,
+        })?;
 
     for include in &header.includes {
         if includes.insert(include.clone()) {
-            let header = file_access.read_header(include)?;
+            let header = read_header(world_index, include, file_access)?;
             parse_header(
+                world_index,
                 include.clone(),
                 header,
                 headers,
@@ -253,15 +400,17 @@ fn parse_header(
 
 /// verifies that inline headers don't claim names already in use
 fn validate_header_names(
+    world_index: usize,
     headers: &FxHashSet<String>,
     inline_headers: &[InlineHeader],
-) -> Result<(), String> {
+) -> Result<(), GenerationError> {
     for inline_header in inline_headers {
         if let Some(name) = &inline_header.name {
             if headers.contains(name) {
-                return Err(format!(
-                    "Ambiguous name: {name} used both as a file header and an inline header"
-                ));
+                return Err(GenerationError::AmbiguousHeaderName {
+                    world_index,
+                    name: name.clone(),
+                });
             }
         }
     }
@@ -270,8 +419,9 @@ fn validate_header_names(
 }
 
 fn build_config_map(
+    world_index: usize,
     header_config: &[HeaderConfig],
-) -> Result<FxHashMap<String, FxHashMap<String, String>>, String> {
+) -> Result<FxHashMap<String, FxHashMap<String, String>>, GenerationError> {
     let mut config_map = FxHashMap::<String, FxHashMap<_, _>>::default();
 
     for config in header_config {
@@ -281,7 +431,13 @@ fn build_config_map(
             .insert(config.config_name.clone(), config.config_value.clone())
         {
             if prior != config.config_value {
-                return Err(format!("provided multiple values for configuration parameter {} for header {} ({} and {})", config.config_name, config.header_name, prior, config.config_value));
+                return Err(GenerationError::ConflictingHeaderConfig {
+                    world_index,
+                    header_name: config.header_name.clone(),
+                    config_name: config.config_name.clone(),
+                    first_value: prior,
+                    second_value: config.config_value.clone(),
+                });
             }
         }
     }
@@ -289,6 +445,273 @@ fn build_config_map(
     Ok(config_map)
 }
 
+/// Names the dedicated keys and makes their doors open once all of their keys are collected
+///
+/// Returns the seed lines needed to open the doors in-game
+fn prepare_keystone_doors(world: &mut World) -> String {
+    let keystone_mode = world.player.settings.keystone_mode;
+    let mut seed_lines = String::new();
+    if keystone_mode == KeystoneMode::KeysAnywhere {
+        return seed_lines;
+    }
+
+    for (door_index, &(identifier, _, keystones)) in KEYSTONE_DOORS.iter().enumerate() {
+        // The logic refuses to build without key states if any world needs them
+        let key_identifier = match world.graph.keystone_door_keys[door_index] {
+            Some(key_state) => key_state,
+            None => continue,
+        };
+        let (key, amount) = keystone_mode.door_key(key_identifier, keystones);
+        let door_trigger = match world
+            .graph
+            .nodes
+            .iter()
+            .find(|node| node.identifier() == identifier)
+            .and_then(Node::trigger)
+        {
+            Some(trigger) => trigger,
+            None => {
+                log::warning!("Keystone door {} not found in the logic", identifier);
+                continue;
+            }
+        };
+
+        world.custom_items.insert(
+            key.clone(),
+            ItemDetails {
+                name: keystone_mode.door_key_name(door_index),
+                price: Some(Item::Resource(Resource::Keystone).shop_price() * keystones / amount),
+                icon: Resource::Keystone.icon(),
+                ..ItemDetails::default()
+            },
+        );
+
+        let open = Placement {
+            node: None,
+            trigger: UberStateTrigger {
+                identifier: key_identifier,
+                condition: Some(UberStateCondition {
+                    comparator: UberStateComparator::GreaterOrEquals,
+                    value: keystones,
+                }),
+            },
+            item: UberStateItem::simple_setter(
+                door_trigger.identifier,
+                UberType::Bool,
+                UberStateValue::Bool(true),
+            ),
+            hide_message: true,
+        };
+        writeln!(seed_lines, "{}", open.code()).unwrap();
+        world.preplace(open.trigger, open.item);
+    }
+
+    seed_lines
+}
+
+/// Takes the starting items out of the item pool and grants them on spawn
+fn grant_starting_items(world_index: usize, world: &mut World) -> Result<(), GenerationError> {
+    for item in &world.player.settings.starting_items {
+        log::trace!("Granting {} on spawn", item);
+        take_from_pool(world_index, world, item)?;
+        world.preplace(UberStateTrigger::spawn(), item.clone());
+    }
+
+    Ok(())
+}
+
+/// Removes one of `item` from the item pool, failing if the pool doesn't contain it
+fn take_from_pool(
+    world_index: usize,
+    world: &mut World,
+    item: &Item,
+) -> Result<(), GenerationError> {
+    let (name, available, removing) = if let Item::SpiritLight(amount) = item {
+        ("Spirit Light".to_string(), world.pool.spirit_light, *amount)
+    } else {
+        (item.to_string(), world.pool.inventory.get(item), 1)
+    };
+    if available < removing {
+        return Err(GenerationError::ItemPoolUnderflow {
+            world_index,
+            item: name,
+            removed: removing,
+            available,
+        });
+    }
+
+    world.pool.remove(item, 1);
+    Ok(())
+}
+
+fn apply_item_pool(world_index: usize, world: &mut World) -> Result<(), GenerationError> {
+    let item_pool = &world.player.settings.item_pool;
+    if item_pool.changes.is_empty() && item_pool.spirit_light.is_none() {
+        return Ok(());
+    }
+
+    let unchanged = world.clone();
+    let mut removed = Vec::new();
+
+    for ItemPoolChange { item, amount } in &item_pool.changes {
+        let count = amount.unsigned_abs();
+        let overflow = || GenerationError::ItemPoolOverflow {
+            world_index,
+            item: "Spirit Light".to_string(),
+        };
+        let spirit_light = match item {
+            Item::SpiritLight(stacked_amount) => {
+                Some(stacked_amount.checked_mul(count).ok_or_else(overflow)?)
+            }
+            _ => None,
+        };
+        if *amount >= 0 {
+            if let Some(spirit_light) = spirit_light {
+                world.pool.spirit_light = world
+                    .pool
+                    .spirit_light
+                    .checked_add(spirit_light)
+                    .ok_or_else(overflow)?;
+            } else {
+                world.pool.grant(item.clone(), count);
+            }
+            continue;
+        }
+
+        let (name, available, removing) = if let Some(spirit_light) = spirit_light {
+            (
+                "Spirit Light".to_string(),
+                world.pool.spirit_light,
+                spirit_light,
+            )
+        } else {
+            (item.to_string(), world.pool.inventory.get(item), count)
+        };
+        if available < removing {
+            return Err(GenerationError::ItemPoolUnderflow {
+                world_index,
+                item: name,
+                removed: removing,
+                available,
+            });
+        }
+
+        world.pool.remove(item, count);
+        removed.push(item.to_string());
+    }
+
+    if let Some(spirit_light) = item_pool.spirit_light {
+        if spirit_light < world.pool.spirit_light {
+            removed.push(Item::SpiritLight(world.pool.spirit_light - spirit_light).to_string());
+        }
+        world.pool.spirit_light = spirit_light;
+    }
+
+    log::trace!(
+        "(World {}): Applied {} item pool changes",
+        world_index,
+        item_pool.changes.len()
+    );
+
+    validate_item_pool(world_index, &unchanged, world, removed)
+}
+
+fn preplace_plando(world_index: usize, world: &mut World) -> Result<(), GenerationError> {
+    let settings = world.player.settings;
+    let unchanged = world.clone();
+
+    for PlandoPlacement { location, item } in &settings.plando {
+        let trigger = find_location(world.graph, location)
+            .and_then(|node| node.trigger())
+            .ok_or_else(|| GenerationError::UnknownPlandoLocation {
+                world_index,
+                location: location.clone(),
+            })?
+            .clone();
+        if world.preplacements.contains_key(&trigger) {
+            return Err(GenerationError::ConflictingPlandoPlacement {
+                world_index,
+                location: location.clone(),
+            });
+        }
+
+        log::trace!("(World {}): Pinning {} to {}", world_index, item, location);
+        take_from_pool(world_index, world, item)?;
+        world.preplace(trigger, item.clone());
+    }
+
+    validate_plando(world_index, &unchanged, world)
+}
+
+fn validate_excluded_locations(world_index: usize, world: &World) -> Result<(), GenerationError> {
+    for excluded in &world.player.settings.excluded_locations {
+        if let LocationSelector::Location(location) = excluded {
+            if find_location(world.graph, location).is_none() {
+                return Err(GenerationError::UnknownExcludedLocation {
+                    world_index,
+                    location: location.clone(),
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_placement_constraints(
+    world_index: usize,
+    world: &World,
+) -> Result<(), GenerationError> {
+    // Step 0 only contains priority placements, nothing constrained can be placed that early
+    if let Some(constraint) = world
+        .player
+        .settings
+        .placement_constraints
+        .iter()
+        .find(|constraint| constraint.max_step == Some(0))
+    {
+        return Err(GenerationError::UnsatisfiableConstraint {
+            world_index,
+            constraint: constraint.to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+fn validate_shop_prices(world_index: usize, world: &World) -> Result<(), GenerationError> {
+    let shop_prices = &world.player.settings.shop_prices;
+    let (min, max) = (shop_prices.min_multiplier, shop_prices.max_multiplier);
+    if !(min.is_finite() && max.is_finite() && 0. <= min && min <= max) {
+        return Err(GenerationError::InvalidPriceRange {
+            world_index,
+            min,
+            max,
+        });
+    }
+
+    Ok(())
+}
+
+fn validate_multiworld_settings(multiworld: &MultiworldSettings) -> Result<(), GenerationError> {
+    if let Some(category) = multiworld
+        .local_items
+        .iter()
+        .find(|category| multiworld.shared_items.contains(category))
+    {
+        return Err(GenerationError::ConflictingItemDistribution {
+            category: category.to_string(),
+        });
+    }
+    if let Some(balance) = multiworld.progression_balance {
+        if !(0.0..=1.0).contains(&balance) {
+            return Err(GenerationError::InvalidProgressionBalance { balance });
+        }
+    }
+
+    Ok(())
+}
+
 fn block_spawn_sets(preplacement: &header::Pickup, world: &mut World) {
     if let Item::UberState(uber_state_item) = &preplacement.item {
         if preplacement.trigger != UberStateTrigger::spawn() {
@@ -317,3 +740,238 @@ fn block_spawn_sets(preplacement: &header::Pickup, world: &mut World) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        files::FILE_SYSTEM_ACCESS,
+        item::{BonusItem, Skill},
+        settings::{
+            InlineHeader, ItemPoolSettings, PlacementConstraint, RetryPolicy, Spawn, WorldSettings,
+        },
+        test_util::{test_graph, test_seed},
+        util::{constants::DEFAULT_SPAWN, Zone},
+    };
+
+    /// Settings that fail every attempt, since there is no Willow location to place Double Jump on in the first step
+    fn failing_settings(retry_policy: RetryPolicy) -> UniverseSettings {
+        UniverseSettings {
+            world_settings: vec![WorldSettings {
+                placement_constraints: vec![PlacementConstraint {
+                    item: Item::Skill(Skill::DoubleJump),
+                    zones: vec![Zone::Willow],
+                    max_step: Some(1),
+                }],
+                ..WorldSettings::default()
+            }],
+            retry_policy,
+            ..UniverseSettings::default()
+        }
+    }
+
+    #[test]
+    fn item_pool_overflow() {
+        let universe_settings = UniverseSettings {
+            world_settings: vec![WorldSettings {
+                item_pool: ItemPoolSettings {
+                    changes: vec![ItemPoolChange {
+                        item: Item::SpiritLight(u32::MAX / 2),
+                        amount: 3,
+                    }],
+                    spirit_light: None,
+                },
+                ..WorldSettings::default()
+            }],
+            ..UniverseSettings::default()
+        };
+        let graph = test_graph(&universe_settings);
+
+        assert!(matches!(
+            generate_seed(&graph, &FILE_SYSTEM_ACCESS, &universe_settings),
+            Err(GenerationError::ItemPoolOverflow { .. })
+        ));
+    }
+
+    #[test]
+    fn starting_items_from_item_pool_changes() {
+        let bonus_item = Item::BonusItem(BonusItem::ExtraDoubleJump);
+        let universe_settings = UniverseSettings {
+            world_settings: vec![WorldSettings {
+                item_pool: ItemPoolSettings {
+                    changes: vec![ItemPoolChange {
+                        item: bonus_item.clone(),
+                        amount: 1,
+                    }],
+                    spirit_light: None,
+                },
+                starting_items: vec![bonus_item.clone()],
+                ..WorldSettings::default()
+            }],
+            ..UniverseSettings::default()
+        };
+        let (_, seed) = test_seed(universe_settings);
+        let placed = seed
+            .spoiler
+            .groups
+            .iter()
+            .flat_map(|group| &group.placements)
+            .filter(|placement| placement.item == bonus_item)
+            .count();
+        assert_eq!(placed, 0);
+    }
+
+    #[test]
+    fn starting_items_not_in_item_pool() {
+        let universe_settings = UniverseSettings {
+            world_settings: vec![WorldSettings {
+                starting_items: vec![Item::BonusItem(BonusItem::ExtraDoubleJump)],
+                ..WorldSettings::default()
+            }],
+            ..UniverseSettings::default()
+        };
+        let graph = test_graph(&universe_settings);
+
+        assert!(matches!(
+            generate_seed(&graph, &FILE_SYSTEM_ACCESS, &universe_settings),
+            Err(GenerationError::ItemPoolUnderflow { available: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn retry_attempts() {
+        let universe_settings = failing_settings(RetryPolicy {
+            attempts: 3,
+            repick_spawns: false,
+            time_budget_ms: None,
+        });
+        let graph = test_graph(&universe_settings);
+
+        match generate_seed(&graph, &FILE_SYSTEM_ACCESS, &universe_settings).err() {
+            Some(GenerationError::AttemptsExhausted { attempts }) => {
+                assert_eq!(attempts.len(), 3);
+                for attempt in attempts {
+                    assert_eq!(attempt.spawns, vec![DEFAULT_SPAWN.to_string()]);
+                    assert!(matches!(
+                        attempt.error,
+                        GenerationError::UnsatisfiableConstraint { world_index: 0, .. }
+                    ));
+                }
+            }
+            other => panic!("expected exhausted attempts, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn retry_time_budget() {
+        let universe_settings = failing_settings(RetryPolicy {
+            time_budget_ms: Some(0),
+            ..RetryPolicy::default()
+        });
+        let graph = test_graph(&universe_settings);
+
+        match generate_seed(&graph, &FILE_SYSTEM_ACCESS, &universe_settings).err() {
+            Some(GenerationError::TimeBudgetExceeded {
+                time_budget_ms,
+                attempts,
+            }) => {
+                assert_eq!(time_budget_ms, 0);
+                assert_eq!(attempts.len(), 1);
+            }
+            other => panic!("expected an exceeded time budget, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn errors_name_their_world() {
+        let universe_settings = UniverseSettings {
+            world_settings: vec![
+                WorldSettings::default(),
+                WorldSettings {
+                    spawn: Spawn::Set("Nowhere".to_string()),
+                    ..WorldSettings::default()
+                },
+            ],
+            ..UniverseSettings::default()
+        };
+        let graph = test_graph(&universe_settings);
+        assert!(matches!(
+            generate_seed(&graph, &FILE_SYSTEM_ACCESS, &universe_settings),
+            Err(GenerationError::InvalidSpawn { world_index: 1, .. })
+        ));
+
+        let universe_settings = UniverseSettings {
+            world_settings: vec![WorldSettings {
+                inline_headers: vec![InlineHeader {
+                    name: Some("broken".to_string()),
+                    content: "!!".to_string(),
+                }],
+                ..WorldSettings::default()
+            }],
+            ..UniverseSettings::default()
+        };
+        let graph = test_graph(&universe_settings);
+        match generate_seed(&graph, &FILE_SYSTEM_ACCESS, &universe_settings).err() {
+            Some(GenerationError::HeaderParse {
+                world_index,
+                header_name,
+                ..
+            }) => {
+                assert_eq!(world_index, 0);
+                assert_eq!(header_name, "broken");
+            }
+            other => panic!("expected a header parse error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn observer_cancels_retries() {
+        let universe_settings = failing_settings(RetryPolicy::default());
+        let graph = test_graph(&universe_settings);
+
+        let mut spawns_chosen = 0;
+        let mut failed_attempts = 0;
+        let result = generate_seed_with_observer(
+            &graph,
+            &FILE_SYSTEM_ACCESS,
+            &universe_settings,
+            &mut |event: GenerationEvent| match event {
+                GenerationEvent::SpawnsChosen { .. } => {
+                    spawns_chosen += 1;
+                    GenerationControl::Continue
+                }
+                GenerationEvent::AttemptFailed { .. } => {
+                    failed_attempts += 1;
+                    GenerationControl::Cancel
+                }
+                _ => GenerationControl::Continue,
+            },
+        );
+
+        assert!(matches!(result, Err(GenerationError::Cancelled)));
+        assert_eq!(spawns_chosen, 1);
+        assert_eq!(failed_attempts, 1);
+    }
+
+    #[test]
+    fn observer_sees_every_step() {
+        let universe_settings = UniverseSettings::default();
+        let graph = test_graph(&universe_settings);
+
+        let mut steps = Vec::new();
+        let seed = generate_seed_with_observer(
+            &graph,
+            &FILE_SYSTEM_ACCESS,
+            &universe_settings,
+            &mut |event: GenerationEvent| {
+                if let GenerationEvent::SpoilerGroupFinalized { step, .. } = event {
+                    steps.push(step);
+                }
+                GenerationControl::Continue
+            },
+        )
+        .unwrap();
+
+        assert_eq!(steps, (0..seed.spoiler.groups.len()).collect::<Vec<_>>());
+    }
+}
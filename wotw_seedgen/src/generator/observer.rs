@@ -0,0 +1,57 @@
+use crate::Inventory;
+
+use super::{FailedAttempt, SpoilerGroup};
+
+/// Something noteworthy that happened during seed generation
+///
+/// See [`GenerationObserver`] to receive these
+#[derive(Debug, Clone, Copy)]
+pub enum GenerationEvent<'a> {
+    /// A new attempt to generate placements started on these spawns
+    ///
+    /// `attempt` counts from 0 and increases with every retry
+    SpawnsChosen {
+        attempt: usize,
+        spawns: &'a [String],
+    },
+    /// The placements of one step are complete
+    ///
+    /// `step` is the index the group will have in [`SeedSpoiler::groups`](super::SeedSpoiler::groups)
+    SpoilerGroupFinalized {
+        step: usize,
+        group: &'a SpoilerGroup,
+    },
+    /// The generator got stuck and picked these items to place as forced progression for a world
+    ForcedProgression {
+        world_index: usize,
+        items: &'a Inventory,
+    },
+    /// An attempt to generate placements failed, the generator may retry afterwards
+    AttemptFailed { attempt: &'a FailedAttempt },
+}
+
+/// Whether seed generation should go on after a [`GenerationEvent`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GenerationControl {
+    Continue,
+    /// Stop generating, resulting in [`GenerationError::Cancelled`](super::GenerationError::Cancelled)
+    Cancel,
+}
+
+/// Receives [`GenerationEvent`]s to report progress while generating a seed and may cancel the generation
+///
+/// Any `FnMut(GenerationEvent) -> GenerationControl` closure is an observer
+///
+/// See [`generate_seed_with_observer`](super::generate_seed_with_observer)
+pub trait GenerationObserver {
+    fn on_event(&mut self, event: GenerationEvent) -> GenerationControl;
+}
+
+impl<F> GenerationObserver for F
+where
+    F: FnMut(GenerationEvent) -> GenerationControl,
+{
+    fn on_event(&mut self, event: GenerationEvent) -> GenerationControl {
+        self(event)
+    }
+}
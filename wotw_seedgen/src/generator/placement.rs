@@ -1,10 +1,15 @@
-use std::mem;
+use std::{
+    cmp::Reverse,
+    mem,
+    time::{Duration, Instant},
+};
 
 use rand::{
     distributions::{Bernoulli, Distribution, Uniform},
     seq::{IteratorRandom, SliceRandom},
     Rng,
 };
+use rustc_hash::{FxHashMap, FxHashSet};
 
 #[cfg(feature = "log")]
 use crate::settings::Difficulty;
@@ -13,20 +18,27 @@ use crate::{
     header::CodeDisplay,
     inventory::Inventory,
     item::{
-        Command, Item, Message, Resource, ShopCommand, Teleporter, UberStateItem, UberStateValue,
+        BonusItem, Command, Item, ItemCategory, Message, Resource, ShopCommand, Teleporter,
+        UberStateItem, UberStateValue,
     },
     log,
-    settings::{Goal, Spawn, WorldSettings},
+    settings::{
+        Accessibility, FillAlgorithm, Goal, KeystoneMode, MultiworldSettings, PlacementConstraint,
+        PlacementStrategy, PlandoPlacement, Spawn, SpiritLightDistribution, UniverseSettings,
+        WorldSettings,
+    },
     util::{
         self,
         constants::{
-            DEFAULT_SPAWN, KEYSTONE_DOORS, PLACEHOLDER_SLOTS, RANDOM_PROGRESSION, RELIC_ZONES,
-            RESERVE_SLOTS, RETRIES, SHOP_PRICES,
+            BALANCED_DEVIATION_EXPONENT, DEFAULT_SPAWN, EARLY_REACH_SAMPLES, KEYSTONE_DOORS,
+            MULTIWORLD_UBER_GROUP, PLACEHOLDER_SLOTS, RANDOM_PROGRESSION, RELIC_ZONES,
+            RESERVE_SLOTS, SHOP_PRICES, TREE_UBER_GROUP, WISP_STATES,
         },
+        NodeKind, Zone,
     },
     world::{
         graph::{self, Graph, Node},
-        requirement, World,
+        requirement, Pool, World,
     },
 };
 use crate::{
@@ -35,7 +47,10 @@ use crate::{
 };
 
 use super::seed::SeedWorld;
-use super::spoiler::{SeedSpoiler, SpoilerGroup, SpoilerPlacement};
+use super::spoiler::{LogicPath, SeedSpoiler, SpiritLightSummary, SpoilerGroup, SpoilerPlacement};
+use super::{
+    FailedAttempt, GenerationControl, GenerationError, GenerationEvent, GenerationObserver,
+};
 
 #[derive(Debug, Clone)]
 /// One [`Item`] tied to an [`UberStateTrigger`]
@@ -67,14 +82,48 @@ struct WorldContext<'a, 'b> {
     placements: Vec<Placement<'a>>,
     placeholders: Vec<&'a Node>,
     collected_preplacements: Vec<usize>,
+    plando: Vec<(&'a Node, Item)>,
+    excluded_locations: Vec<&'a Node>,
     spawn_slots: usize,
     prevent_sharing: usize,
     reachable_locations: Vec<&'a Node>,
     unreachable_locations: Vec<&'a Node>,
-    spirit_light_rng: SpiritLightAmounts, // TODO this can get kinda weird maybe have a shared spirit light rng instead
+    spirit_light_rng: SpiritLightAmounts,
     random_spirit_light: Bernoulli,
     shop_slots: usize,
-    world_tour: Option<usize>,
+    price_range: Uniform<f32>,
+    /// The prices assigned so far, which may still be scaled down to fit the shop's budget
+    shop_prices: Vec<ShopPrice>,
+    world_tour: Option<WorldTour>,
+}
+
+/// A shop price that was placed as a setter in [`WorldContext::placements`]
+struct ShopPrice {
+    placement_index: usize,
+    /// The uber group of the shop selling the item
+    shop: u16,
+    uber_identifier: UberIdentifier,
+    price: u32,
+}
+
+/// How to place the Relics of a world, resolved from its relic [`Goal`]
+struct WorldTour {
+    amount: usize,
+    min: usize,
+    zones: Vec<Zone>,
+    avoid_shops_and_quests: bool,
+    avoid_late_locations: bool,
+}
+impl WorldTour {
+    fn new(amount: usize) -> Self {
+        Self {
+            amount,
+            min: 0,
+            zones: RELIC_ZONES.to_vec(),
+            avoid_shops_and_quests: false,
+            avoid_late_locations: false,
+        }
+    }
 }
 
 struct GeneratorContext<'a, R, I>
@@ -87,23 +136,118 @@ where
     multiworld_state_index: I,
     spoiler_groups: Vec<SpoilerGroup>,
     current_spoiler_group: SpoilerGroup,
-    price_range: Uniform<f32>,
     random_progression: Bernoulli,
+    multiworld: &'a MultiworldSettings,
+    placement_strategy: PlacementStrategy,
+    /// Whether to record how new reachables were reached in the spoiler groups
+    spoiler_paths: bool,
+    /// How many progression items have been placed, counting only items that could be sent to other worlds
+    progression_placements: usize,
+    /// How many of the `progression_placements` were placed in a different world than their own
+    shared_progression_placements: usize,
+    observer: &'a mut dyn GenerationObserver,
     rng: &'a mut R,
 }
 
 impl<R: Rng, I: Iterator<Item = u16>> GeneratorContext<'_, R, I> {
-    fn finalize_spoiler_group(&mut self) {
+    fn finalize_spoiler_group(&mut self) -> Result<(), GenerationError> {
         self.current_spoiler_group
             .placements
             .sort_unstable_by(|a, b| a.item.cmp(&b.item));
         self.spoiler_groups
             .push(mem::take(&mut self.current_spoiler_group));
+
+        let step = self.spoiler_groups.len() - 1;
+        notify(
+            self.observer,
+            GenerationEvent::SpoilerGroupFinalized {
+                step,
+                group: &self.spoiler_groups[step],
+            },
+        )
+    }
+
+    /// The index of the spoiler group currently being placed into
+    fn current_step(&self) -> usize {
+        self.spoiler_groups.len()
+    }
+
+    /// Whether fewer progression items have been sent to other worlds than the progression balance asks for
+    ///
+    /// Returns [`None`] if no progression balance applies
+    fn progression_balance_unmet(&self) -> Option<bool> {
+        match self.multiworld.progression_balance {
+            Some(balance) if self.world_count > 1 => {
+                #[allow(clippy::cast_precision_loss)]
+                let shared_fraction = if self.progression_placements == 0 {
+                    0.
+                } else {
+                    self.shared_progression_placements as f32 / self.progression_placements as f32
+                };
+
+                Some(shared_fraction < balance)
+            }
+            _ => None,
+        }
+    }
+
+    /// Chooses which world should receive an item placed in the origin world, following the progression balance if one is set
+    fn pick_target_world(&mut self, origin_world_index: usize) -> usize {
+        match self.progression_balance_unmet() {
+            Some(true) => {
+                let index = self.rng.gen_range(0..self.world_count - 1);
+                if index >= origin_world_index {
+                    index + 1
+                } else {
+                    index
+                }
+            }
+            Some(false) => origin_world_index,
+            None => self.rng.gen_range(0..self.world_count),
+        }
+    }
+
+    /// Whether an item of the category should stay in its own world if possible (`Some(true)`) or only if no other world has space (`Some(false)`)
+    ///
+    /// Shared categories always avoid their own world, otherwise the progression balance decides
+    fn keep_in_own_world(&self, category: ItemCategory) -> Option<bool> {
+        if self.multiworld.is_shared(category) {
+            Some(false)
+        } else {
+            self.progression_balance_unmet().map(|unmet| !unmet)
+        }
+    }
+
+    /// Shuffles the worlds an item for the target world could be placed in, moving the target world according to [`keep_in_own_world`](Self::keep_in_own_world)
+    fn origin_world_order(
+        &mut self,
+        target_world_index: usize,
+        category: ItemCategory,
+    ) -> Vec<usize> {
+        let mut indices = (0..self.world_count).collect::<Vec<_>>();
+        indices.shuffle(self.rng);
+        match self.keep_in_own_world(category) {
+            Some(true) => {
+                indices.retain(|&index| index != target_world_index);
+                indices.insert(0, target_world_index);
+            }
+            Some(false) => deprioritize_world(&mut indices, target_world_index),
+            None => {}
+        }
+        indices
     }
 }
 
+/// Moves the target world to the end of the origin world candidates, so it only gets used if no other world has space
+fn deprioritize_world(world_indices: &mut Vec<usize>, world_index: usize) {
+    world_indices.retain(|&index| index != world_index);
+    world_indices.push(world_index);
+}
+
 struct ReachContext<'a> {
     reachable: Vec<graph::Reached<'a>>,
+    /// How the reachables of each world were reached, only if spoiler paths are enabled
+    paths: Vec<Option<graph::ReachPaths<'a>>>,
     reachable_states: Vec<Vec<&'a Node>>,
     unmet: Vec<graph::Progressions<'a>>,
     reachable_counts: Vec<usize>,
@@ -129,16 +273,25 @@ fn format_identifiers(mut identifiers: Vec<&str>) -> String {
 fn progression_check<'a, R, I>(
     world_contexts: &mut [WorldContext<'a, '_>],
     context: &mut GeneratorContext<'_, R, I>,
-) -> Result<ReachContext<'a>, String>
+) -> Result<ReachContext<'a>, GenerationError>
 where
     R: Rng,
     I: Iterator<Item = u16>,
 {
     let mut reachable = Vec::with_capacity(context.world_count);
+    let mut paths = Vec::with_capacity(context.world_count);
     let mut reachable_states = Vec::with_capacity(context.world_count);
     let mut unmet = Vec::with_capacity(context.world_count);
 
     for world_context in world_contexts {
+        paths.push(context.spoiler_paths.then(|| {
+            world_context.world.graph.reach_paths(
+                &world_context.world.player,
+                world_context.spawn,
+                world_context.world.uber_states(),
+                &world_context.world.sets,
+            )
+        }));
         let (world_reachable, world_unmet) = world_context.world.graph.reached_and_progressions(
             &world_context.world.player,
             world_context.spawn,
@@ -168,6 +321,7 @@ where
 
     Ok(ReachContext {
         reachable,
+        paths,
         reachable_states,
         unmet,
         reachable_counts,
@@ -175,6 +329,34 @@ where
     })
 }
 
+/// Returns the [`PlacementConstraint`] that would be broken by placing `item` on `node` during `step`, if any
+fn violated_constraint<'s>(
+    settings: &'s WorldSettings,
+    item: &Item,
+    node: &Node,
+    step: usize,
+) -> Option<&'s PlacementConstraint> {
+    settings
+        .placement_constraints
+        .iter()
+        .find(|constraint| &constraint.item == item && !constraint.allows(node.zone(), step))
+}
+
+fn unsatisfiable_constraint(
+    world_index: usize,
+    settings: &WorldSettings,
+    item: &Item,
+) -> Option<GenerationError> {
+    settings
+        .placement_constraints
+        .iter()
+        .find(|constraint| &constraint.item == item)
+        .map(|constraint| GenerationError::UnsatisfiableConstraint {
+            world_index,
+            constraint: constraint.to_string(),
+        })
+}
+
 fn place_item<'a, R, I>(
     origin_world_index: usize,
     target_world_index: usize,
@@ -184,7 +366,7 @@ fn place_item<'a, R, I>(
     item: Item,
     world_contexts: &mut [WorldContext<'a, '_>],
     context: &mut GeneratorContext<'_, R, I>,
-) -> Result<(), String>
+) -> Result<(), GenerationError>
 where
     R: Rng,
     I: Iterator<Item = u16>,
@@ -192,6 +374,36 @@ where
     let trigger = node.trigger().unwrap();
     let is_shop = trigger.identifier.is_shop();
 
+    let step = context.current_step();
+    if let Some(constraint) = violated_constraint(
+        world_contexts[target_world_index].world.player.settings,
+        &item,
+        node,
+        step,
+    ) {
+        return Err(GenerationError::ConstraintViolated {
+            world_index: target_world_index,
+            constraint: constraint.to_string(),
+            location: node.identifier().to_string(),
+            step,
+        });
+    }
+
+    if item.is_multiworld_spread()
+        && item.is_progression(
+            world_contexts[target_world_index]
+                .world
+                .player
+                .settings
+                .difficulty,
+        )
+    {
+        context.progression_placements += 1;
+        if origin_world_index != target_world_index {
+            context.shared_progression_placements += 1;
+        }
+    }
+
     if trigger.identifier.is_purchasable() {
         world_contexts[origin_world_index].shop_slots -= 1;
 
@@ -286,7 +498,7 @@ where
             "$[15|5|{}]'s {}",
             target_world_index, custom_name
         )));
-        let send_identifier = UberIdentifier::new(12, state_index);
+        let send_identifier = UberIdentifier::new(MULTIWORLD_UBER_GROUP, state_index);
         let send_item = UberStateItem::simple_setter(
             send_identifier,
             UberType::Bool,
@@ -298,7 +510,7 @@ where
             origin_world_index
         )));
         let target_trigger = UberStateTrigger {
-            identifier: UberIdentifier::new(12, state_index),
+            identifier: UberIdentifier::new(MULTIWORLD_UBER_GROUP, state_index),
             condition: None,
         };
 
@@ -374,6 +586,38 @@ where
     Ok(())
 }
 
+/// Writes a pinned item into the seed once the generator logically collected it
+fn place_plando<'a, R, I>(
+    world_index: usize,
+    node: &'a Node,
+    item: Item,
+    world_contexts: &mut [WorldContext<'a, '_>],
+    context: &mut GeneratorContext<'_, R, I>,
+) -> Result<(), GenerationError>
+where
+    R: Rng,
+    I: Iterator<Item = u16>,
+{
+    // Pinned shop locations were never counted as free shop slots, but place_item will mark them as used
+    if node
+        .trigger()
+        .is_some_and(|trigger| trigger.identifier.is_purchasable())
+    {
+        world_contexts[world_index].shop_slots += 1;
+    }
+
+    place_item(
+        world_index,
+        world_index,
+        node,
+        false,
+        false,
+        item,
+        world_contexts,
+        context,
+    )
+}
+
 fn shop_placement<'a, R, I>(
     node: &Node,
     item: &Item,
@@ -381,7 +625,7 @@ fn shop_placement<'a, R, I>(
     target_world_index: usize,
     world_contexts: &mut [WorldContext<'a, '_>],
     context: &mut GeneratorContext<'_, R, I>,
-) -> Result<(), String>
+) -> Result<(), GenerationError>
 where
     R: Rng,
     I: Iterator<Item = u16>,
@@ -396,9 +640,13 @@ where
         .get(item);
     let trigger = node.trigger().unwrap();
 
-    let (_, _, price_uber_identifier) = SHOP_PRICES.iter()
+    let (_, _, price_uber_identifier) = SHOP_PRICES
+        .iter()
         .find(|(_, location, _)| &trigger.identifier == location)
-        .ok_or_else(|| format!("(World {}): {} claims to be a shop location, but doesn't have an entry in the shop prices table!", origin_world_index, node))?;
+        .ok_or_else(|| GenerationError::MissingShopPrice {
+            world_index: origin_world_index,
+            location: node.to_string(),
+        })?;
 
     let mut price = origin_details
         .and_then(|details| details.price)
@@ -406,15 +654,33 @@ where
         .unwrap_or_else(|| item.shop_price());
 
     if item.random_shop_price() {
-        let modified_price = price as f32 * context.price_range.sample(context.rng);
-        price = util::float_to_int(modified_price).map_err(|_| {
-            format!(
-                "(World {}): Overflowed shop price for {} after adding a random amount to it",
-                origin_world_index, item
-            )
-        })?;
+        let modified_price = price as f32
+            * world_contexts[origin_world_index]
+                .price_range
+                .sample(context.rng);
+        price =
+            util::float_to_int(modified_price).map_err(|_| GenerationError::ShopPriceOverflow {
+                world_index: origin_world_index,
+                item: item.to_string(),
+            })?;
     }
 
+    let origin_world = &world_contexts[origin_world_index].world;
+    let shop_prices = &origin_world.player.settings.shop_prices;
+    if item.is_progression(
+        world_contexts[target_world_index]
+            .world
+            .player
+            .settings
+            .difficulty,
+    ) {
+        if shop_prices.free_progression {
+            price = 0;
+        } else if let Some(logical_price) = logical_shop_price(origin_world.graph, node) {
+            // Logic only guarantees the player to have this much Spirit Light when reaching the shop
+            price = price.min(logical_price);
+        }
+    }
     let price_setter = UberStateItem::simple_setter(
         *price_uber_identifier,
         UberType::Int,
@@ -438,6 +704,12 @@ where
 
     let origin_world_context = &mut world_contexts[origin_world_index];
 
+    origin_world_context.shop_prices.push(ShopPrice {
+        placement_index: origin_world_context.placements.len(),
+        shop: trigger.identifier.uber_group,
+        uber_identifier: *price_uber_identifier,
+        price,
+    });
     origin_world_context.placements.push(Placement {
         node: None,
         trigger: UberStateTrigger::load(),
@@ -476,21 +748,93 @@ where
     Ok(())
 }
 
+/// Scales down the prices of every shop that exceeds the [`ShopPriceSettings::max_total`](crate::settings::ShopPriceSettings::max_total) to fit it
+fn apply_shop_budget(world_context: &mut WorldContext) {
+    let max_total = match world_context.world.player.settings.shop_prices.max_total {
+        Some(max_total) => max_total,
+        None => return,
+    };
+
+    let mut totals = FxHashMap::<u16, u64>::default();
+    for shop_price in &world_context.shop_prices {
+        *totals.entry(shop_price.shop).or_default() += u64::from(shop_price.price);
+    }
+
+    for shop_price in &world_context.shop_prices {
+        let total = totals[&shop_price.shop];
+        if total <= u64::from(max_total) {
+            continue;
+        }
+
+        // Rounding down keeps the sum within the budget
+        #[allow(clippy::cast_possible_truncation)]
+        let price = (u64::from(shop_price.price) * u64::from(max_total) / total) as u32; // never more than the old price
+        log::trace!(
+            "Scaling the price at {} from {} to {} to fit the shop's budget",
+            shop_price.uber_identifier,
+            shop_price.price,
+            price
+        );
+        world_context.placements[shop_price.placement_index].item = UberStateItem::simple_setter(
+            shop_price.uber_identifier,
+            UberType::Int,
+            UberStateValue::Number((price as f32).into()),
+        );
+    }
+}
+
+/// Returns how much Spirit Light logic expects the player to have when buying from a shop location
+fn logical_shop_price(graph: &Graph, node: &Node) -> Option<u32> {
+    graph
+        .nodes
+        .iter()
+        .filter_map(|anchor| match anchor {
+            Node::Anchor(anchor) => Some(anchor),
+            _ => None,
+        })
+        .flat_map(|anchor| &anchor.connections)
+        .filter(|connection| connection.to == node.index())
+        .map(|connection| connection.requirement.spirit_light_needed())
+        .min()
+}
+
 fn place_relics<'a, R, I>(
-    amount: usize,
+    world_tour: WorldTour,
     world_index: usize,
     world_contexts: &mut [WorldContext<'a, '_>],
     context: &mut GeneratorContext<'_, R, I>,
-) -> Result<(), String>
+) -> Result<(), GenerationError>
 where
     R: Rng,
     I: Iterator<Item = u16>,
 {
     let world_context = &mut world_contexts[world_index];
 
-    let mut relic_zones = RELIC_ZONES.to_vec();
+    let WorldTour {
+        amount,
+        min,
+        zones: mut relic_zones,
+        avoid_shops_and_quests,
+        avoid_late_locations,
+    } = world_tour;
     relic_zones.shuffle(context.rng);
-    relic_zones.truncate(amount);
+
+    let early_locations = if avoid_late_locations {
+        Some(early_reach_check(
+            &world_context.world,
+            world_context.spawn,
+            context.rng,
+        ))
+    } else {
+        None
+    };
+
+    let is_early = |node: &Node| match &early_locations {
+        Some(early_locations) => early_locations
+            .iter()
+            .any(|early| early.index() == node.index()),
+        None => true,
+    };
 
     let mut relic_locations = relic_zones
         .into_iter()
@@ -510,6 +854,14 @@ where
                         .placements
                         .iter()
                         .any(|placement| &placement.trigger == trigger)
+                    && !world_context
+                        .excluded_locations
+                        .iter()
+                        .any(|excluded| excluded.index() == node.index())
+                    && !(avoid_shops_and_quests
+                        && (trigger.identifier.is_purchasable()
+                            || node.node_kind() == NodeKind::Quest))
+                    && is_early(node)
                 {
                     zone_relic_locations.push(node);
                 }
@@ -517,7 +869,13 @@ where
         }
     }
 
+    // Zones without any suitable location are skipped in favour of the next one
+    let mut placed = 0;
     for (zone, relic_locations) in &mut relic_locations {
+        if placed == amount {
+            break;
+        }
+
         log::trace!("(World {}): Placing Relic in {}", world_index, zone);
 
         if let Some(&location) = relic_locations.choose(context.rng) {
@@ -531,9 +889,18 @@ where
                 world_contexts,
                 context,
             )?;
+            placed += 1;
         }
     }
 
+    if placed < min {
+        return Err(GenerationError::InsufficientRelicLocations {
+            world_index,
+            placed,
+            min,
+        });
+    }
+
     Ok(())
 }
 
@@ -543,13 +910,17 @@ fn force_keystones<'a, R, I>(
     reserved_slots: &mut Vec<(usize, &'a Node)>,
     world_contexts: &mut [WorldContext<'a, '_>],
     context: &mut GeneratorContext<'_, R, I>,
-) -> Result<(), String>
+) -> Result<(), GenerationError>
 where
     R: Rng,
     I: Iterator<Item = u16>,
 {
     for target_world_index in 0..context.world_count {
         let world_context = &mut world_contexts[target_world_index];
+        // Only keystones that can be used on any door risk being spent on the wrong door
+        if world_context.world.player.settings.keystone_mode != KeystoneMode::KeysAnywhere {
+            continue;
+        }
 
         let placed_keystones = world_context
             .world
@@ -565,8 +936,8 @@ where
             .map(|&node| {
                 KEYSTONE_DOORS
                     .iter()
-                    .find(|&&(identifier, _)| identifier == node.identifier())
-                    .map_or(0, |(_, keystones)| *keystones)
+                    .find(|&&(identifier, _, _)| identifier == node.identifier())
+                    .map_or(0, |(_, _, keystones)| *keystones)
             })
             .sum();
         if required_keystones <= placed_keystones {
@@ -601,65 +972,93 @@ fn forced_placement<'a, R, I>(
     reserved_slots: &mut Vec<(usize, &'a Node)>,
     world_contexts: &mut [WorldContext<'a, '_>],
     context: &mut GeneratorContext<'_, R, I>,
-) -> Result<(), String>
+) -> Result<(), GenerationError>
 where
     R: Rng,
     I: Iterator<Item = u16>,
 {
+    let category = world_contexts[target_world_index]
+        .world
+        .graph
+        .item_category(&item);
     let prevent_sharing = &mut world_contexts[target_world_index].prevent_sharing;
     let is_multiworld_spread = if *prevent_sharing > 0 {
         *prevent_sharing -= 1;
         false
     } else {
-        item.is_multiworld_spread()
+        item.is_multiworld_spread() && !context.multiworld.is_local(category)
     };
+    let keep_in_own_world = context.keep_in_own_world(category);
+    let prefer_own_world = keep_in_own_world == Some(true);
+    let prefer_other_worlds = keep_in_own_world == Some(false);
+
+    let settings = world_contexts[target_world_index].world.player.settings;
+    let step = context.current_step();
+    let allowed = |node: &Node| violated_constraint(settings, &item, node, step).is_none();
 
     let mut choose_node = || {
         if is_multiworld_spread {
-            let mut world_indices = (0..context.world_count).collect::<Vec<_>>();
-            world_indices.shuffle(context.rng);
-
-            if let Some((origin_world_index, node)) = reserved_slots.pop() {
+            let world_indices = context.origin_world_order(target_world_index, category);
+
+            if prefer_own_world {
+                if let Some(index) = reserved_slots.iter().rposition(|(world_index, node)| {
+                    *world_index == target_world_index && allowed(node)
+                }) {
+                    let (_, node) = reserved_slots.remove(index);
+                    return Ok((target_world_index, node, false));
+                }
+            }
+            if let Some(index) = reserved_slots.iter().rposition(|(world_index, node)| {
+                !(prefer_other_worlds && *world_index == target_world_index) && allowed(node)
+            }) {
+                let (origin_world_index, node) = reserved_slots.remove(index);
                 return Ok((origin_world_index, node, false));
             }
             for origin_world_index in world_indices {
-                let placeholders = &mut world_contexts[origin_world_index].placeholders;
-                if !placeholders.is_empty() {
-                    let index = context.rng.gen_range(0..placeholders.len());
-                    let node = placeholders.remove(index);
+                if let Some(node) = remove_random_allowed(
+                    &mut world_contexts[origin_world_index].placeholders,
+                    allowed,
+                    context.rng,
+                ) {
                     return Ok((origin_world_index, node, true));
                 }
             }
+            if prefer_other_worlds {
+                if let Some(index) = reserved_slots.iter().rposition(|(_, node)| allowed(node)) {
+                    let (origin_world_index, node) = reserved_slots.remove(index);
+                    return Ok((origin_world_index, node, false));
+                }
+            }
         } else {
-            if let Some((index, _)) = reserved_slots
+            if let Some(index) = reserved_slots
                 .iter()
-                .enumerate()
-                .find(|(_, (world_index, _))| world_index == &target_world_index)
+                .position(|(world_index, node)| world_index == &target_world_index && allowed(node))
             {
                 let (_, node) = reserved_slots.remove(index);
                 return Ok((target_world_index, node, false));
             }
 
-            let placeholders = &mut world_contexts[target_world_index].placeholders;
-            if !placeholders.is_empty() {
-                let index = context.rng.gen_range(0..placeholders.len());
-                let node = placeholders.remove(index);
+            if let Some(node) = remove_random_allowed(
+                &mut world_contexts[target_world_index].placeholders,
+                allowed,
+                context.rng,
+            ) {
                 return Ok((target_world_index, node, true));
             }
         }
         let target_world_context = &mut world_contexts[target_world_index];
-        if target_world_context.spawn_slots > 0 {
+        let spawn_pickup_node = &target_world_context.world.graph.spawn_pickup_node;
+        if target_world_context.spawn_slots > 0 && allowed(spawn_pickup_node) {
             target_world_context.spawn_slots -= 1;
-            return Ok((
-                target_world_index,
-                &target_world_context.world.graph.spawn_pickup_node,
-                false,
-            ));
+            return Ok((target_world_index, spawn_pickup_node, false));
         }
-        return Err(format!(
-            "(World {}): Not enough slots to place forced progression {}",
-            target_world_index, item
-        )); // due to the slot checks in missing_items this should only ever happen for forced keystone placements
+        if let Some(err) = unsatisfiable_constraint(target_world_index, settings, &item) {
+            return Err(err);
+        }
+        return Err(GenerationError::NotEnoughSlots {
+            world_index: target_world_index,
+            item: item.to_string(),
+        }); // due to the slot checks in missing_items this should only ever happen for forced keystone placements
     };
 
     let mut node = choose_node()?;
@@ -698,6 +1097,85 @@ where
     Ok(())
 }
 
+/// Removes a random node out of those that pass `allowed`, or returns [`None`] if none of them do
+///
+/// The remaining nodes keep their order
+fn remove_random_allowed<'a>(
+    nodes: &mut Vec<&'a Node>,
+    allowed: impl Fn(&Node) -> bool,
+    rng: &mut impl Rng,
+) -> Option<&'a Node> {
+    let allowed_indices = nodes
+        .iter()
+        .enumerate()
+        .filter(|(_, node)| allowed(node))
+        .map(|(index, _)| index)
+        .collect::<Vec<_>>();
+    if allowed_indices.is_empty() {
+        return None;
+    }
+    let index = allowed_indices[rng.gen_range(0..allowed_indices.len())];
+    Some(nodes.remove(index))
+}
+
+/// Removes the last node that passes `allowed`
+fn pop_allowed<'a>(nodes: &mut Vec<&'a Node>, allowed: impl Fn(&Node) -> bool) -> Option<&'a Node> {
+    let index = nodes.iter().rposition(|node| allowed(node))?;
+    Some(nodes.remove(index))
+}
+
+/// Force places any items whose [`PlacementConstraint::max_step`] would be exceeded after this step
+fn force_constrained_items<'a, R, I>(
+    reserved_slots: &mut Vec<(usize, &'a Node)>,
+    world_contexts: &mut [WorldContext<'a, '_>],
+    context: &mut GeneratorContext<'_, R, I>,
+) -> Result<(), GenerationError>
+where
+    R: Rng,
+    I: Iterator<Item = u16>,
+{
+    let step = context.current_step();
+
+    for target_world_index in 0..context.world_count {
+        let settings = world_contexts[target_world_index].world.player.settings;
+
+        for constraint in &settings.placement_constraints {
+            match constraint.max_step {
+                Some(max_step) if step >= max_step => {}
+                _ => continue,
+            }
+
+            let remaining = world_contexts[target_world_index]
+                .world
+                .pool
+                .inventory
+                .get(&constraint.item);
+            if remaining == 0 {
+                continue;
+            }
+
+            log::trace!(
+                "(World {}): Force placing {} to satisfy the placement constraint {}",
+                target_world_index,
+                constraint.item,
+                constraint
+            );
+
+            for _ in 0..remaining {
+                forced_placement(
+                    target_world_index,
+                    constraint.item.clone(),
+                    reserved_slots,
+                    world_contexts,
+                    context,
+                )?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
 fn determine_progressions<'a>(
     world_index: usize,
     slots: usize,
@@ -744,7 +1222,7 @@ fn pick_progression<'a, 'b, R, I>(
     reach_context: &ReachContext,
     world_contexts: &mut [WorldContext<'a, '_>],
     context: &mut GeneratorContext<'_, R, I>,
-) -> Result<&'b Inventory, String>
+) -> Result<&'b Inventory, GenerationError>
 where
     R: Rng,
     I: Iterator<Item = u16>,
@@ -755,7 +1233,7 @@ where
         itemsets.len()
     );
 
-    let weight = |inventory: &Inventory| -> Result<f32, String> {
+    let weight = |inventory: &Inventory| -> Result<f32, GenerationError> {
         let target_world_context = &world_contexts[target_world_index];
 
         let mut lookahead_player = target_world_context.world.player.clone();
@@ -774,7 +1252,8 @@ where
             .len()
             .saturating_sub(reach_context.reachable_counts[target_world_index]);
 
-        let mut weight = 1.0 / inventory.cost() as f32 * (newly_reached + 1) as f32;
+        let mut weight =
+            strategy_weight(context.placement_strategy, inventory.cost(), newly_reached);
 
         let begrudgingly_used_slots = (inventory.item_count() as usize
             + (SPAWN_SLOTS - PREFERRED_SPAWN_SLOTS))
@@ -787,7 +1266,7 @@ where
     };
     let with_weights = itemsets
         .iter()
-        .map::<Result<(&Inventory, f32), String>, _>(|inventory| {
+        .map::<Result<(&Inventory, f32), GenerationError>, _>(|inventory| {
             Ok((inventory, weight(inventory)?))
         })
         .collect::<Result<Vec<_>, _>>()?;
@@ -806,11 +1285,9 @@ where
 
             weight
         })
-        .map_err(|err| {
-            format!(
-                "(World {}): Error choosing progression: {}",
-                target_world_index, err
-            )
+        .map_err(|err| GenerationError::ProgressionChoice {
+            world_index: target_world_index,
+            message: err.to_string(),
         })?;
 
     log::trace!(
@@ -822,13 +1299,26 @@ where
     Ok(progression)
 }
 
+/// How likely a progression option costing `cost` that reaches `newly_reached` new locations should be chosen under the [`PlacementStrategy`]
+fn strategy_weight(placement_strategy: PlacementStrategy, cost: u32, newly_reached: usize) -> f32 {
+    match placement_strategy {
+        PlacementStrategy::Classic => 1.0 / cost as f32 * (newly_reached + 1) as f32,
+        PlacementStrategy::Balanced { target_step_size } => {
+            let target_step_size = target_step_size.get();
+            let deviation =
+                newly_reached.abs_diff(target_step_size) as f32 / target_step_size as f32;
+            1.0 / cost as f32 / (1.0 + deviation).powi(BALANCED_DEVIATION_EXPONENT)
+        }
+    }
+}
+
 fn split_progression_item<'a, R, I>(
     world_index: usize,
     item: &Item,
     amount: u32,
     world_contexts: &mut [WorldContext<'a, '_>],
     context: &mut GeneratorContext<'_, R, I>,
-) -> Vec<Item>
+) -> Result<Vec<Item>, GenerationError>
 where
     R: Rng,
     I: Iterator<Item = u16>,
@@ -838,16 +1328,18 @@ where
         let mut amount_placed = 0;
 
         while amount_placed < amount {
-            let stacked_amount = world_contexts[world_index]
-                .spirit_light_rng
-                .sample(context.rng);
+            let spirit_light_rng = &mut world_contexts[world_index].spirit_light_rng;
+            if spirit_light_rng.remaining == 0 {
+                break;
+            }
+            let stacked_amount = spirit_light_rng.sample(None, context.rng)?;
             amount_placed += stacked_amount;
             spirit_light_items.push(Item::SpiritLight(stacked_amount));
         }
 
-        spirit_light_items
+        Ok(spirit_light_items)
     } else {
-        vec![item.clone(); amount as usize]
+        Ok(vec![item.clone(); amount as usize])
     }
 }
 
@@ -856,7 +1348,7 @@ fn force_progression<'a, R, I>(
     reach_context: &mut ReachContext,
     world_contexts: &mut [WorldContext<'a, '_>],
     context: &mut GeneratorContext<'_, R, I>,
-) -> Result<(), String>
+) -> Result<(), GenerationError>
 where
     R: Rng,
     I: Iterator<Item = u16>,
@@ -923,48 +1415,63 @@ where
                         world_context.spawn
                     );
                 }
-                return Err(String::from("Failed to reach anything from spawn location"));
+                let spawns = world_contexts
+                    .iter()
+                    .map(|world_context| world_context.spawn.identifier().to_string())
+                    .collect();
+                return Err(GenerationError::NothingReachable { spawns });
             }
 
-            #[cfg(feature = "log")]
-            for (world_index, world_context) in world_contexts.iter().enumerate() {
-                let identifiers: Vec<_> = world_context
-                    .reachable_locations
-                    .iter()
-                    .filter_map(|&node| {
-                        let node_index = node.index();
+            let unreached = world_contexts
+                .iter()
+                .map(|world_context| {
+                    world_context
+                        .reachable_locations
+                        .iter()
+                        .filter_map(|&node| {
+                            let node_index = node.index();
 
-                        node.trigger().and_then(|uber_state| {
-                            if !world_context
-                                .placements
-                                .iter()
-                                .any(|placement| &placement.trigger == uber_state)
-                                && !world_context
-                                    .placeholders
+                            node.trigger().and_then(|uber_state| {
+                                if !world_context
+                                    .placements
                                     .iter()
-                                    .any(|&placeholder| placeholder.index() == node_index)
-                                && !world_context
-                                    .collected_preplacements
-                                    .iter()
-                                    .any(|&collected| collected == node_index)
-                            {
-                                Some(node.identifier())
-                            } else {
-                                None
-                            }
+                                    .any(|placement| &placement.trigger == uber_state)
+                                    && !world_context
+                                        .placeholders
+                                        .iter()
+                                        .any(|&placeholder| placeholder.index() == node_index)
+                                    && !world_context
+                                        .collected_preplacements
+                                        .iter()
+                                        .any(|&collected| collected == node_index)
+                                {
+                                    Some(node.identifier())
+                                } else {
+                                    None
+                                }
+                            })
                         })
-                    })
-                    .collect();
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
 
+            #[cfg(feature = "log")]
+            for (world_index, (world_context, identifiers)) in
+                world_contexts.iter().zip(&unreached).enumerate()
+            {
                 log::trace!(
                     "(World {}): Failed to reach all locations with inventory: {}\nUnreached locations: {}",
                     world_index,
                     world_context.world.player.inventory,
-                    format_identifiers(identifiers)
+                    format_identifiers(identifiers.clone())
                 );
             }
 
-            return Err(String::from("Failed to reach all locations"));
+            let unreached = unreached
+                .into_iter()
+                .map(|identifiers| identifiers.into_iter().map(str::to_string).collect())
+                .collect();
+            return Err(GenerationError::UnreachableLocations { unreached });
         }
     };
 
@@ -978,10 +1485,17 @@ where
         world_contexts,
         context,
     )?;
+    notify(
+        context.observer,
+        GenerationEvent::ForcedProgression {
+            world_index: target_world_index,
+            items: progression,
+        },
+    )?;
 
     for (item, amount) in &progression.items {
         let items =
-            split_progression_item(target_world_index, item, *amount, world_contexts, context);
+            split_progression_item(target_world_index, item, *amount, world_contexts, context)?;
 
         for item in items {
             forced_placement(
@@ -1003,7 +1517,7 @@ fn random_item_placement<'a, R, I>(
     node: &'a Node,
     world_contexts: &mut [WorldContext<'a, '_>],
     context: &mut GeneratorContext<'_, R, I>,
-) -> Result<bool, String>
+) -> Result<bool, GenerationError>
 where
     R: Rng,
     I: Iterator<Item = u16>,
@@ -1019,7 +1533,7 @@ where
             origin_world_context.prevent_sharing -= 1;
             origin_world_index
         } else {
-            context.rng.gen_range(0..context.world_count)
+            context.pick_target_world(origin_world_index)
         };
 
         if is_purchasable
@@ -1031,13 +1545,49 @@ where
                     .item_count() as usize
         {
             let target_world_context = &mut world_contexts[target_world_index];
-
-            if let Some(item) = target_world_context
-                .world
-                .pool
-                .choose_random(origin_world_index != target_world_index, context.rng)
-            {
+            let multiworld = context.multiworld;
+            let is_shared = origin_world_index != target_world_index;
+            let is_multiworld = context.world_count > 1;
+            // The balanced strategy only places resources and non-progression randomly and leaves all other progression to force_progression, so it can control the step sizes
+            let allow_unlocks = context.placement_strategy == PlacementStrategy::Classic;
+            let difficulty = target_world_context.world.player.settings.difficulty;
+            let graph = target_world_context.world.graph;
+
+            if let Some(item) = target_world_context.world.pool.choose_random_filtered(
+                |item| {
+                    if !(allow_unlocks
+                        || matches!(item, Item::Resource(_) | Item::SpiritLight(_))
+                        || !item.is_progression(difficulty))
+                    {
+                        false
+                    } else if is_shared {
+                        item.is_multiworld_spread()
+                            && !multiworld.is_local(graph.item_category(item))
+                    } else {
+                        !(is_multiworld && multiworld.is_shared(graph.item_category(item)))
+                    }
+                },
+                context.rng,
+            ) {
                 let item = item.clone();
+                if violated_constraint(
+                    target_world_context.world.player.settings,
+                    &item,
+                    node,
+                    context.current_step(),
+                )
+                .is_some()
+                {
+                    log::trace!(
+                        "(World {}): Reserving {} as placeholder since {} isn't allowed there",
+                        origin_world_index,
+                        node,
+                        item
+                    );
+
+                    world_contexts[origin_world_index].placeholders.push(node);
+                    return Ok(false);
+                }
                 let is_progression =
                     item.is_progression(target_world_context.world.player.settings.difficulty);
                 target_world_context.world.pool.remove(&item, 1);
@@ -1055,6 +1605,14 @@ where
 
                 return Ok(is_progression);
             }
+
+            // This happens for instance if the pool only holds item categories that have to be shared while the target is the own world
+            log::trace!(
+                "(World {}): None of the items left for World {} may be placed on {}, placing Spirit Light instead",
+                origin_world_index,
+                target_world_index,
+                node
+            );
         } else {
             log::trace!(
                 "(World {}) Forcing spirit light placement to preserve items for shop slots",
@@ -1065,7 +1623,9 @@ where
 
     let origin_world_context = &mut world_contexts[origin_world_index];
 
-    let amount = origin_world_context.spirit_light_rng.sample(context.rng);
+    let amount = origin_world_context
+        .spirit_light_rng
+        .sample(node.zone(), context.rng)?;
     let item = Item::SpiritLight(amount);
 
     origin_world_context.world.pool.remove(&item, 1);
@@ -1084,33 +1644,113 @@ where
     Ok(true)
 }
 
-#[inline]
-/// May randomly place an item, or add a placeholder
-/// Returns `true` if a progression item was placed, `false` if a placeholder was added or the placed item cannot be progression
-fn random_placement<'a, R, I>(
-    origin_world_index: usize,
+/// Fills an excluded location with Spirit Light or an item that can never be progression
+fn excluded_placement<'a, R, I>(
+    world_index: usize,
     node: &'a Node,
-    allow_placeholder: bool,
     world_contexts: &mut [WorldContext<'a, '_>],
     context: &mut GeneratorContext<'_, R, I>,
-) -> Result<bool, String>
+) -> Result<(), GenerationError>
 where
     R: Rng,
     I: Iterator<Item = u16>,
 {
-    let origin_world_context = &mut world_contexts[origin_world_index];
+    let world_context = &mut world_contexts[world_index];
 
-    // force a couple placeholders at the start
-    #[cfg(feature = "log")]
-    let mut force = false;
-    if origin_world_context.placeholders.len() < 4 {
-        #[cfg(feature = "log")]
-        {
-            force = true
-        };
-    } else if context.random_progression.sample(context.rng) {
-        return random_item_placement(origin_world_index, node, world_contexts, context);
-    }
+    let is_purchasable = node
+        .trigger()
+        .is_some_and(|trigger| trigger.identifier.is_purchasable());
+
+    let item = if is_purchasable || !world_context.random_spirit_light.sample(context.rng) {
+        let difficulty = world_context.world.player.settings.difficulty;
+        world_context
+            .world
+            .pool
+            .inventory
+            .items
+            .keys()
+            .filter(|item| !item.is_progression(difficulty))
+            .choose(context.rng)
+            .cloned()
+    } else {
+        None
+    };
+
+    let item = match item {
+        Some(item) => item,
+        None if is_purchasable => {
+            log::warning!(
+                "(World {}): No items left that can't be progression to fill the excluded shop location {}! Filling with an extra Health Regeneration",
+                world_index,
+                node
+            );
+            // Shops can't sell Spirit Light, so this needs an item that is never progression on any difficulty
+            place_item(
+                world_index,
+                world_index,
+                node,
+                false,
+                false,
+                Item::BonusItem(BonusItem::HealthRegeneration),
+                world_contexts,
+                context,
+            )?;
+            return Ok(());
+        }
+        None => Item::SpiritLight(
+            world_context
+                .spirit_light_rng
+                .sample(node.zone(), context.rng)?,
+        ),
+    };
+
+    log::trace!(
+        "(World {}): Filling excluded location {}",
+        world_index,
+        node
+    );
+
+    world_context.world.pool.remove(&item, 1);
+    world_context.world.grant_player(item.clone(), 1);
+    place_item(
+        world_index,
+        world_index,
+        node,
+        false,
+        false,
+        item,
+        world_contexts,
+        context,
+    )
+}
+
+#[inline]
+/// May randomly place an item, or add a placeholder
+/// Returns `true` if a progression item was placed, `false` if a placeholder was added or the placed item cannot be progression
+fn random_placement<'a, R, I>(
+    origin_world_index: usize,
+    node: &'a Node,
+    allow_placeholder: bool,
+    world_contexts: &mut [WorldContext<'a, '_>],
+    context: &mut GeneratorContext<'_, R, I>,
+) -> Result<bool, GenerationError>
+where
+    R: Rng,
+    I: Iterator<Item = u16>,
+{
+    let origin_world_context = &mut world_contexts[origin_world_index];
+
+    // force a couple placeholders at the start
+    #[cfg(feature = "log")]
+    let mut force = false;
+    if origin_world_context.placeholders.len() < 4 {
+        #[cfg(feature = "log")]
+        {
+            force = true
+        };
+    } else if context.random_progression.sample(context.rng) {
+        return random_item_placement(origin_world_index, node, world_contexts, context);
+    }
 
     let origin_world_context = &mut world_contexts[origin_world_index];
     log::trace!(
@@ -1136,7 +1776,7 @@ where
 fn one_xp<'a, R, I>(
     world_contexts: &mut [WorldContext<'a, '_>],
     context: &mut GeneratorContext<'_, R, I>,
-) -> Result<(), String>
+) -> Result<(), GenerationError>
 where
     R: Rng,
     I: Iterator<Item = u16>,
@@ -1157,13 +1797,20 @@ where
             })
             .choose(context.rng)
         {
+            let world_context = &mut world_contexts[world_index];
+            let amount = world_context.spirit_light_rng.take(1)?;
+            world_context
+                .world
+                .pool
+                .remove(&Item::SpiritLight(amount), 1);
+
             place_item(
                 world_index,
                 world_index,
                 node,
                 false,
                 false,
-                Item::SpiritLight(1),
+                Item::SpiritLight(amount),
                 world_contexts,
                 context,
             )?;
@@ -1183,44 +1830,192 @@ where
  * this gives us a nice shallow parabola with some randomness but not so much that you can't tell approximately when a pickup was placed
  */
 struct SpiritLightAmounts {
+    world_index: usize,
+    distribution: SpiritLightDistribution,
+    /// How much Spirit Light from the item pool has not been placed yet
+    remaining: u32,
+    /// How much Spirit Light from the item pool has been placed so far
+    placed: u32,
+    /// How many pickups received Spirit Light so far
+    pickups: usize,
+    /// How many more pickups are estimated to receive Spirit Light, each of them keeps at least 1 Spirit Light reserved
+    slots: usize,
     factor: f32,
+    average: f32,
     noise: Uniform<f32>,
     index: usize,
 }
 impl SpiritLightAmounts {
     fn new(
-        spirit_light_pool: f32,
-        spirit_light_slots: f32,
+        world_index: usize,
+        distribution: SpiritLightDistribution,
+        spirit_light_pool: u32,
+        spirit_light_slots: usize,
         random_low: f32,
         random_high: f32,
     ) -> SpiritLightAmounts {
-        let factor = (spirit_light_pool - spirit_light_slots * 50.0)
-            / (spirit_light_slots.powi(3) / 3.0
-                + spirit_light_slots.powi(2) / 2.0
-                + spirit_light_slots / 6.0);
+        #[allow(clippy::cast_precision_loss)]
+        let total = spirit_light_pool as f32;
+        #[allow(clippy::cast_precision_loss)]
+        let spirit_light_slots_f32 = spirit_light_slots as f32;
+        let factor = (total - spirit_light_slots_f32 * 50.0)
+            / (spirit_light_slots_f32.powi(3) / 3.0
+                + spirit_light_slots_f32.powi(2) / 2.0
+                + spirit_light_slots_f32 / 6.0);
+        let average = total / spirit_light_slots_f32;
         let noise = Uniform::new_inclusive(random_low, random_high);
 
         SpiritLightAmounts {
+            world_index,
+            distribution,
+            remaining: spirit_light_pool,
+            placed: 0,
+            pickups: 0,
+            slots: spirit_light_slots + 1, // The 1xp pickup comes on top of the estimate
             factor,
+            average,
             noise,
             index: 0,
         }
     }
-    fn sample(&mut self, rng: &mut impl Rng) -> u32 {
+    /// Relative amount of Spirit Light for pickups in a zone when using [`SpiritLightDistribution::ZoneWeighted`], based on how late the zone is usually reached
+    fn zone_weight(zone: Option<Zone>) -> f32 {
+        match zone {
+            Some(Zone::Marsh | Zone::Hollow | Zone::Glades) => 0.5,
+            Some(Zone::Reach | Zone::Pools | Zone::Depths) => 1.25,
+            Some(Zone::Wastes | Zone::Ruins | Zone::Willow) => 1.5,
+            _ => 1.0,
+        }
+    }
+    /// Marks up to `amount` Spirit Light from the item pool as placed for the next pickup and returns how much that was
+    ///
+    /// The pickup receives at least 1 Spirit Light, but never so much that the estimated pickups after it couldn't receive at least 1 each
+    fn take(&mut self, amount: u32) -> Result<u32, GenerationError> {
+        if self.remaining == 0 {
+            return Err(self.insufficient(1));
+        }
+        self.slots = self.slots.saturating_sub(1);
+        let reserved = u32::try_from(self.slots).unwrap_or(u32::MAX);
+        let amount = amount.clamp(1, self.remaining.saturating_sub(reserved).max(1));
+        self.remaining -= amount;
+        self.placed += amount;
+        self.pickups += 1;
+        Ok(amount)
+    }
+    /// The error for when the Spirit Light from the item pool runs out with `missing` more pickups to place
+    fn insufficient(&self, missing: usize) -> GenerationError {
+        GenerationError::InsufficientSpiritLight {
+            world_index: self.world_index,
+            spirit_light: self.placed + self.remaining,
+            slots: self.pickups + missing,
+        }
+    }
+    fn sample_unbounded(&mut self, zone: Option<Zone>, rng: &mut impl Rng) -> u32 {
         #[allow(clippy::cast_precision_loss)]
-        let amount =
-            (self.factor * self.index.pow(2) as f32 + 50.0 * self.noise.sample(rng)).round();
+        let amount = match self.distribution {
+            SpiritLightDistribution::Increasing => {
+                self.factor * self.index.pow(2) as f32 + 50.0 * self.noise.sample(rng)
+            }
+            SpiritLightDistribution::Flat => self.average * self.noise.sample(rng),
+            SpiritLightDistribution::ZoneWeighted => {
+                self.average * Self::zone_weight(zone) * self.noise.sample(rng)
+            }
+            SpiritLightDistribution::Capped(max) => {
+                (self.average * self.noise.sample(rng)).min(max as f32)
+            }
+        }
+        .round();
         self.index += 1;
 
         #[allow(clippy::cast_possible_truncation)]
         util::float_to_int(amount).unwrap_or(u32::MAX)
     }
+    /// Samples the amount for the next pickup, see [`SpiritLightAmounts::take`]
+    fn sample(&mut self, zone: Option<Zone>, rng: &mut impl Rng) -> Result<u32, GenerationError> {
+        let amount = self.sample_unbounded(zone, rng);
+        self.take(amount)
+    }
+    /// Splits all of the remaining Spirit Light across the last pickups, located in the given zones
+    ///
+    /// Every pickup receives at least 1 Spirit Light, which fails if less Spirit Light than pickups remains.
+    /// The sampled amounts are scaled to add up to the remaining Spirit Light exactly, while respecting the cap of [`SpiritLightDistribution::Capped`]
+    /// If every pickup is capped, some Spirit Light may remain afterwards
+    fn distribute(
+        &mut self,
+        zones: &[Option<Zone>],
+        rng: &mut impl Rng,
+    ) -> Result<Vec<u32>, GenerationError> {
+        if (self.remaining as usize) < zones.len() {
+            return Err(self.insufficient(zones.len()));
+        }
+
+        let weights = zones
+            .iter()
+            .map(|&zone| u128::from(self.sample_unbounded(zone, rng).max(1)))
+            .collect::<Vec<_>>();
+
+        let mut amounts = vec![0; zones.len()];
+        let mut uncapped = (0..zones.len()).collect::<Vec<_>>();
+        let mut left = self.remaining;
+
+        if let SpiritLightDistribution::Capped(max) = self.distribution {
+            // Pickups whose share would exceed the cap receive exactly the cap, the others split whatever is left
+            loop {
+                let weight_sum = uncapped.iter().map(|&index| weights[index]).sum::<u128>();
+                let spare = u128::from(left) - uncapped.len() as u128;
+                let (capped, rest): (Vec<usize>, Vec<usize>) =
+                    uncapped.iter().partition(|&&index| {
+                        spare * weights[index] > u128::from(max.saturating_sub(1)) * weight_sum
+                    });
+                if capped.is_empty() {
+                    break;
+                }
+
+                for index in capped {
+                    amounts[index] = max;
+                    left -= max;
+                }
+                uncapped = rest;
+            }
+        }
+
+        if !uncapped.is_empty() {
+            // Everyone gets 1 Spirit Light upfront and the sampled amounts split the rest
+            #[allow(clippy::cast_possible_truncation)]
+            let spare = left - uncapped.len() as u32; // uncapped pickups never outnumber the Spirit Light left
+            let weight_sum = uncapped.iter().map(|&index| weights[index]).sum::<u128>();
+            let mut remainders = Vec::with_capacity(uncapped.len());
+            for &index in &uncapped {
+                let share = u128::from(spare) * weights[index];
+                #[allow(clippy::cast_possible_truncation)]
+                let amount = (share / weight_sum) as u32; // never more than spare
+                amounts[index] = amount + 1;
+                remainders.push((share % weight_sum, index));
+            }
+
+            // Hand out what got lost to rounding down, preferring the pickups that lost the most
+            #[allow(clippy::cast_possible_truncation)]
+            let distributed =
+                uncapped.iter().map(|&index| amounts[index]).sum::<u32>() - uncapped.len() as u32;
+            remainders.sort_by_key(|&(remainder, _)| Reverse(remainder));
+            for (_, index) in remainders.into_iter().take((spare - distributed) as usize) {
+                amounts[index] += 1;
+            }
+        }
+
+        let placed = amounts.iter().sum::<u32>();
+        self.remaining -= placed;
+        self.placed += placed;
+        self.pickups += zones.len();
+        self.slots = self.slots.saturating_sub(zones.len());
+        Ok(amounts)
+    }
 }
 
 fn place_remaining<'a, R, I>(
     world_contexts: &mut [WorldContext<'a, '_>],
     context: &mut GeneratorContext<'_, R, I>,
-) -> Result<(), String>
+) -> Result<(), GenerationError>
 where
     R: Rng,
     I: Iterator<Item = u16>,
@@ -1261,21 +2056,40 @@ where
 
         remaining.shuffle(context.rng);
 
+        let settings = world_contexts[target_world_index].world.player.settings;
+        let step = context.current_step();
+
         let mut space_remaining = true;
         'outer: for item in remaining {
             if space_remaining {
-                let origin_world_indices = if item.is_multiworld_spread() {
-                    let mut indices = (0..context.world_count).collect::<Vec<_>>();
-                    indices.shuffle(context.rng);
-                    indices
-                } else {
-                    vec![target_world_index]
-                };
+                let allowed =
+                    |node: &Node| violated_constraint(settings, &item, node, step).is_none();
+                let category = world_contexts[target_world_index]
+                    .world
+                    .graph
+                    .item_category(&item);
+                let origin_world_indices =
+                    if item.is_multiworld_spread() && !context.multiworld.is_local(category) {
+                        let mut indices = (0..context.world_count).collect::<Vec<_>>();
+                        indices.shuffle(context.rng);
+                        if context.multiworld.is_shared(category) {
+                            deprioritize_world(&mut indices, target_world_index);
+                        }
+                        indices
+                    } else {
+                        vec![target_world_index]
+                    };
 
                 for origin_world_index in origin_world_indices {
-                    if let Some(node) = shop_placeholders[origin_world_index]
-                        .pop()
-                        .or_else(|| world_contexts[origin_world_index].placeholders.pop())
+                    if let Some(node) =
+                        pop_allowed(&mut shop_placeholders[origin_world_index], allowed).or_else(
+                            || {
+                                pop_allowed(
+                                    &mut world_contexts[origin_world_index].placeholders,
+                                    allowed,
+                                )
+                            },
+                        )
                     {
                         place_item(
                             origin_world_index,
@@ -1291,6 +2105,10 @@ where
                     }
                 }
 
+                if let Some(err) = unsatisfiable_constraint(target_world_index, settings, &item) {
+                    return Err(err);
+                }
+
                 space_remaining = false;
 
                 log::warning!(
@@ -1298,6 +2116,9 @@ where
                     target_world_index
                 );
                 log::trace!("Unable to place {}", item);
+            } else if let Some(err) = unsatisfiable_constraint(target_world_index, settings, &item)
+            {
+                return Err(err);
             } else {
                 log::trace!("Unable to place {}", item);
             }
@@ -1329,47 +2150,71 @@ where
             world_index
         );
 
-        while let Some(placeholder) = world_contexts[world_index].placeholders.pop() {
-            let amount = world_contexts[world_index]
-                .spirit_light_rng
-                .sample(context.rng);
-            let item = Item::SpiritLight(amount);
+        let world_context = &mut world_contexts[world_index];
+        if !world_context.unreachable_locations.is_empty() {
+            log::trace!("(World {}): Filling unreachable locations", world_index);
+        }
+
+        let mut unreachable_shops = Vec::new();
+        let placeholders = world_context
+            .placeholders
+            .drain(..)
+            .rev()
+            .map(|node| (node, true));
+        let unreachables = world_context
+            .unreachable_locations
+            .drain(..)
+            .rev()
+            .filter_map(|node| {
+                if node
+                    .trigger()
+                    .is_some_and(|trigger| trigger.identifier.is_purchasable())
+                {
+                    unreachable_shops.push(node);
+                    None
+                } else {
+                    Some((node, false))
+                }
+            });
+        let locations = placeholders.chain(unreachables).collect::<Vec<_>>();
 
+        let zones = locations
+            .iter()
+            .map(|(node, _)| node.zone())
+            .collect::<Vec<_>>();
+        let amounts = world_context
+            .spirit_light_rng
+            .distribute(&zones, context.rng)?;
+
+        let unplaced = world_context.spirit_light_rng.remaining;
+        if unplaced > 0 {
+            log::warning!(
+                "(World {}): Not enough space to place the remaining {} Spirit Light from the item pool!",
+                world_index,
+                unplaced
+            );
+        }
+
+        for ((node, was_placeholder), amount) in locations.into_iter().zip(amounts) {
             place_item(
                 world_index,
                 world_index,
-                placeholder,
-                true,
+                node,
+                was_placeholder,
                 false,
-                item,
+                Item::SpiritLight(amount),
                 world_contexts,
                 context,
             )?;
         }
-
-        if !world_contexts[world_index].unreachable_locations.is_empty() {
-            log::trace!("(World {}): Filling unreachable locations", world_index);
-        }
-        while let Some(unreachable) = world_contexts[world_index].unreachable_locations.pop() {
-            let item = if unreachable
-                .trigger()
-                .map_or(false, |trigger| trigger.identifier.is_purchasable())
-            {
-                Item::Resource(Resource::GorlekOre)
-            } else {
-                let amount = world_contexts[world_index]
-                    .spirit_light_rng
-                    .sample(context.rng);
-                Item::SpiritLight(amount)
-            };
-
+        for node in unreachable_shops {
             place_item(
                 world_index,
                 world_index,
-                unreachable,
+                node,
                 false,
                 false,
-                item,
+                Item::Resource(Resource::GorlekOre),
                 world_contexts,
                 context,
             )?;
@@ -1381,8 +2226,11 @@ where
     Ok(())
 }
 
-#[inline]
-fn total_reach_check<'a>(world: &World<'a, '_>) -> Result<Vec<&'a Node>, String> {
+/// Finds the locations that can be reached once the whole item pool has been collected
+fn finished_reach_check<'a>(
+    world_index: usize,
+    world: &World<'a, '_>,
+) -> Result<Vec<&'a Node>, GenerationError> {
     let mut finished_world = world.clone();
     for (item, amount) in &world.pool.inventory.items {
         if item.is_progression(world.player.settings.difficulty) {
@@ -1390,26 +2238,128 @@ fn total_reach_check<'a>(world: &World<'a, '_>) -> Result<Vec<&'a Node>, String>
         }
     }
     finished_world.grant_player(Item::SpiritLight(1), world.pool.spirit_light);
+    // Anything preplaced on spawn, such as starting items, is collected right away, the same as in Replay and verify_seed
+    finished_world.set_uber_state(UberIdentifier::spawn(), 1.);
+
+    let spawn = finished_world
+        .graph
+        .find_spawn(DEFAULT_SPAWN)
+        .map_err(|message| GenerationError::InvalidSpawn {
+            world_index,
+            message,
+        })?;
+
+    Ok(exhaustive_reach_check(finished_world, spawn))
+}
 
+/// Finds the locations that can be reached once the whole item pool has been collected, and the locations that never can
+///
+/// Fails if the [`Accessibility`] requires every location to be reachable and the item pool is missing something to reach one.
+/// Locations that the logic doesn't reach on these settings even with the full default item pool don't count towards that
+fn total_reach_check<'a>(
+    world_index: usize,
+    world: &World<'a, '_>,
+) -> Result<(Vec<&'a Node>, Vec<&'a Node>), GenerationError> {
+    let reachable_locations = finished_reach_check(world_index, world)?;
+
+    let unreachable_locations = world
+        .graph
+        .nodes
+        .iter()
+        .filter(|&node| {
+            node.can_place()
+                && !reachable_locations
+                    .iter()
+                    .any(|&reachable| reachable.index() == node.index())
+                && !world.preplacements.contains_key(node.trigger().unwrap())
+        })
+        .collect::<Vec<_>>();
+
+    if world.player.settings.accessibility == Accessibility::Full
+        && !unreachable_locations.is_empty()
+    {
+        // For instance Moki has two pickups it never reaches, which shouldn't rule out full accessibility
+        let mut logical_world = world.clone();
+        logical_world.pool = Pool::preset(world.player.settings, world.graph);
+        let logical_locations = finished_reach_check(world_index, &logical_world)?;
+
+        let locations = unreachable_locations
+            .iter()
+            .filter(|node| {
+                logical_locations
+                    .iter()
+                    .any(|logical| logical.index() == node.index())
+            })
+            .map(|node| node.identifier().to_string())
+            .collect::<Vec<_>>();
+        if !locations.is_empty() {
+            return Err(GenerationError::InaccessibleLocations {
+                world_index,
+                locations,
+            });
+        }
+    }
+
+    Ok((reachable_locations, unreachable_locations))
+}
+
+/// Estimates which locations may be reachable midway through the seed
+///
+/// A location counts as reachable midway if any of several random halves of the progression from the item pool can reach it
+fn early_reach_check<'a>(
+    world: &World<'a, '_>,
+    spawn: &'a Node,
+    rng: &mut impl Rng,
+) -> Vec<&'a Node> {
+    let mut progression = world
+        .pool
+        .inventory
+        .items
+        .iter()
+        .filter(|(item, _)| item.is_progression(world.player.settings.difficulty))
+        .flat_map(|(item, amount)| (0..*amount).map(move |_| item))
+        .collect::<Vec<_>>();
+    let half = progression.len() / 2;
+
+    let mut early_locations = Vec::<&Node>::new();
+    for _ in 0..EARLY_REACH_SAMPLES {
+        let mut early_world = world.clone();
+        progression.shuffle(rng);
+        for &item in &progression[..half] {
+            early_world.grant_player(item.clone(), 1);
+        }
+        early_world.grant_player(Item::SpiritLight(1), world.pool.spirit_light / 2);
+
+        for node in exhaustive_reach_check(early_world, spawn) {
+            if !early_locations
+                .iter()
+                .any(|early| early.index() == node.index())
+            {
+                early_locations.push(node);
+            }
+        }
+    }
+
+    early_locations
+}
+
+/// Collects all reachable pickups until no new locations become reachable
+fn exhaustive_reach_check<'a>(mut world: World<'a, '_>, spawn: &'a Node) -> Vec<&'a Node> {
     let mut collected_preplacements = Vec::new();
     let mut total_reachable_count = 0;
 
-    let spawn = finished_world.graph.find_spawn(DEFAULT_SPAWN)?;
-
     loop {
-        let mut reachable_locations = finished_world.graph.reached_locations(
-            &finished_world.player,
-            spawn,
-            finished_world.uber_states(),
-            &finished_world.sets,
-        );
+        let mut reachable_locations =
+            world
+                .graph
+                .reached_locations(&world.player, spawn, world.uber_states(), &world.sets);
         let new_reachable_count = reachable_locations.len();
 
         if new_reachable_count > total_reachable_count {
             total_reachable_count = new_reachable_count;
         } else {
             reachable_locations.retain(|&node| node.can_place());
-            return Ok(reachable_locations);
+            return reachable_locations;
         }
 
         reachable_locations.retain(|&node| {
@@ -1426,7 +2376,7 @@ fn total_reach_check<'a>(world: &World<'a, '_>) -> Result<Vec<&'a Node>, String>
         for node in reachable_locations {
             let trigger = node.trigger().unwrap();
             let value = trigger.set_value() as f32;
-            let preplaced = finished_world.set_incremental_uber_state(trigger.identifier, value);
+            let preplaced = world.set_incremental_uber_state(trigger.identifier, value);
             if preplaced {
                 collected_preplacements.push(node.index());
             }
@@ -1434,10 +2384,111 @@ fn total_reach_check<'a>(world: &World<'a, '_>) -> Result<Vec<&'a Node>, String>
     }
 }
 
+/// Returns the locations that have to be reachable to finish the game with the world's [`Goal`]s
+///
+/// These are the locations in Willow's End and any locations holding Trees, Wisps, Quests or Relics if the goals require them
+fn goal_locations<'a>(world_context: &WorldContext<'a, '_>) -> Vec<&'a Node> {
+    let relic_locations = world_context
+        .placements
+        .iter()
+        .filter(|placement| matches!(placement.item, Item::Relic(_)))
+        .filter_map(|placement| placement.node)
+        .collect::<Vec<_>>();
+
+    world_context
+        .reachable_locations
+        .iter()
+        .copied()
+        .filter(|&node| is_goal_location(node, &world_context.world.goals, &relic_locations))
+        .collect()
+}
+
+/// Checks whether the location has to be reachable to finish the game with the given [`Goal`]s
+pub(crate) fn is_goal_location(node: &Node, goals: &[Goal], relic_locations: &[&Node]) -> bool {
+    node.zone() == Some(Zone::Willow)
+        || goals.iter().any(|goal| match goal {
+            Goal::Wisps => node
+                .trigger()
+                .is_some_and(|trigger| WISP_STATES.contains(&trigger.identifier)),
+            Goal::Trees => node
+                .trigger()
+                .is_some_and(|trigger| trigger.identifier.uber_group == TREE_UBER_GROUP),
+            Goal::Quests => node.node_kind() == NodeKind::Quest,
+            Goal::Relics(_) | Goal::RelicChance(_) | Goal::RelicRules(_) => relic_locations
+                .iter()
+                .any(|relic_location| relic_location.index() == node.index()),
+        })
+}
+
+/// Hands the locations that were left unreached over to [`place_remaining`] once a world using [`Accessibility::Beatable`] reached its goal
+///
+/// Unlike with other accessibility settings, these may hold any item
+fn release_unreached<'a, R, I>(
+    world_index: usize,
+    world_contexts: &mut [WorldContext<'a, '_>],
+    context: &mut GeneratorContext<'_, R, I>,
+) -> Result<(), GenerationError>
+where
+    R: Rng,
+    I: Iterator<Item = u16>,
+{
+    let world_context = &mut world_contexts[world_index];
+
+    let mut unreached = world_context
+        .reachable_locations
+        .iter()
+        .copied()
+        .filter(|&node| {
+            let trigger = node.trigger().unwrap();
+            !world_context.world.preplacements.contains_key(trigger)
+                && !world_context
+                    .placements
+                    .iter()
+                    .any(|placement| &placement.trigger == trigger)
+                && !world_context
+                    .placeholders
+                    .iter()
+                    .any(|placeholder| placeholder.index() == node.index())
+        })
+        .collect::<Vec<_>>();
+    unreached.append(&mut world_context.unreachable_locations);
+    log::trace!(
+        "(World {}): Reached the goal, {} locations may hold any item",
+        world_index,
+        unreached.len()
+    );
+
+    let plando = mem::take(&mut world_context.plando);
+    let mut junk = Vec::new();
+    unreached.retain(|&node| {
+        let excluded = world_context
+            .excluded_locations
+            .iter()
+            .any(|excluded| excluded.index() == node.index());
+        let pinned = plando
+            .iter()
+            .any(|(plando_node, _)| plando_node.index() == node.index());
+        if excluded && !pinned {
+            junk.push(node);
+        }
+        !excluded && !pinned
+    });
+    world_context.placeholders.append(&mut unreached);
+
+    for (node, item) in plando {
+        place_plando(world_index, node, item, world_contexts, context)?;
+    }
+    for node in junk {
+        excluded_placement(world_index, node, world_contexts, context)?;
+    }
+
+    Ok(())
+}
+
 fn flush_item_pool<'a, R, I>(
     world_contexts: &mut [WorldContext<'a, '_>],
     context: &mut GeneratorContext<'_, R, I>,
-) -> Result<(), String>
+) -> Result<(), GenerationError>
 where
     R: Rng,
     I: Iterator<Item = u16>,
@@ -1482,17 +2533,740 @@ where
         }
     }
 
-    Ok(())
+    Ok(())
+}
+
+/// A progression item placed by [`assumed_fill`], which logic may use once its location has been reached
+#[derive(Clone)]
+struct AssumedPlacement<'a> {
+    origin_world_index: usize,
+    node: &'a Node,
+    target_world_index: usize,
+    item: Item,
+}
+
+/// Collects everything reachable in all worlds one sphere at a time, granting placed items to their target worlds once their location is reached
+///
+/// Returns the newly reached locations that can hold items for each sphere and world
+///
+/// If `paths` is given, how these locations were reached gets recorded into it in the same layout
+fn sphere_search<'a>(
+    worlds: &mut [World<'a, '_>],
+    spawns: &[&'a Node],
+    placements: &[AssumedPlacement<'a>],
+    mut paths: Option<&mut Vec<Vec<Vec<LogicPath>>>>,
+) -> Vec<Vec<Vec<&'a Node>>> {
+    for placement in placements {
+        if placement.node.index() == usize::MAX {
+            // Items on the spawn pickup node are collected right away
+            worlds[placement.target_world_index].grant_player(placement.item.clone(), 1);
+        }
+    }
+
+    let mut collected = vec![FxHashSet::<usize>::default(); worlds.len()];
+    let mut spheres = Vec::new();
+
+    loop {
+        let reached = worlds
+            .iter()
+            .zip(spawns)
+            .zip(&collected)
+            .map(|((world, &spawn), world_collected)| {
+                let mut reached = world.graph.reached_locations(
+                    &world.player,
+                    spawn,
+                    world.uber_states(),
+                    &world.sets,
+                );
+                reached.retain(|node| {
+                    node.trigger().is_some() && !world_collected.contains(&node.index())
+                });
+                // We need to ensure that if multiple quest steps are unlocked at the same time, their uberState values are set in order.
+                // Otherwise we would block placements on the skipped steps
+                reached.sort_unstable_by_key(|node| {
+                    node.trigger().map_or(0, UberStateTrigger::set_value)
+                });
+                reached
+            })
+            .collect::<Vec<_>>();
+
+        if reached.iter().all(Vec::is_empty) {
+            return spheres;
+        }
+
+        let reach_paths = paths.is_some().then(|| {
+            worlds
+                .iter()
+                .zip(spawns)
+                .map(|(world, spawn)| {
+                    world
+                        .graph
+                        .reach_paths(&world.player, spawn, world.uber_states(), &world.sets)
+                })
+                .collect::<Vec<_>>()
+        });
+
+        let mut sphere = Vec::with_capacity(worlds.len());
+        for (world_index, world_reached) in reached.into_iter().enumerate() {
+            let mut locations = Vec::new();
+
+            for node in world_reached {
+                collected[world_index].insert(node.index());
+
+                let trigger = node.trigger().unwrap();
+                let value = trigger.set_value() as f32;
+                let preplaced =
+                    worlds[world_index].set_incremental_uber_state(trigger.identifier, value);
+                if !preplaced && node.can_place() {
+                    locations.push(node);
+                }
+
+                for placement in placements.iter().filter(|placement| {
+                    placement.origin_world_index == world_index
+                        && placement.node.index() == node.index()
+                }) {
+                    worlds[placement.target_world_index].grant_player(placement.item.clone(), 1);
+                }
+            }
+
+            sphere.push(locations);
+        }
+        if let (Some(paths), Some(reach_paths)) = (&mut paths, reach_paths) {
+            paths.push(
+                sphere
+                    .iter()
+                    .zip(reach_paths)
+                    .map(|(locations, reach_paths)| {
+                        locations
+                            .iter()
+                            .filter_map(|&node| reach_paths.path(node))
+                            .collect()
+                    })
+                    .collect(),
+            );
+        }
+        spheres.push(sphere);
+    }
+}
+
+/// Places the progression of all worlds backwards, then fills the remaining locations randomly
+///
+/// Every progression item goes on a location that is reachable while assuming the player has all progression that hasn't been placed yet.
+/// Since nothing is placed based on the current reach, the resulting placements get checked and sorted into spoiler groups afterwards
+fn assumed_fill<'a, R, I>(
+    spawns: &[&'a Node],
+    goal_nodes: &[Vec<&'a Node>],
+    world_contexts: &mut [WorldContext<'a, '_>],
+    context: &mut GeneratorContext<'_, R, I>,
+) -> Result<(), GenerationError>
+where
+    R: Rng,
+    I: Iterator<Item = u16>,
+{
+    context.finalize_spoiler_group()?;
+
+    let base_worlds = world_contexts
+        .iter()
+        .map(|world_context| world_context.world.clone())
+        .collect::<Vec<_>>();
+
+    let mut free_locations = world_contexts
+        .iter()
+        .map(|world_context| {
+            world_context
+                .reachable_locations
+                .iter()
+                .copied()
+                .filter(|&node| {
+                    let trigger = node.trigger().unwrap();
+                    !world_context.world.preplacements.contains_key(trigger)
+                        && !world_context
+                            .placements
+                            .iter()
+                            .any(|placement| &placement.trigger == trigger)
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    for world_index in 0..context.world_count {
+        let plando = mem::take(&mut world_contexts[world_index].plando);
+        for (node, item) in plando {
+            place_plando(world_index, node, item, world_contexts, context)?;
+        }
+
+        let mut reached_excluded = Vec::new();
+        free_locations[world_index].retain(|&node| {
+            let excluded = world_contexts[world_index]
+                .excluded_locations
+                .iter()
+                .any(|excluded| excluded.index() == node.index());
+            if excluded {
+                reached_excluded.push(node);
+            }
+            !excluded
+        });
+        for node in reached_excluded {
+            excluded_placement(world_index, node, world_contexts, context)?;
+        }
+    }
+
+    // Locations that can be reached without any items are needed for the last progression items, so other placements avoid them where possible
+    let mut early_locations = vec![Vec::new(); context.world_count];
+    for sphere in sphere_search(&mut base_worlds.clone(), spawns, &[], None) {
+        for (world_early_locations, locations) in early_locations.iter_mut().zip(sphere) {
+            world_early_locations.extend(locations);
+        }
+    }
+    let is_early = |world_index: usize, node: &Node| {
+        early_locations[world_index]
+            .iter()
+            .any(|early| early.index() == node.index())
+    };
+
+    let mut placed = Vec::<AssumedPlacement>::new();
+    let mut forced = Vec::<AssumedPlacement>::new();
+
+    // Spirit Light gets placed first so logic knows where to find it when placing progression
+    for world_index in 0..context.world_count {
+        let world_context = &mut world_contexts[world_index];
+        let item_count = world_context.world.pool.inventory.item_count() as usize;
+        let world_free_locations = &mut free_locations[world_index];
+
+        let mut candidates = world_free_locations
+            .iter()
+            .copied()
+            .filter(|node| !node.trigger().unwrap().identifier.is_purchasable())
+            .collect::<Vec<_>>();
+        candidates.shuffle(context.rng);
+        candidates.sort_by_key(|&node| is_early(world_index, node));
+        candidates.truncate(world_free_locations.len().saturating_sub(item_count));
+        world_free_locations.retain(|node| {
+            !candidates
+                .iter()
+                .any(|candidate| candidate.index() == node.index())
+        });
+
+        let mut unreachable_shops = Vec::new();
+        for node in world_context.unreachable_locations.drain(..) {
+            if node.trigger().unwrap().identifier.is_purchasable() {
+                unreachable_shops.push(node);
+            } else {
+                candidates.push(node);
+            }
+        }
+        world_context.unreachable_locations = unreachable_shops;
+
+        log::trace!(
+            "(World {}): Placing Spirit Light on {} locations",
+            world_index,
+            candidates.len()
+        );
+
+        let zones = candidates
+            .iter()
+            .map(|node| node.zone())
+            .collect::<Vec<_>>();
+        let amounts = world_context
+            .spirit_light_rng
+            .distribute(&zones, context.rng)?;
+        for (node, amount) in candidates.into_iter().zip(amounts) {
+            let item = Item::SpiritLight(amount);
+            place_item(
+                world_index,
+                world_index,
+                node,
+                false,
+                false,
+                item.clone(),
+                world_contexts,
+                context,
+            )?;
+            placed.push(AssumedPlacement {
+                origin_world_index: world_index,
+                node,
+                target_world_index: world_index,
+                item,
+            });
+        }
+    }
+
+    let world_settings = world_contexts
+        .iter()
+        .map(|world_context| world_context.world.player.settings)
+        .collect::<Vec<_>>();
+    let has_max_step = |world_index: usize, item: &Item| {
+        world_settings[world_index]
+            .placement_constraints
+            .iter()
+            .any(|constraint| &constraint.item == item && constraint.max_step.is_some())
+    };
+    let has_max_step = &has_max_step;
+
+    // Items that have to be reached by a certain step get placed here as well, even if they aren't progression, since the remaining placements ignore steps
+    let mut progression = world_contexts
+        .iter()
+        .enumerate()
+        .flat_map(|(world_index, world_context)| {
+            let difficulty = world_context.world.player.settings.difficulty;
+            world_context
+                .world
+                .pool
+                .inventory
+                .items
+                .iter()
+                .filter(move |(item, _)| {
+                    item.is_progression(difficulty) || has_max_step(world_index, item)
+                })
+                .flat_map(move |(item, amount)| {
+                    (0..*amount).map(move |_| (world_index, item.clone()))
+                })
+        })
+        .collect::<Vec<_>>();
+    progression.shuffle(context.rng);
+
+    // Keystones that can be used on any door risk being spent on the wrong door, so they get placed first.
+    // Logic only relies on the keystones placed so far if they are enough to open every door they could be spent on.
+    // Items that have to be reached by a certain step come last, once the placements they can be reached with are known
+    let keys_anywhere = world_contexts
+        .iter()
+        .map(|world_context| {
+            world_context.world.player.settings.keystone_mode == KeystoneMode::KeysAnywhere
+        })
+        .collect::<Vec<_>>();
+    let is_loose_keystone = |world_index: usize, item: &Item| {
+        keys_anywhere[world_index] && *item == Item::Resource(Resource::Keystone)
+    };
+    progression.sort_by_key(|(world_index, item)| {
+        (
+            !is_loose_keystone(*world_index, item),
+            has_max_step(*world_index, item),
+        )
+    });
+    let loose_keystones = progression
+        .iter()
+        .filter(|(world_index, item)| is_loose_keystone(*world_index, item))
+        .count();
+
+    log::trace!(
+        "Placing {} progression items with assumed fill",
+        progression.len()
+    );
+
+    let first_step = context.current_step();
+
+    for (index, (target_world_index, item)) in progression.iter().enumerate() {
+        let target_world_index = *target_world_index;
+        let keystone_locked = index < loose_keystones;
+
+        let mut worlds = base_worlds.clone();
+        for (assumed_world_index, assumed) in &progression[index + 1..] {
+            if !(keystone_locked && is_loose_keystone(*assumed_world_index, assumed)) {
+                worlds[*assumed_world_index].grant_player(assumed.clone(), 1);
+            }
+        }
+
+        let spheres = if keystone_locked {
+            let mut keyed_worlds = worlds.clone();
+            let keyed_spheres = sphere_search(&mut keyed_worlds, spawns, &placed, None);
+
+            let world = &keyed_worlds[target_world_index];
+            let keystones = world
+                .player
+                .inventory
+                .get(&Item::Resource(Resource::Keystone));
+            let required_keystones: u32 = world
+                .graph
+                .reached_locations(
+                    &world.player,
+                    spawns[target_world_index],
+                    world.uber_states(),
+                    &world.sets,
+                )
+                .iter()
+                .filter_map(|node| {
+                    KEYSTONE_DOORS
+                        .iter()
+                        .find(|&&(identifier, _, _)| identifier == node.identifier())
+                        .map(|(_, _, keystones)| *keystones)
+                })
+                .sum();
+
+            if required_keystones <= keystones {
+                keyed_spheres
+            } else {
+                let placed = placed
+                    .iter()
+                    .filter(|placement| {
+                        !is_loose_keystone(placement.target_world_index, &placement.item)
+                    })
+                    .cloned()
+                    .collect::<Vec<_>>();
+                sphere_search(&mut worlds, spawns, &placed, None)
+            }
+        } else {
+            sphere_search(&mut worlds, spawns, &placed, None)
+        };
+
+        let category = worlds[target_world_index].graph.item_category(item);
+        let is_multiworld_spread =
+            item.is_multiworld_spread() && !context.multiworld.is_local(category);
+        let origin_world_indices = if is_multiworld_spread {
+            context.origin_world_order(target_world_index, category)
+        } else {
+            vec![target_world_index]
+        };
+
+        let settings = world_settings[target_world_index];
+        let allowed =
+            |node: &Node, step: usize| violated_constraint(settings, item, node, step).is_none();
+        // The assumed items may end up on locations that are reached late, so the spheres with all assumed items can be too early for max_step.
+        // Only using the placements so far can't be too early, since every later placement can only help reaching locations sooner
+        let placed_spheres = has_max_step(target_world_index, item)
+            .then(|| sphere_search(&mut base_worlds.clone(), spawns, &placed, None));
+        let step_spheres = placed_spheres.as_ref().unwrap_or(&spheres);
+
+        let mut location = None;
+        for origin_world_index in origin_world_indices {
+            let world_free_locations = &mut free_locations[origin_world_index];
+            // sort_assumed_spoiler numbers its spoiler groups starting from first_step as well
+            let mut candidates = step_spheres
+                .iter()
+                .enumerate()
+                .flat_map(|(sphere_index, sphere)| {
+                    sphere[origin_world_index]
+                        .iter()
+                        .map(move |node| (first_step + sphere_index, node))
+                })
+                .filter_map(|(step, node)| {
+                    world_free_locations
+                        .iter()
+                        .position(|free| free.index() == node.index())
+                        .filter(|&index| allowed(world_free_locations[index], step))
+                })
+                .collect::<Vec<_>>();
+            if candidates
+                .iter()
+                .any(|&index| !is_early(origin_world_index, world_free_locations[index]))
+            {
+                candidates
+                    .retain(|&index| !is_early(origin_world_index, world_free_locations[index]));
+            }
+
+            if let Some(&index) = candidates.choose(context.rng) {
+                location = Some((origin_world_index, world_free_locations.swap_remove(index)));
+                break;
+            }
+        }
+
+        let (origin_world_index, node) = match location {
+            Some(location) => location,
+            None => {
+                let target_world_context = &mut world_contexts[target_world_index];
+                let spawn_pickup_node = &target_world_context.world.graph.spawn_pickup_node;
+                if target_world_context.spawn_slots > 0 && allowed(spawn_pickup_node, first_step) {
+                    target_world_context.spawn_slots -= 1;
+                    (target_world_index, spawn_pickup_node)
+                } else if let Some(err) =
+                    unsatisfiable_constraint(target_world_index, settings, item)
+                {
+                    return Err(err);
+                } else {
+                    return Err(GenerationError::NotEnoughSlots {
+                        world_index: target_world_index,
+                        item: item.to_string(),
+                    });
+                }
+            }
+        };
+
+        world_contexts[target_world_index]
+            .world
+            .pool
+            .remove(item, 1);
+        let is_progression = item.is_progression(settings.difficulty);
+        place_item(
+            origin_world_index,
+            target_world_index,
+            node,
+            false,
+            is_progression,
+            item.clone(),
+            world_contexts,
+            context,
+        )?;
+        let placement = AssumedPlacement {
+            origin_world_index,
+            node,
+            target_world_index,
+            item: item.clone(),
+        };
+        placed.push(placement.clone());
+        if is_progression {
+            forced.push(placement);
+        }
+    }
+
+    for (world_context, world_free_locations) in world_contexts.iter_mut().zip(free_locations) {
+        world_context.placeholders.extend(world_free_locations);
+    }
+    place_remaining(world_contexts, context)?;
+
+    sort_assumed_spoiler(
+        base_worlds,
+        spawns,
+        goal_nodes,
+        forced,
+        world_contexts,
+        context,
+    )
+}
+
+/// Checks that every location, or only the goal for [`Accessibility::Beatable`], can be reached with the finished placements of [`assumed_fill`] and splits its placements into spoiler groups by the sphere they get reached in
+///
+/// `forced` are the progression placements, which get listed as the forced items of their spoiler group like the progression of a forward fill would
+fn sort_assumed_spoiler<'a, R, I>(
+    mut base_worlds: Vec<World<'a, '_>>,
+    spawns: &[&'a Node],
+    goal_nodes: &[Vec<&'a Node>],
+    mut forced: Vec<AssumedPlacement<'a>>,
+    world_contexts: &[WorldContext<'a, '_>],
+    context: &mut GeneratorContext<'_, R, I>,
+) -> Result<(), GenerationError>
+where
+    R: Rng,
+    I: Iterator<Item = u16>,
+{
+    let find_node = |world_index: usize, identifier: &str| {
+        let graph = world_contexts[world_index].world.graph;
+        if identifier == graph.spawn_pickup_node.identifier() {
+            Some(&graph.spawn_pickup_node)
+        } else {
+            find_location(graph, identifier)
+        }
+    };
+
+    let placements = context
+        .spoiler_groups
+        .iter()
+        .flat_map(|group| &group.placements)
+        .chain(&context.current_spoiler_group.placements)
+        .filter_map(|placement| {
+            find_node(placement.origin_world_index, &placement.location.identifier).map(|node| {
+                AssumedPlacement {
+                    origin_world_index: placement.origin_world_index,
+                    node,
+                    target_world_index: placement.target_world_index,
+                    item: placement.item.clone(),
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut paths = Vec::new();
+    let spheres = sphere_search(
+        &mut base_worlds,
+        spawns,
+        &placements,
+        context.spoiler_paths.then_some(&mut paths),
+    );
+
+    let unreached = world_contexts
+        .iter()
+        .enumerate()
+        .map(|(world_index, world_context)| {
+            let required =
+                if world_context.world.player.settings.accessibility == Accessibility::Beatable {
+                    &goal_nodes[world_index]
+                } else {
+                    &world_context.reachable_locations
+                };
+            required
+                .iter()
+                .filter(|&&node| {
+                    !world_context
+                        .world
+                        .preplacements
+                        .contains_key(node.trigger().unwrap())
+                        && !spheres.iter().any(|sphere| {
+                            sphere[world_index]
+                                .iter()
+                                .any(|reached| reached.index() == node.index())
+                        })
+                })
+                .map(|node| node.identifier().to_string())
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    if unreached.iter().any(|identifiers| !identifiers.is_empty()) {
+        return Err(GenerationError::UnreachableLocations { unreached });
+    }
+
+    let mut paths = paths.into_iter();
+    let mut groups = spheres
+        .iter()
+        .map(|sphere| SpoilerGroup {
+            reachable: sphere
+                .iter()
+                .map(|locations| locations.iter().map(|node| node.summary()).collect())
+                .collect(),
+            paths: paths.next().unwrap_or_default(),
+            ..SpoilerGroup::default()
+        })
+        .collect::<Vec<_>>();
+    // Without any spheres nothing is reachable, but the placements still need a group
+    if groups.is_empty() {
+        groups.push(SpoilerGroup::default());
+    }
+    let last_group = groups.len() - 1;
+
+    let first_step = context.current_step();
+    context.current_spoiler_group.forced_items = Inventory::default();
+    for placement in mem::take(&mut context.current_spoiler_group.placements) {
+        let group_index = if placement.location.identifier
+            == world_contexts[placement.origin_world_index]
+                .world
+                .graph
+                .spawn_pickup_node
+                .identifier()
+        {
+            0
+        } else {
+            spheres
+                .iter()
+                .position(|sphere| {
+                    sphere[placement.origin_world_index]
+                        .iter()
+                        .any(|node| node.identifier() == placement.location.identifier)
+                })
+                .unwrap_or(last_group)
+        };
+
+        let step = first_step + group_index;
+        let settings = world_contexts[placement.target_world_index]
+            .world
+            .player
+            .settings;
+        // assumed_fill only places constrained items on locations reached early enough with the placements before them
+        debug_assert!(
+            settings.placement_constraints.iter().all(|constraint| {
+                constraint.item != placement.item
+                    || constraint.allows(placement.location.zone, step)
+            }),
+            "{} on {} violates its placement constraint in step {}",
+            placement.item_name,
+            placement.location.identifier,
+            step
+        );
+
+        if let Some(index) = forced.iter().position(|forced| {
+            forced.origin_world_index == placement.origin_world_index
+                && forced.node.identifier() == placement.location.identifier
+                && forced.item == placement.item
+        }) {
+            forced.swap_remove(index);
+            groups[group_index]
+                .forced_items
+                .grant(placement.item.clone(), 1);
+        }
+        groups[group_index].placements.push(placement);
+    }
+
+    for group in groups {
+        context.current_spoiler_group = group;
+        context.finalize_spoiler_group()?;
+    }
+
+    Ok(())
+}
+
+/// Collects the placements and spoiler of every world once all locations have been filled
+fn finish_placements<'graph, 'settings, R, I>(
+    mut world_contexts: Vec<WorldContext<'graph, 'settings>>,
+    spawns: Vec<&'graph Node>,
+    context: GeneratorContext<'_, R, I>,
+) -> (Vec<SeedWorld<'graph, 'settings>>, SeedSpoiler)
+where
+    R: Rng,
+    I: Iterator<Item = u16>,
+{
+    for world_context in &mut world_contexts {
+        apply_shop_budget(world_context);
+    }
+
+    let excluded_locations = world_contexts
+        .iter()
+        .map(|world_context| {
+            world_context
+                .excluded_locations
+                .iter()
+                .map(|node| node.summary())
+                .collect()
+        })
+        .collect();
+
+    let spirit_light = world_contexts
+        .iter()
+        .map(|world_context| SpiritLightSummary {
+            distribution: world_context.spirit_light_rng.distribution,
+            total: world_context.spirit_light_rng.placed,
+        })
+        .collect();
+
+    let relics = world_contexts
+        .iter()
+        .map(|world_context| {
+            world_context
+                .placements
+                .iter()
+                .filter(|placement| matches!(placement.item, Item::Relic(_)))
+                .filter_map(|placement| placement.node.map(Node::summary))
+                .collect()
+        })
+        .collect();
+
+    let (seed_worlds, spawns) = world_contexts
+        .into_iter()
+        .zip(spawns)
+        .map(|(world_context, spawn)| {
+            (
+                SeedWorld {
+                    flags: Vec::new(), // filled later
+                    spawn,
+                    placements: world_context.placements,
+                    headers: String::new(), // Filled later
+                    hints: Vec::new(),      // Filled later
+                    world_settings: world_context.world.player.settings,
+                },
+                world_context.spawn.identifier().to_string(),
+            )
+        })
+        .unzip();
+    let groups = context.spoiler_groups;
+
+    let mut spoiler = SeedSpoiler {
+        spawns,
+        groups,
+        excluded_locations,
+        spirit_light,
+        relics,
+        summaries: Vec::new(),
+        playthrough: None,
+    };
+    spoiler.summaries = spoiler.summarize();
+    (seed_worlds, spoiler)
 }
 
 fn generate_placements_from_spawn<'graph, 'settings>(
     worlds: Vec<World<'graph, 'settings>>,
     spawns: Vec<&'graph Node>,
+    multiworld: &MultiworldSettings,
+    placement_strategy: PlacementStrategy,
+    fill_algorithm: FillAlgorithm,
+    spoiler_paths: bool,
+    observer: &mut dyn GenerationObserver,
     rng: &mut impl Rng,
-) -> Result<(Vec<SeedWorld<'graph, 'settings>>, SeedSpoiler), String> {
-    // TODO enforce a max total price for shops
-    let price_range = Uniform::new_inclusive(0.75, 1.25);
-
+) -> Result<(Vec<SeedWorld<'graph, 'settings>>, SeedSpoiler), GenerationError> {
     let mut world_contexts = build_world_contexts(worlds, &spawns, rng)?;
 
     let total_reachable_count: usize = world_contexts
@@ -1506,22 +3280,37 @@ fn generate_placements_from_spawn<'graph, 'settings>(
         multiworld_state_index: 0..,
         spoiler_groups: Vec::new(),
         current_spoiler_group: SpoilerGroup::default(),
-        price_range,
         random_progression: Bernoulli::new(RANDOM_PROGRESSION).unwrap(),
+        multiworld,
+        placement_strategy,
+        spoiler_paths,
+        progression_placements: 0,
+        shared_progression_placements: 0,
+        observer,
         rng,
     };
 
     one_xp(&mut world_contexts, &mut context)?;
     for world_index in 0..context.world_count {
-        if let Some(amount) = world_contexts[world_index].world_tour {
-            place_relics(amount, world_index, &mut world_contexts, &mut context)?;
+        if let Some(world_tour) = world_contexts[world_index].world_tour.take() {
+            place_relics(world_tour, world_index, &mut world_contexts, &mut context)?;
         }
     }
 
+    let goal_nodes = world_contexts
+        .iter()
+        .map(goal_locations)
+        .collect::<Vec<_>>();
+
+    if fill_algorithm == FillAlgorithm::Assumed {
+        assumed_fill(&spawns, &goal_nodes, &mut world_contexts, &mut context)?;
+        return Ok(finish_placements(world_contexts, spawns, context));
+    }
+
     let mut reserved_slots = Vec::<(usize, &Node)>::with_capacity(RESERVE_SLOTS);
 
     loop {
-        context.finalize_spoiler_group();
+        context.finalize_spoiler_group()?;
 
         let mut reach_context = progression_check(&mut world_contexts, &mut context)?;
 
@@ -1532,6 +3321,32 @@ fn generate_placements_from_spawn<'graph, 'settings>(
             &mut context,
         )?;
 
+        // Worlds that only need to be beatable are done once their goal is reachable
+        let goal_reached = (0..context.world_count)
+            .map(|world_index| {
+                world_contexts[world_index]
+                    .world
+                    .player
+                    .settings
+                    .accessibility
+                    == Accessibility::Beatable
+                    && reach_context.reachable_counts[world_index]
+                        < world_contexts[world_index].reachable_locations.len()
+                    && goal_nodes[world_index].iter().all(|goal| {
+                        reach_context.reachable[world_index]
+                            .iter()
+                            .any(|node| node.index() == goal.index())
+                    })
+            })
+            .collect::<Vec<_>>();
+        let all_reached = (0..context.world_count).all(|world_index| {
+            goal_reached[world_index]
+                || reach_context.reachable_counts[world_index]
+                    >= world_contexts[world_index].reachable_locations.len()
+        });
+
+        let mut collected_plando = Vec::new();
+        let mut reached_excluded = Vec::new();
         let mut needs_placement = (0..context.world_count)
             .map(|world_index| {
                 let world_reachable = &mut reach_context.reachable[world_index];
@@ -1595,6 +3410,14 @@ fn generate_placements_from_spawn<'graph, 'settings>(
                         .set_incremental_uber_state(trigger.identifier, value);
                     if preplaced {
                         world_context.collected_preplacements.push(node.index());
+                        if let Some(index) = world_context
+                            .plando
+                            .iter()
+                            .position(|(plando_node, _)| plando_node.index() == node.index())
+                        {
+                            let (node, item) = world_context.plando.swap_remove(index);
+                            collected_plando.push((world_index, node, item));
+                        }
                     } else if node.can_place() {
                         world_needs_placement.push(*node);
                     }
@@ -1605,7 +3428,24 @@ fn generate_placements_from_spawn<'graph, 'settings>(
                     .map(|node| node.summary())
                     .collect::<Vec<_>>();
                 context.current_spoiler_group.reachable.push(locations);
+                if let Some(paths) = &reach_context.paths[world_index] {
+                    let paths = world_needs_placement
+                        .iter()
+                        .filter_map(|&node| paths.path(node))
+                        .collect();
+                    context.current_spoiler_group.paths.push(paths);
+                }
 
+                world_needs_placement.retain(|&node| {
+                    let excluded = world_context
+                        .excluded_locations
+                        .iter()
+                        .any(|excluded| excluded.index() == node.index());
+                    if excluded {
+                        reached_excluded.push((world_index, node));
+                    }
+                    !excluded
+                });
                 world_needs_placement.shrink_to_fit();
                 world_needs_placement.shuffle(context.rng);
 
@@ -1613,8 +3453,19 @@ fn generate_placements_from_spawn<'graph, 'settings>(
             })
             .collect::<Vec<_>>();
 
-        if reach_context.unreached_count == 0 {
-            log::trace!("All locations reached");
+        for (world_index, node, item) in collected_plando {
+            place_plando(world_index, node, item, &mut world_contexts, &mut context)?;
+        }
+        for (world_index, node) in reached_excluded {
+            excluded_placement(world_index, node, &mut world_contexts, &mut context)?;
+        }
+
+        if reach_context.unreached_count == 0 || all_reached {
+            if reach_context.unreached_count == 0 {
+                log::trace!("All locations reached");
+            } else {
+                log::trace!("All goals reached");
+            }
 
             for (world_index, reserved) in reserved_slots {
                 world_contexts[world_index].placeholders.push(reserved);
@@ -1626,31 +3477,17 @@ fn generate_placements_from_spawn<'graph, 'settings>(
                     .placeholders
                     .append(&mut world_needs_placement);
             }
+            for (world_index, goal_reached) in goal_reached.into_iter().enumerate() {
+                if goal_reached {
+                    release_unreached(world_index, &mut world_contexts, &mut context)?;
+                }
+            }
 
             place_remaining(&mut world_contexts, &mut context)?;
 
-            context.finalize_spoiler_group();
-
-            let (seed_worlds, spawns) = world_contexts
-                .into_iter()
-                .zip(spawns)
-                .map(|(world_context, spawn)| {
-                    (
-                        SeedWorld {
-                            flags: Vec::new(), // filled later
-                            spawn,
-                            placements: world_context.placements,
-                            headers: String::new(), // Filled later
-                            world_settings: world_context.world.player.settings,
-                        },
-                        world_context.spawn.identifier().to_string(),
-                    )
-                })
-                .unzip();
-            let groups = context.spoiler_groups;
+            context.finalize_spoiler_group()?;
 
-            let spoiler = SeedSpoiler { spawns, groups };
-            return Ok((seed_worlds, spoiler));
+            return Ok(finish_placements(world_contexts, spawns, context));
         }
 
         if reserved_slots.len() < RESERVE_SLOTS {
@@ -1702,9 +3539,13 @@ fn generate_placements_from_spawn<'graph, 'settings>(
                 }
             }
 
+            force_constrained_items(&mut reserved_slots, &mut world_contexts, &mut context)?;
+
             if any_random_placements {
                 continue;
             }
+        } else {
+            force_constrained_items(&mut reserved_slots, &mut world_contexts, &mut context)?;
         }
 
         force_progression(
@@ -1720,7 +3561,7 @@ fn build_world_contexts<'a, 'b>(
     worlds: Vec<World<'a, 'b>>,
     spawns: &[&'a Node],
     rng: &mut impl Rng,
-) -> Result<Vec<WorldContext<'a, 'b>>, String> {
+) -> Result<Vec<WorldContext<'a, 'b>>, GenerationError> {
     let mut has_warned_about_tp_refill = false;
 
     worlds.into_iter().enumerate().map(|(world_index, mut world)| {
@@ -1779,39 +3620,47 @@ fn build_world_contexts<'a, 'b>(
                     hide_message: false,
                 });
             } else {
-                return Err(format!("Cannot spawn on {} which has no specified coordinates", spawn.identifier()));
+                return Err(GenerationError::InvalidSpawn {
+                    world_index,
+                    message: format!("Cannot spawn on {} which has no specified coordinates", spawn.identifier()),
+                });
             }
         }
 
         let world_tour = world.goals.iter().find_map(|goal|
-            match *goal {
-                Goal::Relics(amount) => Some(amount),
+            match goal {
+                Goal::Relics(amount) => Some(WorldTour::new(*amount)),
                 Goal::RelicChance(chance) => {
-                    if chance == 0.0 { return Some(0); }
+                    if *chance == 0.0 { return Some(WorldTour::new(0)); }
                     loop {
-                        let amount = (0..11).filter(|_| rng.gen_bool(chance)).count();
+                        let amount = (0..11).filter(|_| rng.gen_bool(*chance)).count();
                         if amount > 0 {
-                            return Some(amount);
+                            return Some(WorldTour::new(amount));
                         }
                     }
                 },
+                Goal::RelicRules(rules) => Some(WorldTour {
+                    amount: rng.gen_range(rules.min()..=rules.max()),
+                    min: rules.min(),
+                    zones: rules.zones().to_vec(),
+                    avoid_shops_and_quests: rules.avoid_shops_and_quests(),
+                    avoid_late_locations: rules.avoid_late_locations(),
+                }),
                 _ => None,
             }
         );
 
-        let reachable_locations = total_reach_check(&world)?;
+        let (reachable_locations, unreachable_locations) = total_reach_check(world_index, &world)?;
 
-        let unreachable_locations = world.graph.nodes.iter()
-            .filter(|&node|
-                node.can_place() &&
-                !reachable_locations.iter().any(|&reachable| reachable.index() == node.index()) &&
-                !world.preplacements.iter().any(|(trigger, _)| trigger == node.trigger().unwrap())
-            ).collect::<Vec<_>>();
         #[cfg(feature = "log")]
         if !unreachable_locations.is_empty() {
             let identifiers = unreachable_locations.iter().map(|&node| node.identifier()).collect::<Vec<_>>();
             if !(unreachable_locations.len() == 2 && world.player.settings.difficulty == Difficulty::Moki) {  // moki always has two unreachable pickups
-                log::warning!("(World {}): {} locations are unreachable on these settings! These will only hold Spirit Light.", world_index, identifiers.len());
+                if world.player.settings.accessibility == Accessibility::Beatable {
+                    log::warning!("(World {}): {} locations are unreachable on these settings! These may hold any item.", world_index, identifiers.len());
+                } else {
+                    log::warning!("(World {}): {} locations are unreachable on these settings! These will only hold Spirit Light.", world_index, identifiers.len());
+                }
             }
             log::trace!("(World {}): Unreachable locations on these settings: {}", world_index, format_identifiers(identifiers));
         }
@@ -1823,24 +3672,52 @@ fn build_world_contexts<'a, 'b>(
             })
             .count() - 1;  // 1 will be 1xp
         let mut spirit_light_slots = world_slots.saturating_sub(world.pool.inventory.item_count() as usize);
-        if let Some(amount) = world_tour { spirit_light_slots -= amount; }
+        if let Some(world_tour) = &world_tour { spirit_light_slots -= world_tour.amount; }
         log::trace!("(World {}): Estimated {}/{} slots for Spirit Light", world_index, spirit_light_slots, world_slots);
 
-        let spirit_light_rng = SpiritLightAmounts::new(world.pool.spirit_light as f32, spirit_light_slots as f32, 0.75, 1.25);
+        let distribution = world.player.settings.spirit_light_distribution;
+        if let SpiritLightDistribution::Capped(cap) = distribution {
+            if u64::from(cap) * (spirit_light_slots as u64) < u64::from(world.pool.spirit_light) {
+                return Err(GenerationError::InsufficientSpiritLightCap { world_index, cap, spirit_light: world.pool.spirit_light, slots: spirit_light_slots });
+            }
+        }
+        // Every Spirit Light slot and the 1xp pickup need at least 1 Spirit Light
+        if (world.pool.spirit_light as usize) <= spirit_light_slots {
+            return Err(GenerationError::InsufficientSpiritLight { world_index, spirit_light: world.pool.spirit_light, slots: spirit_light_slots + 1 });
+        }
+        let spirit_light_rng = SpiritLightAmounts::new(world_index, distribution, world.pool.spirit_light, spirit_light_slots, 0.75, 1.25);
         let random_spirit_light = Bernoulli::new(spirit_light_slots as f64 / world_slots as f64).unwrap();
 
+        let plando = world.player.settings.plando.iter()
+            .filter_map(|plando| find_location(world.graph, &plando.location).map(|node| (node, plando.item.clone())))
+            .collect();
+
+        let excluded_locations = world.graph.nodes.iter()
+            .filter(|&node| {
+                node.can_place()
+                && !world.preplacements.contains_key(node.trigger().unwrap())
+                && world.player.settings.excluded_locations.iter().any(|excluded| excluded.matches(node.identifier(), node.zone()))
+            })
+            .collect::<Vec<_>>();
+        log::trace!("(World {}): Excluding {} locations from progression", world_index, excluded_locations.len());
+
         let shop_slots = world.graph.nodes.iter().filter(|&node|
             node.trigger().map_or(false, |trigger|
                 trigger.identifier.is_purchasable()
                 && !world.preplacements.contains_key(trigger)
         )).count();
 
+        let shop_prices = &world.player.settings.shop_prices;
+        let price_range = Uniform::new_inclusive(shop_prices.min_multiplier, shop_prices.max_multiplier);
+
         Ok(WorldContext {
             world,
             spawn,
             placements,
             placeholders: Vec::with_capacity(300),
             collected_preplacements: Vec::new(),
+            plando,
+            excluded_locations,
             spawn_slots,
             prevent_sharing: 5,
             reachable_locations,
@@ -1848,16 +3725,24 @@ fn build_world_contexts<'a, 'b>(
             spirit_light_rng,
             random_spirit_light,
             shop_slots,
+            price_range,
+            shop_prices: Vec::new(),
             world_tour,
         })
-    }).collect::<Result<Vec<_>, String>>()
+    }).collect::<Result<Vec<_>, GenerationError>>()
 }
 
 fn pick_spawn<'a>(
+    world_index: usize,
     graph: &'a Graph,
     world_settings: &WorldSettings,
     rng: &mut impl Rng,
-) -> Result<&'a Node, String> {
+) -> Result<&'a Node, GenerationError> {
+    let invalid_spawn = |message| GenerationError::InvalidSpawn {
+        world_index,
+        message,
+    };
+
     let spawn = match &world_settings.spawn {
         Spawn::Random => {
             let spawns = world_settings.difficulty.spawn_locations();
@@ -1866,22 +3751,22 @@ fn pick_spawn<'a>(
                 .iter()
                 .filter(|&node| spawns.contains(&node.identifier()))
                 .choose(rng)
-                .ok_or_else(|| String::from("No valid spawn locations available"))?
+                .ok_or_else(|| invalid_spawn(String::from("No valid spawn locations available")))?
         }
         Spawn::FullyRandom => graph
             .nodes
             .iter()
             .filter(|node| node.can_spawn())
             .choose(rng)
-            .ok_or_else(|| String::from("No valid spawn locations available"))?,
+            .ok_or_else(|| invalid_spawn(String::from("No valid spawn locations available")))?,
         Spawn::Set(spawn_loc) => {
             let node = graph
                 .nodes
                 .iter()
                 .find(|&node| node.identifier() == spawn_loc)
-                .ok_or_else(|| format!("Spawn {} not found", spawn_loc))?;
+                .ok_or_else(|| invalid_spawn(format!("Spawn {} not found", spawn_loc)))?;
             if !node.can_spawn() {
-                return Err(format!("{} is not a valid spawn", spawn_loc));
+                return Err(invalid_spawn(format!("{} is not a valid spawn", spawn_loc)));
             }
             node
         }
@@ -1889,46 +3774,731 @@ fn pick_spawn<'a>(
     Ok(spawn)
 }
 
+/// Finds the location with the given identifier, as long as it can hold items
+pub(super) fn find_location<'a>(graph: &'a Graph, location: &str) -> Option<&'a Node> {
+    graph
+        .nodes
+        .iter()
+        .find(|node| node.can_place() && node.identifier() == location)
+}
+
+/// Checks that every [`PlandoPlacement`] of the world can logically be collected, and that the goals can still be reached with the items pinned in place
+///
+/// `unchanged` is the world before pinning the items
+pub(super) fn validate_plando(
+    world_index: usize,
+    unchanged: &World,
+    world: &World,
+) -> Result<(), GenerationError> {
+    let plando = &world.player.settings.plando;
+    if plando.is_empty() {
+        return Ok(());
+    }
+
+    let (reachable_locations, _) = total_reach_check(world_index, world)?;
+
+    for PlandoPlacement { location, item } in plando {
+        if !reachable_locations
+            .iter()
+            .any(|node| node.identifier() == location)
+        {
+            return Err(GenerationError::UnreachablePlandoPlacement {
+                world_index,
+                location: location.clone(),
+                item: item.to_string(),
+            });
+        }
+    }
+
+    // Pinned items are only collected once their location is reached, which may lock the goals behind them
+    let relic_locations = plando
+        .iter()
+        .filter(|plando| matches!(plando.item, Item::Relic(_)))
+        .filter_map(|plando| find_location(world.graph, &plando.location))
+        .collect::<Vec<_>>();
+    let locations = finished_reach_check(world_index, unchanged)?
+        .into_iter()
+        .filter(|node| {
+            is_goal_location(node, &world.goals, &relic_locations)
+                && !reachable_locations
+                    .iter()
+                    .any(|reachable| reachable.index() == node.index())
+        })
+        .map(|node| node.identifier().to_string())
+        .collect::<Vec<_>>();
+    if !locations.is_empty() {
+        return Err(GenerationError::UnbeatablePlando {
+            world_index,
+            locations,
+        });
+    }
+
+    Ok(())
+}
+
+/// Checks that removing items through the [`ItemPoolSettings`](crate::settings::ItemPoolSettings) didn't make any locations unreachable
+///
+/// `unchanged` is the world before applying the item pool settings, `removed` names the items that were removed
+pub(super) fn validate_item_pool(
+    world_index: usize,
+    unchanged: &World,
+    world: &World,
+    removed: Vec<String>,
+) -> Result<(), GenerationError> {
+    if removed.is_empty() {
+        return Ok(());
+    }
+
+    let reachable_before = finished_reach_check(world_index, unchanged)?;
+    let reachable_after = finished_reach_check(world_index, world)?;
+
+    let locations = reachable_before
+        .iter()
+        .filter(|node| {
+            !reachable_after
+                .iter()
+                .any(|reachable| reachable.index() == node.index())
+        })
+        .map(|node| node.identifier().to_string())
+        .collect::<Vec<_>>();
+    if !locations.is_empty() {
+        return Err(GenerationError::RequiredItemsRemoved {
+            world_index,
+            items: removed,
+            locations,
+        });
+    }
+
+    Ok(())
+}
+
+/// Placements and spoiler per world, along with any attempts that failed before succeeding
+pub(super) type PlacementResult<'graph, 'settings> = (
+    Vec<SeedWorld<'graph, 'settings>>,
+    SeedSpoiler,
+    Vec<FailedAttempt>,
+);
+
+/// Passes the event on to the observer and stops generating if it asks to
+fn notify(
+    observer: &mut dyn GenerationObserver,
+    event: GenerationEvent,
+) -> Result<(), GenerationError> {
+    match observer.on_event(event) {
+        GenerationControl::Continue => Ok(()),
+        GenerationControl::Cancel => Err(GenerationError::Cancelled),
+    }
+}
+
 pub(super) fn generate_placements<'graph, 'settings>(
     graph: &'graph Graph,
     worlds: &[World<'graph, 'settings>],
+    settings: &UniverseSettings,
+    observer: &mut dyn GenerationObserver,
     rng: &mut impl Rng,
-) -> Result<(Vec<SeedWorld<'graph, 'settings>>, SeedSpoiler), String> {
-    let mut index = 0;
-    loop {
-        let spawn_locs = worlds
+) -> Result<PlacementResult<'graph, 'settings>, GenerationError> {
+    let retry_policy = &settings.retry_policy;
+    let start = Instant::now();
+
+    let pick_spawns = |rng: &mut _| {
+        worlds
             .iter()
-            .map(|world| pick_spawn(graph, world.player.settings, rng))
-            .collect::<Result<Vec<_>, String>>()?;
-        #[cfg(feature = "log")]
-        let identifiers = spawn_locs
+            .enumerate()
+            .map(|(world_index, world)| pick_spawn(world_index, graph, world.player.settings, rng))
+            .collect::<Result<Vec<_>, GenerationError>>()
+    };
+
+    let mut spawn_locs = pick_spawns(rng)?;
+    let mut failed_attempts = Vec::new();
+    loop {
+        let spawns = spawn_locs
             .iter()
-            .map(|spawn_loc| spawn_loc.identifier())
+            .map(|spawn_loc| spawn_loc.identifier().to_string())
             .collect::<Vec<_>>();
-        log::trace!("Spawning on {}", identifiers.join(", "));
+        log::trace!("Spawning on {}", spawns.join(", "));
+
+        notify(
+            observer,
+            GenerationEvent::SpawnsChosen {
+                attempt: failed_attempts.len(),
+                spawns: &spawns,
+            },
+        )?;
 
-        match generate_placements_from_spawn(worlds.to_owned(), spawn_locs, rng) {
-            Ok(seed) => {
+        match generate_placements_from_spawn(
+            worlds.to_owned(),
+            spawn_locs.clone(),
+            &settings.multiworld,
+            settings.placement_strategy,
+            settings.fill_algorithm,
+            settings.spoiler_paths,
+            observer,
+            rng,
+        ) {
+            Ok((seed_worlds, spoiler)) => {
+                let index = failed_attempts.len();
                 if index > 0 {
                     log::info!(
                         "Generated seed after {} tries{}",
                         index + 1,
-                        if index < RETRIES / 2 { "" } else { " (phew)" }
+                        if index < usize::from(retry_policy.attempts / 2) {
+                            ""
+                        } else {
+                            " (phew)"
+                        }
                     );
                 }
 
-                return Ok(seed);
+                return Ok((seed_worlds, spoiler, failed_attempts));
+            }
+            Err(GenerationError::Cancelled) => return Err(GenerationError::Cancelled),
+            Err(error) => {
+                log::warning!("{}\nRetrying...", error);
+                let failed_attempt = FailedAttempt { spawns, error };
+                notify(
+                    observer,
+                    GenerationEvent::AttemptFailed {
+                        attempt: &failed_attempt,
+                    },
+                )?;
+                failed_attempts.push(failed_attempt);
+            }
+        }
+
+        if failed_attempts.len() >= usize::from(retry_policy.attempts) {
+            return Err(GenerationError::AttemptsExhausted {
+                attempts: failed_attempts,
+            });
+        }
+        if let Some(time_budget_ms) = retry_policy.time_budget_ms {
+            if start.elapsed() >= Duration::from_millis(time_budget_ms) {
+                return Err(GenerationError::TimeBudgetExceeded {
+                    time_budget_ms,
+                    attempts: failed_attempts,
+                });
+            }
+        }
+
+        if retry_policy.repick_spawns {
+            spawn_locs = pick_spawns(rng)?;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use super::*;
+    use crate::{
+        files::FILE_SYSTEM_ACCESS,
+        generate_seed,
+        generator::seed_rng,
+        item::{Skill, UberStateOperator},
+        settings::{
+            Difficulty, LocationSelector, RelicRules, RetryPolicy, ShopPriceSettings,
+            UniverseSettings, WorldSettings,
+        },
+        test_util::{test_graph, test_seed},
+        util::constants::ALGORITHM_VERSION,
+    };
+
+    #[test]
+    fn spirit_light_amounts_never_run_dry() {
+        let mut rng = seed_rng("spirit light", ALGORITHM_VERSION).unwrap();
+        for distribution in [
+            SpiritLightDistribution::Increasing,
+            SpiritLightDistribution::Flat,
+            SpiritLightDistribution::ZoneWeighted,
+            SpiritLightDistribution::Capped(5),
+        ] {
+            // Barely more Spirit Light than pickups, so any pickup taking more than its share would starve later ones
+            let mut amounts = SpiritLightAmounts::new(0, distribution, 21, 20, 0.75, 1.25);
+            assert_eq!(amounts.take(1).unwrap(), 1);
+            for _ in 0..10 {
+                assert!(amounts.sample(Some(Zone::Wastes), &mut rng).unwrap() >= 1);
             }
-            #[cfg_attr(not(feature = "log"), allow(unused_variables))]
-            Err(err) => log::warning!("{}\nRetrying...", err),
+            let distributed = amounts
+                .distribute(&[Some(Zone::Ruins); 10], &mut rng)
+                .unwrap();
+            assert!(distributed.iter().all(|&amount| amount >= 1));
+            assert_eq!(amounts.remaining, 0);
+            assert_eq!(amounts.placed, 21);
+
+            assert!(matches!(
+                amounts.sample(None, &mut rng),
+                Err(GenerationError::InsufficientSpiritLight { slots: 22, .. })
+            ));
         }
 
-        index += 1;
-        if index == RETRIES {
-            return Err(format!(
-                "All {} attempts to generate a seed failed :(",
-                RETRIES
+        let mut amounts =
+            SpiritLightAmounts::new(0, SpiritLightDistribution::Flat, 3, 2, 0.75, 1.25);
+        assert!(matches!(
+            amounts.distribute(&[None; 4], &mut rng),
+            Err(GenerationError::InsufficientSpiritLight {
+                spirit_light: 3,
+                slots: 4,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn plando_validation() {
+        let plando = |location: &str| UniverseSettings {
+            seed: "plando".to_string(),
+            world_settings: vec![WorldSettings {
+                plando: vec![PlandoPlacement {
+                    location: location.to_string(),
+                    item: Item::Skill(Skill::DoubleJump),
+                }],
+                ..WorldSettings::default()
+            }],
+            ..UniverseSettings::default()
+        };
+        let universe_settings = plando("MarshSpawn.RockHC");
+        let graph = test_graph(&universe_settings);
+
+        let seed = generate_seed(&graph, &FILE_SYSTEM_ACCESS, &universe_settings).unwrap();
+        assert!(seed
+            .spoiler
+            .groups
+            .iter()
+            .flat_map(|group| &group.placements)
+            .any(
+                |placement| placement.location.identifier == "MarshSpawn.RockHC"
+                    && placement.item == Item::Skill(Skill::DoubleJump)
             ));
+
+        // Willow's End can't be reached without Double Jump
+        let universe_settings = plando("WillowsEnd.EntryEX");
+        assert!(matches!(
+            generate_seed(&graph, &FILE_SYSTEM_ACCESS, &universe_settings),
+            Err(GenerationError::UnreachablePlandoPlacement { .. })
+        ));
+
+        // There's only one Double Jump in the item pool
+        let mut universe_settings = plando("MarshSpawn.RockHC");
+        universe_settings.world_settings[0]
+            .plando
+            .push(PlandoPlacement {
+                location: "MarshSpawn.FirstPickupEX".to_string(),
+                item: Item::Skill(Skill::DoubleJump),
+            });
+        assert!(matches!(
+            generate_seed(&graph, &FILE_SYSTEM_ACCESS, &universe_settings),
+            Err(GenerationError::ItemPoolUnderflow { available: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn shop_budget() {
+        let universe_settings = UniverseSettings {
+            seed: "shop budget".to_string(),
+            world_settings: vec![WorldSettings {
+                shop_prices: ShopPriceSettings {
+                    max_total: Some(1000),
+                    ..ShopPriceSettings::default()
+                },
+                ..WorldSettings::default()
+            }],
+            ..UniverseSettings::default()
+        };
+        let (_, seed) = test_seed(universe_settings);
+
+        let mut shops = FxHashMap::<u16, Vec<f32>>::default();
+        for placement in &seed.worlds[0].placements {
+            if let Item::UberState(UberStateItem {
+                identifier,
+                operator: UberStateOperator::Value(value),
+                ..
+            }) = &placement.item
+            {
+                if let Some((_, location, _)) = SHOP_PRICES
+                    .iter()
+                    .find(|(_, _, price_identifier)| price_identifier == identifier)
+                {
+                    shops
+                        .entry(location.uber_group)
+                        .or_default()
+                        .push(value.to_f32());
+                }
+            }
+        }
+
+        assert_eq!(shops.len(), 3);
+        for prices in shops.values() {
+            assert!(prices.iter().sum::<f32>() <= 1000.);
+            // Scaling keeps every price, rather than giving away whatever exceeds the budget
+            assert!(prices.iter().all(|&price| price > 0.));
+        }
+    }
+
+    #[test]
+    fn full_accessibility_on_moki() {
+        let universe_settings = UniverseSettings {
+            seed: "full accessibility".to_string(),
+            world_settings: vec![WorldSettings {
+                difficulty: Difficulty::Moki,
+                accessibility: Accessibility::Full,
+                ..WorldSettings::default()
+            }],
+            ..UniverseSettings::default()
+        };
+        let graph = test_graph(&universe_settings);
+
+        let world_settings = &universe_settings.world_settings[0];
+        let mut world = World::new_spawn(&graph, world_settings);
+        world.pool = Pool::preset(world_settings, &graph);
+        // Moki can't reach everything, but that shouldn't fail full accessibility
+        let (_, unreachable_locations) = total_reach_check(0, &world).unwrap();
+        assert!(!unreachable_locations.is_empty());
+
+        generate_seed(&graph, &FILE_SYSTEM_ACCESS, &universe_settings).unwrap();
+    }
+
+    #[test]
+    fn assumed_fill_forced_items() {
+        let universe_settings = UniverseSettings {
+            seed: "assumed fill".to_string(),
+            fill_algorithm: FillAlgorithm::Assumed,
+            ..UniverseSettings::default()
+        };
+        let (_, seed) = test_seed(universe_settings);
+
+        let groups = &seed.spoiler.groups;
+        assert!(groups
+            .iter()
+            .any(|group| !group.forced_items.items.is_empty()));
+        // Every forced item has to be among the placements of its group
+        for group in groups {
+            for (item, amount) in &group.forced_items.items {
+                let placed = group
+                    .placements
+                    .iter()
+                    .filter(|placement| &placement.item == item)
+                    .count();
+                assert!(placed >= *amount as usize);
+            }
+        }
+    }
+
+    #[test]
+    fn assumed_fill_max_step() {
+        let constraint = PlacementConstraint {
+            item: Item::Resource(Resource::ShardSlot),
+            zones: Vec::new(),
+            max_step: Some(8),
+        };
+        for seed in ["max step", "max step 2"] {
+            let universe_settings = UniverseSettings {
+                seed: seed.to_string(),
+                fill_algorithm: FillAlgorithm::Assumed,
+                world_settings: vec![WorldSettings {
+                    placement_constraints: vec![constraint.clone()],
+                    ..WorldSettings::default()
+                }],
+                ..UniverseSettings::default()
+            };
+            let (_, seed) = test_seed(universe_settings);
+
+            let steps = seed
+                .spoiler
+                .groups
+                .iter()
+                .enumerate()
+                .filter(|(_, group)| {
+                    group
+                        .placements
+                        .iter()
+                        .any(|placement| placement.item == constraint.item)
+                })
+                .map(|(step, _)| step)
+                .collect::<Vec<_>>();
+            assert!(!steps.is_empty());
+            assert!(steps.iter().all(|&step| step <= 8));
+        }
+    }
+
+    #[test]
+    fn multiworld_item_categories() {
+        let universe_settings = UniverseSettings {
+            seed: "item categories".to_string(),
+            world_settings: vec![WorldSettings::default(), WorldSettings::default()],
+            multiworld: MultiworldSettings {
+                local_items: vec![ItemCategory::Skill],
+                shared_items: vec![ItemCategory::ShardSlot],
+                ..MultiworldSettings::default()
+            },
+            ..UniverseSettings::default()
+        };
+        let (graph, seed) = test_seed(universe_settings);
+
+        let placements = seed
+            .spoiler
+            .groups
+            .iter()
+            .flat_map(|group| &group.placements)
+            .collect::<Vec<_>>();
+        let category_placements = |category: ItemCategory| {
+            placements
+                .iter()
+                .filter(move |placement| graph.item_category(&placement.item) == category)
+        };
+        let skills = category_placements(ItemCategory::Skill).collect::<Vec<_>>();
+        assert!(!skills.is_empty());
+        assert!(skills
+            .iter()
+            .all(|placement| placement.origin_world_index == placement.target_world_index));
+        let shard_slots = category_placements(ItemCategory::ShardSlot).collect::<Vec<_>>();
+        assert!(!shard_slots.is_empty());
+        assert!(shard_slots
+            .iter()
+            .all(|placement| placement.origin_world_index != placement.target_world_index));
+    }
+
+    #[test]
+    fn forced_progression_balance() {
+        let universe_settings = UniverseSettings {
+            seed: "progression balance".to_string(),
+            world_settings: vec![WorldSettings::default(), WorldSettings::default()],
+            multiworld: MultiworldSettings {
+                progression_balance: Some(1.),
+                ..MultiworldSettings::default()
+            },
+            ..UniverseSettings::default()
+        };
+        let (_, seed) = test_seed(universe_settings);
+
+        // Forced placements should send progression to the other world just like random placements, unless it has no space left
+        let forced = seed
+            .spoiler
+            .groups
+            .iter()
+            .flat_map(|group| {
+                group.placements.iter().filter(|placement| {
+                    group.forced_items.items.contains_key(&placement.item)
+                        && placement.item.is_multiworld_spread()
+                })
+            })
+            .collect::<Vec<_>>();
+        let kept = forced
+            .iter()
+            .filter(|placement| placement.origin_world_index == placement.target_world_index)
+            .count();
+        assert!(!forced.is_empty());
+        assert!(kept * 10 < forced.len());
+    }
+
+    #[test]
+    fn excluded_locations_hold_no_progression() {
+        let excluded_locations = vec![
+            LocationSelector::Location("MarshSpawn.RockHC".to_string()),
+            LocationSelector::Zone(Zone::Shop),
+        ];
+        for fill_algorithm in [FillAlgorithm::Forward, FillAlgorithm::Assumed] {
+            let universe_settings = UniverseSettings {
+                seed: "excluded".to_string(),
+                fill_algorithm,
+                world_settings: vec![WorldSettings {
+                    excluded_locations: excluded_locations.clone(),
+                    ..WorldSettings::default()
+                }],
+                ..UniverseSettings::default()
+            };
+            let (_, seed) = test_seed(universe_settings);
+            let difficulty = seed.worlds[0].world_settings.difficulty;
+
+            let excluded = seed
+                .spoiler
+                .groups
+                .iter()
+                .flat_map(|group| &group.placements)
+                .filter(|placement| {
+                    excluded_locations.iter().any(|selector| {
+                        selector.matches(&placement.location.identifier, placement.location.zone)
+                    })
+                })
+                .collect::<Vec<_>>();
+            assert!(!excluded.is_empty());
+            // Spirit Light only counts as progression for buying items in shops
+            assert!(excluded.iter().all(|placement| {
+                matches!(placement.item, Item::SpiritLight(_))
+                    || !placement.item.is_progression(difficulty)
+            }));
+        }
+    }
+
+    #[test]
+    fn placement_constraints() {
+        let constraints = vec![
+            PlacementConstraint {
+                item: Item::Skill(Skill::DoubleJump),
+                zones: Vec::new(),
+                max_step: Some(3),
+            },
+            PlacementConstraint {
+                item: Item::Resource(Resource::ShardSlot),
+                zones: vec![Zone::Glades, Zone::Hollow],
+                max_step: None,
+            },
+        ];
+        let universe_settings = UniverseSettings {
+            seed: "placement constraints".to_string(),
+            world_settings: vec![WorldSettings {
+                placement_constraints: constraints.clone(),
+                ..WorldSettings::default()
+            }],
+            ..UniverseSettings::default()
+        };
+        let (_, seed) = test_seed(universe_settings);
+
+        for constraint in &constraints {
+            let placements = seed
+                .spoiler
+                .groups
+                .iter()
+                .enumerate()
+                .flat_map(|(step, group)| {
+                    group
+                        .placements
+                        .iter()
+                        .filter(|placement| placement.item == constraint.item)
+                        .map(move |placement| (step, placement))
+                })
+                .collect::<Vec<_>>();
+            assert!(!placements.is_empty());
+            assert!(placements
+                .iter()
+                .all(|(step, placement)| constraint.allows(placement.location.zone, *step)));
+        }
+
+        let universe_settings = UniverseSettings {
+            world_settings: vec![WorldSettings {
+                placement_constraints: vec![PlacementConstraint {
+                    item: Item::Skill(Skill::DoubleJump),
+                    zones: Vec::new(),
+                    max_step: Some(0),
+                }],
+                ..WorldSettings::default()
+            }],
+            ..UniverseSettings::default()
+        };
+        let graph = test_graph(&universe_settings);
+        assert!(matches!(
+            generate_seed(&graph, &FILE_SYSTEM_ACCESS, &universe_settings),
+            Err(GenerationError::UnsatisfiableConstraint { world_index: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn relic_rules() {
+        let zones = vec![Zone::Marsh, Zone::Glades, Zone::Depths, Zone::Wastes];
+        let rules = RelicRules::new(zones.clone(), 2, 3)
+            .unwrap()
+            .with_avoid_shops_and_quests(true);
+        for seed in ["relic rules", "relic rules 2", "relic rules 3"] {
+            let universe_settings = UniverseSettings {
+                seed: seed.to_string(),
+                world_settings: vec![WorldSettings {
+                    goals: [Goal::RelicRules(rules.clone())].into_iter().collect(),
+                    ..WorldSettings::default()
+                }],
+                ..UniverseSettings::default()
+            };
+            let (_, seed) = test_seed(universe_settings);
+
+            let relic_zones = seed
+                .spoiler
+                .groups
+                .iter()
+                .flat_map(|group| &group.placements)
+                .filter_map(|placement| match placement.item {
+                    Item::Relic(zone) => {
+                        assert_eq!(placement.location.zone, Some(zone));
+                        Some(zone)
+                    }
+                    _ => None,
+                })
+                .collect::<FxHashSet<_>>();
+            assert!((2..=3).contains(&relic_zones.len()));
+            assert!(relic_zones.iter().all(|zone| zones.contains(zone)));
+        }
+
+        // Excluding the only zone leaves no room for the minimum
+        let universe_settings = UniverseSettings {
+            world_settings: vec![WorldSettings {
+                goals: [Goal::RelicRules(
+                    RelicRules::new(vec![Zone::Marsh], 1, 1).unwrap(),
+                )]
+                .into_iter()
+                .collect(),
+                excluded_locations: vec![LocationSelector::Zone(Zone::Marsh)],
+                ..WorldSettings::default()
+            }],
+            retry_policy: RetryPolicy {
+                attempts: 1,
+                ..RetryPolicy::default()
+            },
+            ..UniverseSettings::default()
+        };
+        let graph = test_graph(&universe_settings);
+        match generate_seed(&graph, &FILE_SYSTEM_ACCESS, &universe_settings).err() {
+            Some(GenerationError::AttemptsExhausted { attempts }) => {
+                assert!(matches!(
+                    attempts[0].error,
+                    GenerationError::InsufficientRelicLocations {
+                        world_index: 0,
+                        placed: 0,
+                        min: 1,
+                    }
+                ));
+            }
+            other => panic!("expected insufficient relic locations, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn balanced_strategy_weights() {
+        let balanced = PlacementStrategy::Balanced {
+            target_step_size: NonZeroUsize::new(10).unwrap(),
+        };
+        let on_target = strategy_weight(balanced, 1, 10);
+        assert!(on_target > strategy_weight(balanced, 1, 5));
+        assert!(on_target > strategy_weight(balanced, 1, 40));
+        assert!(strategy_weight(balanced, 1, 12) > strategy_weight(balanced, 1, 20));
+        // Cost still matters for equally sized steps
+        assert!(on_target > strategy_weight(balanced, 2, 10));
+        // Unlike the classic strategy, which always prefers reaching more
+        assert!(
+            strategy_weight(PlacementStrategy::Classic, 1, 40)
+                > strategy_weight(PlacementStrategy::Classic, 1, 10)
+        );
+    }
+
+    #[test]
+    fn balanced_strategy_only_forces_unlocks() {
+        let universe_settings = UniverseSettings {
+            seed: "balanced".to_string(),
+            placement_strategy: PlacementStrategy::Balanced {
+                target_step_size: NonZeroUsize::new(12).unwrap(),
+            },
+            ..UniverseSettings::default()
+        };
+        let (_, seed) = test_seed(universe_settings);
+        let difficulty = seed.worlds[0].world_settings.difficulty;
+
+        // Random placements may only hold resources or items that aren't progression, so unlocks only come from forced progression.
+        // The last group holds whatever is left once everything has been reached
+        let groups = &seed.spoiler.groups;
+        for group in &groups[..groups.len() - 1] {
+            for placement in &group.placements {
+                if placement.item.is_progression(difficulty)
+                    && !matches!(placement.item, Item::Resource(_) | Item::SpiritLight(_))
+                {
+                    assert!(group.forced_items.items.contains_key(&placement.item));
+                }
+            }
         }
     }
 }
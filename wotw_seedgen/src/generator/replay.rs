@@ -0,0 +1,351 @@
+use std::iter;
+
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::{
+    item::Item,
+    uber_state::{UberIdentifier, UberStateTrigger},
+    world::{graph::Node, World},
+};
+
+use super::{placement::is_goal_location, SeedSpoiler};
+
+/// One [`SpoilerPlacement`](super::SpoilerPlacement) resolved to its logic [`Node`]
+pub(super) struct ReplayPlacement<'graph> {
+    pub origin_world_index: usize,
+    pub target_world_index: usize,
+    pub node: &'graph Node,
+    pub item: Item,
+    pub item_name: String,
+}
+
+/// Follows the placements of a generated seed the way a player would, collecting everything that is in logic
+///
+/// This is independent of the generator's own bookkeeping, so it can tell which placements the goals actually depend on
+pub(super) struct Replay<'graph, 'settings> {
+    /// The worlds as they were before any placements, with pinned items taken out of their preplacements
+    worlds: Vec<World<'graph, 'settings>>,
+    spawns: Vec<&'graph Node>,
+    pub placements: Vec<ReplayPlacement<'graph>>,
+    /// Indices into `placements` for each world, keyed by node index
+    locations: Vec<FxHashMap<usize, Vec<usize>>>,
+    /// Indices into `placements` that are collected on spawn
+    spawn_placements: Vec<usize>,
+    /// Node indices for each world that have to be reached to finish the game
+    goal_locations: Vec<Vec<usize>>,
+}
+
+/// Outcome of a [`Replay`]
+pub(super) struct ReplayResult {
+    /// Indices of the placements collected in each sphere
+    pub spheres: Vec<Vec<usize>>,
+    /// Whether every world reached all of its goal locations
+    pub beatable: bool,
+}
+
+impl<'graph, 'settings> Replay<'graph, 'settings> {
+    /// Prepares a replay of the [`SeedSpoiler`]'s placements
+    ///
+    /// `worlds` have to be the worlds as they were passed to the generator, `spawns` the spawns that were picked for them
+    pub fn new(
+        worlds: &[World<'graph, 'settings>],
+        spawns: Vec<&'graph Node>,
+        spoiler: &SeedSpoiler,
+    ) -> Self {
+        let mut worlds = worlds.to_vec();
+        let mut placements = Vec::new();
+        let mut locations = vec![FxHashMap::<usize, Vec<usize>>::default(); worlds.len()];
+        let mut spawn_placements = Vec::new();
+
+        for spoiler_placement in spoiler.groups.iter().flat_map(|group| &group.placements) {
+            let world = &mut worlds[spoiler_placement.origin_world_index];
+            let graph = world.graph;
+            let identifier = &spoiler_placement.location.identifier;
+            let node = if *identifier == graph.spawn_pickup_node.identifier() {
+                &graph.spawn_pickup_node
+            } else {
+                match graph
+                    .nodes
+                    .iter()
+                    .find(|node| node.can_place() && node.identifier() == identifier)
+                {
+                    Some(node) => node,
+                    None => continue,
+                }
+            };
+
+            // Pinned items are preplaced as well as placed, collecting them once is enough
+            if let Some(trigger) = node.trigger() {
+                if let Some(preplaced) = world.preplacements.get_mut(trigger) {
+                    if let Some(position) = preplaced
+                        .iter()
+                        .position(|item| *item == spoiler_placement.item)
+                    {
+                        preplaced.remove(position);
+                        if preplaced.is_empty() {
+                            world.preplacements.remove(trigger);
+                        }
+                    }
+                }
+            }
+
+            let index = placements.len();
+            if node.index() == graph.spawn_pickup_node.index() {
+                spawn_placements.push(index);
+            } else {
+                locations[spoiler_placement.origin_world_index]
+                    .entry(node.index())
+                    .or_default()
+                    .push(index);
+            }
+            placements.push(ReplayPlacement {
+                origin_world_index: spoiler_placement.origin_world_index,
+                target_world_index: spoiler_placement.target_world_index,
+                node,
+                item: spoiler_placement.item.clone(),
+                item_name: spoiler_placement.item_name.clone(),
+            });
+        }
+
+        let mut replay = Replay {
+            worlds,
+            spawns,
+            placements,
+            locations,
+            spawn_placements,
+            goal_locations: Vec::new(),
+        };
+
+        let reached = replay.collect(&FxHashSet::default(), false).1;
+        replay.goal_locations = replay
+            .worlds
+            .iter()
+            .zip(reached)
+            .enumerate()
+            .map(|(world_index, (world, reached))| {
+                let relic_locations = replay
+                    .placements
+                    .iter()
+                    .filter(|placement| {
+                        placement.origin_world_index == world_index
+                            && matches!(placement.item, Item::Relic(_))
+                    })
+                    .map(|placement| placement.node)
+                    .collect::<Vec<_>>();
+                reached
+                    .into_iter()
+                    .filter(|&index| {
+                        is_goal_location(&world.graph.nodes[index], &world.goals, &relic_locations)
+                    })
+                    .collect()
+            })
+            .collect();
+
+        replay
+    }
+
+    /// Collects everything in logic, stopping as soon as all goals are reached
+    ///
+    /// The placements at the `skipped` indices will be treated as if they held nothing
+    pub fn run(&self, skipped: &FxHashSet<usize>) -> ReplayResult {
+        let (spheres, reached) = self.collect(skipped, true);
+        let beatable = self.goals_reached(&reached);
+
+        ReplayResult { spheres, beatable }
+    }
+
+    /// Checks whether the goals can't be reached without collecting the placement at `index`
+    pub fn is_required(&self, index: usize) -> bool {
+        let skipped = iter::once(index).collect();
+        !self.run(&skipped).beatable
+    }
+
+    /// Checks whether the placement at `index` may be needed to reach the goals
+    ///
+    /// Spirit Light doesn't count since it is spread across too many placements to consider them individually
+    pub fn is_progression(&self, index: usize) -> bool {
+        let placement = &self.placements[index];
+        let difficulty = self.worlds[placement.target_world_index]
+            .player
+            .settings
+            .difficulty;
+        !matches!(placement.item, Item::SpiritLight(_)) && placement.item.is_progression(difficulty)
+    }
+
+    /// Finds a minimal set of placements that still reaches the goals, grouped into spheres
+    ///
+    /// Every sphere can be collected with the placements of the previous spheres, Spirit Light is assumed to be collected along the way.
+    /// Returns [`None`] if the placements don't reach the goals
+    pub fn playthrough(&self) -> Option<Vec<Vec<usize>>> {
+        let result = self.run(&FxHashSet::default());
+        if !result.beatable {
+            return None;
+        }
+
+        let mut skipped = FxHashSet::default();
+        // Later placements are more likely to be replaceable, checking them first keeps the spheres short
+        for &index in result.spheres.iter().flatten().rev() {
+            if self.is_progression(index) {
+                skipped.insert(index);
+                if !self.run(&skipped).beatable {
+                    skipped.remove(&index);
+                }
+            }
+        }
+
+        let spheres = self
+            .run(&skipped)
+            .spheres
+            .into_iter()
+            .map(|sphere| {
+                sphere
+                    .into_iter()
+                    .filter(|&index| self.is_progression(index))
+                    .collect::<Vec<_>>()
+            })
+            .filter(|sphere| !sphere.is_empty())
+            .collect();
+
+        Some(spheres)
+    }
+
+    fn goals_reached(&self, reached: &[FxHashSet<usize>]) -> bool {
+        self.goal_locations
+            .iter()
+            .zip(reached)
+            .all(|(goal_locations, reached)| {
+                goal_locations.iter().all(|index| reached.contains(index))
+            })
+    }
+
+    fn collect(
+        &self,
+        skipped: &FxHashSet<usize>,
+        stop_at_goals: bool,
+    ) -> (Vec<Vec<usize>>, Vec<FxHashSet<usize>>) {
+        let mut worlds = self.worlds.clone();
+        let mut reached_nodes = vec![FxHashSet::default(); worlds.len()];
+        let mut spheres = Vec::new();
+
+        for world in &mut worlds {
+            world.set_uber_state(UberIdentifier::spawn(), 1.);
+        }
+        let mut sphere = Vec::new();
+        for &index in &self.spawn_placements {
+            self.grant(index, skipped, &mut worlds, &mut sphere);
+        }
+
+        loop {
+            if stop_at_goals && self.goals_reached(&reached_nodes) {
+                break;
+            }
+
+            let reached = worlds
+                .iter()
+                .zip(&self.spawns)
+                .map(|(world, spawn)| {
+                    world.graph.reached_locations(
+                        &world.player,
+                        spawn,
+                        world.uber_states(),
+                        &world.sets,
+                    )
+                })
+                .collect::<Vec<_>>();
+
+            let mut progress = false;
+            for (world_index, mut reached) in reached.into_iter().enumerate() {
+                reached.retain(|node| {
+                    node.trigger().is_some() && !reached_nodes[world_index].contains(&node.index())
+                });
+                // Quest steps that unlock at the same time have to be set in order, like in the generator
+                reached.sort_unstable_by_key(|node| {
+                    node.trigger().map_or(0, UberStateTrigger::set_value)
+                });
+
+                for node in reached {
+                    progress = true;
+                    reached_nodes[world_index].insert(node.index());
+
+                    let trigger = node.trigger().unwrap();
+                    worlds[world_index]
+                        .set_incremental_uber_state(trigger.identifier, trigger.set_value() as f32);
+
+                    if let Some(indices) = self.locations[world_index].get(&node.index()) {
+                        for &index in indices {
+                            self.grant(index, skipped, &mut worlds, &mut sphere);
+                        }
+                    }
+                }
+            }
+
+            if !progress {
+                break;
+            }
+            if !sphere.is_empty() {
+                spheres.push(std::mem::take(&mut sphere));
+            }
+        }
+        if !sphere.is_empty() {
+            spheres.push(sphere);
+        }
+
+        (spheres, reached_nodes)
+    }
+
+    fn grant(
+        &self,
+        index: usize,
+        skipped: &FxHashSet<usize>,
+        worlds: &mut [World],
+        sphere: &mut Vec<usize>,
+    ) {
+        if skipped.contains(&index) {
+            return;
+        }
+        let placement = &self.placements[index];
+        worlds[placement.target_world_index].grant_player(placement.item.clone(), 1);
+        sphere.push(index);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{settings::UniverseSettings, test_util::test_seed};
+
+    #[test]
+    fn playthrough_is_minimal() {
+        let universe_settings = UniverseSettings {
+            seed: "playthrough".to_string(),
+            ..UniverseSettings::default()
+        };
+        let (_, seed) = test_seed(universe_settings);
+
+        let spawns = seed.worlds.iter().map(|world| world.spawn).collect();
+        let replay = Replay::new(&seed.initial_worlds, spawns, &seed.spoiler);
+        let playthrough = replay
+            .playthrough()
+            .unwrap()
+            .into_iter()
+            .flatten()
+            .collect::<FxHashSet<_>>();
+        assert!(!playthrough.is_empty());
+
+        // Only Spirit Light and the playthrough's own placements should be needed
+        let skipped = (0..replay.placements.len())
+            .filter(|index| replay.is_progression(*index) && !playthrough.contains(index))
+            .collect::<FxHashSet<_>>();
+        assert!(replay.run(&skipped).beatable);
+        for &index in &playthrough {
+            let mut skipped = skipped.clone();
+            skipped.insert(index);
+            assert!(
+                !replay.run(&skipped).beatable,
+                "{} on {} is not needed for the playthrough",
+                replay.placements[index].item_name,
+                replay.placements[index].node
+            );
+        }
+    }
+}
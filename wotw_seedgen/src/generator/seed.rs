@@ -5,10 +5,14 @@ use crate::{
     header,
     settings::{UniverseSettings, WorldSettings},
     util::constants::{DEFAULT_SPAWN, SPAWN_GRANTS},
-    world::{graph::Node, Graph},
+    world::{graph::Node, Graph, World},
 };
 
-use super::{spoiler::SeedSpoiler, Placement};
+use super::{
+    replay::Replay,
+    spoiler::{Playthrough, SeedSpoiler, SpoilerPlacement},
+    FailedAttempt, GenerationError, Hint, Placement,
+};
 
 /// End Result of seed generation
 pub struct Seed<'graph, 'settings> {
@@ -20,6 +24,12 @@ pub struct Seed<'graph, 'settings> {
     pub settings: &'settings UniverseSettings,
     /// Spoiler data for the generation process
     pub spoiler: SeedSpoiler,
+    /// Attempts to generate placements that failed before this seed was successfully generated
+    ///
+    /// See [`RetryPolicy`](crate::settings::RetryPolicy) to configure how many attempts may be made
+    pub failed_attempts: Vec<FailedAttempt>,
+    /// The worlds as they were before generating placements, needed to replay the placements
+    pub(super) initial_worlds: Vec<World<'graph, 'settings>>,
 }
 /// World-specific data related to a [`Seed`]
 pub struct SeedWorld<'graph, 'settings> {
@@ -31,6 +41,8 @@ pub struct SeedWorld<'graph, 'settings> {
     pub placements: Vec<Placement<'graph>>,
     /// Section that should be added as a result of headers
     pub headers: String,
+    /// Generated [`Hint`]s, see [`HintSettings`](crate::settings::HintSettings)
+    pub hints: Vec<Hint<'graph>>,
     /// Portion of the seed settings that belong to this world
     pub world_settings: &'settings WorldSettings,
 }
@@ -39,13 +51,14 @@ impl Seed<'_, '_> {
     /// Returns the seed files for each world to be used by the randomizer client
     ///
     /// May error if postprocessing commands (such as `$WHEREIS`) contain invalid regexes
-    pub fn seed_files(&self) -> Result<Vec<String>, String> {
+    pub fn seed_files(&self) -> Result<Vec<String>, GenerationError> {
         let mut seeds = self
             .worlds
             .iter()
             .enumerate()
             .map(|(index, world)| {
                 let version = crate::VERSION;
+                let algorithm_version = self.settings.algorithm_version;
                 let slug = &self.settings.slugify();
                 let config = &self.settings.to_json();
 
@@ -55,6 +68,7 @@ impl Seed<'_, '_> {
                         // This World: {index}\n\
                         // Format Version: 1.0.0\n\
                         // Generator Version: {version}\n\
+                        // Algorithm Version: {algorithm_version}\n\
                         // Slug: {slug}\n\
                         // Config: {config}\n\
                     "
@@ -62,10 +76,43 @@ impl Seed<'_, '_> {
             })
             .collect::<Vec<_>>();
 
-        header::parser::postprocess(&mut seeds, self.graph)?;
+        header::parser::postprocess(&mut seeds, self.graph)
+            .map_err(|message| GenerationError::Postprocess { message })?;
 
         Ok(seeds)
     }
+
+    /// Computes the minimal playthrough, the placements needed to reach all goals ordered by sphere
+    ///
+    /// Returns [`None`] if replaying the placements doesn't reach the goals
+    ///
+    /// This repeatedly simulates collecting the entire seed, so it is considerably slower than generating the seed itself
+    pub fn playthrough(&self) -> Option<Playthrough> {
+        let spawns = self.worlds.iter().map(|world| world.spawn).collect();
+        let replay = Replay::new(&self.initial_worlds, spawns, &self.spoiler);
+
+        let spheres = replay
+            .playthrough()?
+            .into_iter()
+            .map(|sphere| {
+                sphere
+                    .into_iter()
+                    .map(|index| {
+                        let placement = &replay.placements[index];
+                        SpoilerPlacement {
+                            origin_world_index: placement.origin_world_index,
+                            target_world_index: placement.target_world_index,
+                            location: placement.node.summary(),
+                            item: placement.item.clone(),
+                            item_name: placement.item_name.clone(),
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Some(Playthrough { spheres })
+    }
 }
 
 impl Display for SeedWorld<'_, '_> {
@@ -102,10 +149,23 @@ impl Display for SeedWorld<'_, '_> {
             }
         }
 
+        for item in &self.world_settings.starting_items {
+            writeln!(
+                f,
+                "{}|{}|mute",
+                UberStateTrigger::spawn().code(),
+                item.code()
+            )?;
+        }
+
         for placement in &self.placements {
             writeln!(f, "{}", placement.code())?;
         }
 
+        for hint in &self.hints {
+            writeln!(f, "{}", hint.code())?;
+        }
+
         write!(f, include_str!("seed_core.wotwr"))?;
 
         write!(f, "{}", self.headers)?;
@@ -1,20 +1,49 @@
-use std::fmt::{self, Display, Write};
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display, Write},
+    iter,
+    str::FromStr,
+};
 
 use itertools::Itertools;
+use rustc_hash::FxHashMap;
 use serde::{Deserialize, Serialize};
 
 use crate::{
+    item::{Message, Resource},
+    settings::SpiritLightDistribution,
     util::{Position, Zone},
+    world::Graph,
     Inventory, Item,
 };
 
 /// Complete data to create a logic spoiler for the seed
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct SeedSpoiler {
     /// Anchor identifier of all the spawn locations
     pub spawns: Vec<String>,
     /// Each [`SpoilerGroup`] represents one "step" of placements
     pub groups: Vec<SpoilerGroup>,
+    /// The locations of each world that were excluded from holding progression
+    #[serde(default)]
+    pub excluded_locations: Vec<Vec<NodeSummary>>,
+    /// How the Spirit Light of each world was split into pickups
+    #[serde(default)]
+    pub spirit_light: Vec<SpiritLightSummary>,
+    /// The locations of each world that hold a Relic
+    #[serde(default)]
+    pub relics: Vec<Vec<NodeSummary>>,
+    /// Overviews of the placements in each world
+    ///
+    /// See [`SeedSpoiler::summarize`]
+    #[serde(default)]
+    pub summaries: Vec<WorldSummary>,
+    /// The minimal playthrough, if it was computed
+    ///
+    /// See [`Seed::playthrough`](super::Seed::playthrough)
+    #[serde(default)]
+    pub playthrough: Option<Playthrough>,
 }
 /// One "step" of placements in a [`SeedSpoiler`]
 #[derive(Debug, Default, Serialize, Deserialize, Clone)]
@@ -28,6 +57,9 @@ pub struct SpoilerGroup {
     ///
     /// [`Placement`]: super::Placement
     pub placements: Vec<SpoilerPlacement>,
+    /// How the new reachables of each world were reached, if [`UniverseSettings::spoiler_paths`](crate::settings::UniverseSettings::spoiler_paths) is enabled
+    #[serde(default)]
+    pub paths: Vec<Vec<LogicPath>>,
 }
 /// One item placed on one location
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -44,6 +76,73 @@ pub struct SpoilerPlacement {
     /// The name of the [`Item`], which may vary from the [`Item`]s [`Display`] implementation if a custom name for item was provided by headers
     pub item_name: String,
 }
+/// The placements required to reach all goals
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Playthrough {
+    /// Each sphere can be collected using the items from previous spheres
+    ///
+    /// Spirit Light is left out and assumed to be collected along the way
+    pub spheres: Vec<Vec<SpoilerPlacement>>,
+}
+/// Overview of the Spirit Light placed in one world
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SpiritLightSummary {
+    /// The [`SpiritLightDistribution`] used to determine the amounts
+    pub distribution: SpiritLightDistribution,
+    /// The sum of all Spirit Light placed from the item pool
+    ///
+    /// This does not include Spirit Light placed by plando or headers
+    pub total: u32,
+}
+/// Overview of the placements in one world
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct WorldSummary {
+    /// Counts of notable items placed in each [`Zone`] of this world
+    pub zones: Vec<ZoneSummary>,
+    /// The items sold by each merchant of this world
+    pub shops: Vec<ShopSummary>,
+    /// Where the skills for this world were placed, which may be in other worlds
+    pub skills: Vec<SpoilerPlacement>,
+    /// Where the teleporters for this world were placed, which may be in other worlds
+    pub teleporters: Vec<SpoilerPlacement>,
+}
+/// Counts of notable items placed in one [`Zone`]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ZoneSummary {
+    pub zone: Zone,
+    pub skills: usize,
+    pub shards: usize,
+    pub keystones: usize,
+    /// Gorlek Ore
+    pub ore: usize,
+    /// The sum of all Spirit Light placed in this zone
+    pub spirit_light: u32,
+}
+/// The items sold by one merchant
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ShopSummary {
+    /// The anchor of the shop's locations, such as `OpherShop`
+    ///
+    /// The maps Lupo sells throughout the zones are listed under `LupoShop` as well
+    pub merchant: String,
+    pub placements: Vec<SpoilerPlacement>,
+}
+/// How a location was reached in the logic [`Graph`](crate::world::Graph)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LogicPath {
+    /// The identifier of the reached location
+    pub location: String,
+    /// The anchors passed through to reach the location, starting at the spawn or the teleporter network
+    pub anchors: Vec<String>,
+    /// The part of the location's requirement that was met, in logic file syntax
+    pub requirement: String,
+}
 /// Select data from a [`Node`](crate::world::graph::Node)
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct NodeSummary {
@@ -61,6 +160,486 @@ impl SeedSpoiler {
         // This is safe because the SeedSpoiler struct is known to serialize successfully
         serde_json::to_string(&self).unwrap()
     }
+
+    /// Looks up the locations in the [`Graph`] to fill in their [`Zone`]s and [`Position`]s
+    ///
+    /// Spoilers parsed from text only know the identifiers of most locations, use this to make them as complete as generated ones.
+    /// Identifiers that don't exist in the [`Graph`] are left untouched
+    pub fn locate(&mut self, graph: &Graph) {
+        let nodes = graph
+            .nodes
+            .iter()
+            .chain(iter::once(&graph.spawn_pickup_node))
+            .map(|node| (node.identifier(), node))
+            .collect::<FxHashMap<_, _>>();
+        let locate = |location: &mut NodeSummary| {
+            if let Some(node) = nodes.get(location.identifier.as_str()) {
+                *location = node.summary();
+            }
+        };
+
+        self.groups
+            .iter_mut()
+            .flat_map(|group| {
+                group.reachable.iter_mut().flatten().chain(
+                    group
+                        .placements
+                        .iter_mut()
+                        .map(|placement| &mut placement.location),
+                )
+            })
+            .chain(self.excluded_locations.iter_mut().flatten())
+            .chain(self.relics.iter_mut().flatten())
+            .chain(
+                self.playthrough
+                    .iter_mut()
+                    .flat_map(|playthrough| playthrough.spheres.iter_mut().flatten())
+                    .chain(self.summaries.iter_mut().flat_map(|summary| {
+                        summary
+                            .shops
+                            .iter_mut()
+                            .flat_map(|shop| &mut shop.placements)
+                            .chain(&mut summary.skills)
+                            .chain(&mut summary.teleporters)
+                    }))
+                    .map(|placement| &mut placement.location),
+            )
+            .for_each(locate);
+    }
+
+    /// Summarizes the placements of each world by zone and shop, and lists where the skills and teleporters ended up
+    pub fn summarize(&self) -> Vec<WorldSummary> {
+        let world_count = self.spawns.len();
+        let mut summaries = vec![WorldSummary::default(); world_count];
+        let mut zones = vec![BTreeMap::new(); world_count];
+        let mut shops = vec![BTreeMap::<_, Vec<_>>::new(); world_count];
+
+        for placement in self.groups.iter().flat_map(|group| &group.placements) {
+            let world_index = placement.origin_world_index;
+            if let Some(zone) = placement.location.zone {
+                let summary = zones[world_index]
+                    .entry(zone)
+                    .or_insert_with(|| ZoneSummary {
+                        zone,
+                        skills: 0,
+                        shards: 0,
+                        keystones: 0,
+                        ore: 0,
+                        spirit_light: 0,
+                    });
+                match placement.item {
+                    Item::Skill(_) => summary.skills += 1,
+                    Item::Shard(_) => summary.shards += 1,
+                    Item::Resource(Resource::Keystone) => summary.keystones += 1,
+                    Item::Resource(Resource::GorlekOre) => summary.ore += 1,
+                    Item::SpiritLight(amount) => summary.spirit_light += amount,
+                    _ => {}
+                }
+            }
+            if let Some(merchant) = merchant(&placement.location.identifier) {
+                shops[world_index]
+                    .entry(merchant)
+                    .or_default()
+                    .push(placement.clone());
+            }
+
+            let summary = &mut summaries[placement.target_world_index];
+            match placement.item {
+                Item::Skill(_) => summary.skills.push(placement.clone()),
+                Item::Teleporter(_) => summary.teleporters.push(placement.clone()),
+                _ => {}
+            }
+        }
+
+        for ((summary, zones), shops) in summaries.iter_mut().zip(zones).zip(shops) {
+            summary.zones = zones.into_values().collect();
+            summary.shops = shops
+                .into_iter()
+                .map(|(merchant, placements)| ShopSummary {
+                    merchant: merchant.to_string(),
+                    placements,
+                })
+                .collect();
+            summary.skills.sort_by(|a, b| a.item.cmp(&b.item));
+            summary.teleporters.sort_by(|a, b| a.item.cmp(&b.item));
+        }
+
+        summaries
+    }
+
+    /// Render as a self-contained html page
+    ///
+    /// Every step can be collapsed and the placements are additionally listed in a table for each zone.
+    /// Multiworld spoilers get one tab for each world, listing the locations of that world
+    pub fn to_html(&self) -> String {
+        let mut html = String::new();
+        // Writing into a String can't fail
+        self.write_html(&mut html).unwrap();
+        html
+    }
+
+    /// Render as csv with one row for each placement
+    ///
+    /// The columns are `world, step, location, zone, x, y, item`, where `world` is the world holding the location.
+    /// In multiworld spoilers the item is prefixed with the index of the receiving world, like in the text spoiler
+    pub fn to_csv(&self) -> String {
+        let multiworld = self.spawns.len() > 1;
+        let mut writer = csv::Writer::from_writer(vec![]);
+
+        // Writing into a Vec can't fail
+        writer
+            .write_record(["world", "step", "location", "zone", "x", "y", "item"])
+            .unwrap();
+        for (step, group) in self.groups.iter().enumerate() {
+            for placement in &group.placements {
+                let location = &placement.location;
+                let (x, y) = location
+                    .position
+                    .as_ref()
+                    .map_or_else(Default::default, |position| {
+                        (position.x.to_string(), position.y.to_string())
+                    });
+                let item = if multiworld {
+                    format!("[{}] {}", placement.target_world_index, placement.item_name)
+                } else {
+                    placement.item_name.clone()
+                };
+                writer
+                    .write_record([
+                        placement.origin_world_index.to_string(),
+                        step.to_string(),
+                        location.identifier.clone(),
+                        location
+                            .zone
+                            .map(|zone| zone.to_string())
+                            .unwrap_or_default(),
+                        x,
+                        y,
+                        item,
+                    ])
+                    .unwrap();
+            }
+        }
+
+        // The records are valid utf-8 since they were created from Strings
+        String::from_utf8(writer.into_inner().unwrap()).unwrap()
+    }
+
+    fn write_html(&self, f: &mut String) -> fmt::Result {
+        let world_count = self.spawns.len();
+        let multiworld = world_count > 1;
+
+        writeln!(f, "<!DOCTYPE html>")?;
+        writeln!(f, "<html lang=\"en\">")?;
+        writeln!(f, "<head>")?;
+        writeln!(f, "<meta charset=\"utf-8\">")?;
+        writeln!(f, "<title>Spoiler</title>")?;
+        writeln!(f, "<style>")?;
+        f.push_str(HTML_STYLE);
+        for index in 0..world_count {
+            writeln!(
+                f,
+                "#world-{index}:checked ~ #world-panel-{index} {{ display: block; }}"
+            )?;
+        }
+        writeln!(f, "</style>")?;
+        writeln!(f, "</head>")?;
+        writeln!(f, "<body>")?;
+        writeln!(f, "<h1>Spoiler</h1>")?;
+
+        // The tabs are radio buttons so the page works without scripts
+        for index in 0..world_count {
+            let checked = if index == 0 { " checked" } else { "" };
+            writeln!(
+                f,
+                "<input type=\"radio\" name=\"world\" id=\"world-{index}\" class=\"tab\"{checked}>"
+            )?;
+            if multiworld {
+                writeln!(f, "<label for=\"world-{index}\">World {index}</label>")?;
+            }
+        }
+
+        for (world_index, spawn) in self.spawns.iter().enumerate() {
+            writeln!(
+                f,
+                "<section class=\"world\" id=\"world-panel-{world_index}\">"
+            )?;
+            self.write_html_world(f, world_index, spawn, multiworld)?;
+            writeln!(f, "</section>")?;
+        }
+
+        writeln!(f, "</body>")?;
+        writeln!(f, "</html>")
+    }
+
+    fn write_html_world(
+        &self,
+        f: &mut String,
+        world_index: usize,
+        spawn: &str,
+        multiworld: bool,
+    ) -> fmt::Result {
+        let item = |placement: &SpoilerPlacement| {
+            if placement.target_world_index == world_index {
+                Escaped(&placement.item_name).to_string()
+            } else {
+                format!(
+                    "{} <span class=\"target\">(World {})</span>",
+                    Escaped(&placement.item_name),
+                    placement.target_world_index
+                )
+            }
+        };
+        let position = |placement: &SpoilerPlacement| {
+            placement
+                .location
+                .position
+                .as_ref()
+                .map(ToString::to_string)
+                .unwrap_or_default()
+        };
+        let identifiers = |nodes: &[NodeSummary]| {
+            nodes
+                .iter()
+                .map(|node| Escaped(&node.identifier))
+                .join(", ")
+        };
+
+        writeln!(f, "<dl>")?;
+        writeln!(f, "<dt>Spawn</dt><dd>{}</dd>", Escaped(spawn))?;
+        if let Some(spirit_light) = self.spirit_light.get(world_index) {
+            writeln!(
+                f,
+                "<dt>Spirit Light</dt><dd>{} ({})</dd>",
+                spirit_light.total, spirit_light.distribution
+            )?;
+        }
+        if let Some(relics) = self
+            .relics
+            .get(world_index)
+            .filter(|relics| !relics.is_empty())
+        {
+            writeln!(f, "<dt>Relics</dt><dd>{}</dd>", identifiers(relics))?;
+        }
+        if let Some(excluded_locations) = self
+            .excluded_locations
+            .get(world_index)
+            .filter(|excluded_locations| !excluded_locations.is_empty())
+        {
+            writeln!(
+                f,
+                "<dt>Excluded locations</dt><dd>{}</dd>",
+                identifiers(excluded_locations)
+            )?;
+        }
+        writeln!(f, "</dl>")?;
+
+        writeln!(f, "<h2>Steps</h2>")?;
+        for (index, group) in self.groups.iter().enumerate() {
+            let placements = group
+                .placements
+                .iter()
+                .filter(|placement| placement.origin_world_index == world_index)
+                .collect::<Vec<_>>();
+
+            write!(f, "<details><summary>Step {index}")?;
+            if group.reachable.is_empty() {
+                write!(f, " (priority placements)")?;
+            }
+            writeln!(
+                f,
+                " &ndash; {}</summary>",
+                placement_count(placements.len())
+            )?;
+
+            if let Some(reachable) = group.reachable.get(world_index) {
+                if reachable.is_empty() {
+                    writeln!(f, "<p>No new reachables</p>")?;
+                } else {
+                    writeln!(
+                        f,
+                        "<p>{} new reachable{}: {}</p>",
+                        reachable.len(),
+                        if reachable.len() > 1 { "s" } else { "" },
+                        identifiers(reachable)
+                    )?;
+                }
+            }
+            if let Some(paths) = group
+                .paths
+                .get(world_index)
+                .filter(|paths| !paths.is_empty())
+            {
+                writeln!(f, "<ul class=\"paths\">")?;
+                for path in paths {
+                    writeln!(
+                        f,
+                        "<li>{}: {} [{}]</li>",
+                        Escaped(&path.location),
+                        Escaped(&path.anchors.join(" -> ")),
+                        Escaped(&path.requirement)
+                    )?;
+                }
+                writeln!(f, "</ul>")?;
+            }
+            if !group.forced_items.items.is_empty() {
+                writeln!(
+                    f,
+                    "<p>Force placed: {}</p>",
+                    Escaped(&group.forced_items.to_string())
+                )?;
+            }
+
+            if !placements.is_empty() {
+                writeln!(f, "<table>")?;
+                writeln!(
+                    f,
+                    "<tr><th>Item</th><th>Location</th><th>Position</th></tr>"
+                )?;
+                for placement in placements {
+                    writeln!(
+                        f,
+                        "<tr><td>{}</td><td>{}</td><td>{}</td></tr>",
+                        item(placement),
+                        Escaped(&placement.location.identifier),
+                        position(placement)
+                    )?;
+                }
+                writeln!(f, "</table>")?;
+            }
+            writeln!(f, "</details>")?;
+        }
+
+        let mut zones = BTreeMap::<_, Vec<_>>::new();
+        for (index, group) in self.groups.iter().enumerate() {
+            for placement in &group.placements {
+                if placement.origin_world_index == world_index {
+                    zones
+                        .entry(placement.location.zone)
+                        .or_default()
+                        .push((index, placement));
+                }
+            }
+        }
+
+        writeln!(f, "<h2>Zones</h2>")?;
+        for (zone, placements) in zones {
+            write!(f, "<details><summary>")?;
+            match zone {
+                Some(zone) => write!(f, "{zone}"),
+                None => write!(f, "Other"),
+            }?;
+            writeln!(
+                f,
+                " &ndash; {}</summary>",
+                placement_count(placements.len())
+            )?;
+            writeln!(f, "<table>")?;
+            writeln!(
+                f,
+                "<tr><th>Location</th><th>Item</th><th>Step</th><th>Position</th></tr>"
+            )?;
+            for (index, placement) in placements {
+                writeln!(
+                    f,
+                    "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                    Escaped(&placement.location.identifier),
+                    item(placement),
+                    index,
+                    position(placement)
+                )?;
+            }
+            writeln!(f, "</table>")?;
+            writeln!(f, "</details>")?;
+        }
+
+        if let Some(playthrough) = &self.playthrough {
+            writeln!(f, "<h2>Playthrough</h2>")?;
+            if multiworld {
+                writeln!(
+                    f,
+                    "<p>The spheres are shared between all worlds, only the locations of World {world_index} are listed</p>"
+                )?;
+            }
+            for (index, sphere) in playthrough.spheres.iter().enumerate() {
+                let placements = sphere
+                    .iter()
+                    .filter(|placement| placement.origin_world_index == world_index)
+                    .collect::<Vec<_>>();
+                if placements.is_empty() {
+                    continue;
+                }
+
+                writeln!(f, "<details open><summary>Sphere {index}</summary>")?;
+                writeln!(f, "<ul>")?;
+                for placement in placements {
+                    writeln!(
+                        f,
+                        "<li>{} at {}</li>",
+                        item(placement),
+                        Escaped(&placement.location.identifier)
+                    )?;
+                }
+                writeln!(f, "</ul>")?;
+                writeln!(f, "</details>")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+const HTML_STYLE: &str = "\
+body { font-family: sans-serif; margin: 2em; color: #222; }
+table { border-collapse: collapse; margin: 0.5em 0 1em; }
+th, td { border: 1px solid #ccc; padding: 0.2em 0.6em; text-align: left; }
+th { background: #eee; }
+details { margin: 0.3em 0; }
+summary { cursor: pointer; font-weight: bold; }
+dt { font-weight: bold; }
+.paths { font-family: monospace; }
+.target { color: #777; }
+.tab { display: none; }
+.tab + label { display: inline-block; padding: 0.4em 1em; border: 1px solid #ccc; cursor: pointer; }
+.tab:checked + label { background: #eee; }
+.world { display: none; }
+";
+
+/// The merchant selling the location, see [`ShopSummary::merchant`]
+fn merchant(identifier: &str) -> Option<&str> {
+    let (anchor, name) = identifier.split_once('.')?;
+    if name == "LupoMap" {
+        Some("LupoShop")
+    } else if anchor.ends_with("Shop") {
+        Some(anchor)
+    } else {
+        None
+    }
+}
+
+fn placement_count(count: usize) -> String {
+    match count {
+        1 => "1 placement".to_string(),
+        _ => format!("{count} placements"),
+    }
+}
+
+/// Escapes text for use in html
+struct Escaped<'a>(&'a str);
+impl Display for Escaped<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for char in self.0.chars() {
+            match char {
+                '&' => f.write_str("&amp;"),
+                '<' => f.write_str("&lt;"),
+                '>' => f.write_str("&gt;"),
+                '"' => f.write_str("&quot;"),
+                '\'' => f.write_str("&#39;"),
+                _ => f.write_char(char),
+            }?;
+        }
+        Ok(())
+    }
 }
 
 impl Display for SeedSpoiler {
@@ -77,6 +656,42 @@ impl Display for SeedSpoiler {
             writeln!(f, "Spawn: {spawn}")?;
         }
 
+        for (index, excluded_locations) in self.excluded_locations.iter().enumerate() {
+            if !excluded_locations.is_empty() {
+                if multiworld {
+                    write!(f, "Excluded locations for World [{index}]: ")?;
+                } else {
+                    write!(f, "Excluded locations: ")?;
+                }
+                let locations = excluded_locations
+                    .iter()
+                    .map(|node| &node.identifier)
+                    .join(", ");
+                writeln!(f, "{locations}")?;
+            }
+        }
+
+        for (index, spirit_light) in self.spirit_light.iter().enumerate() {
+            if multiworld {
+                write!(f, "Spirit Light for World [{index}]: ")?;
+            } else {
+                write!(f, "Spirit Light: ")?;
+            }
+            writeln!(f, "{} ({})", spirit_light.total, spirit_light.distribution)?;
+        }
+
+        for (index, relics) in self.relics.iter().enumerate() {
+            if !relics.is_empty() {
+                if multiworld {
+                    write!(f, "Relics for World [{index}]: ")?;
+                } else {
+                    write!(f, "Relics: ")?;
+                }
+                let locations = relics.iter().map(|node| &node.identifier).join(", ");
+                writeln!(f, "{locations}")?;
+            }
+        }
+
         writeln!(f)?;
         writeln!(f)?;
 
@@ -114,13 +729,15 @@ impl Display for SeedSpoiler {
                     .collect::<Result<Vec<_>, _>>()?;
                 Ok((
                     &spoiler_group.reachable,
+                    &spoiler_group.paths,
                     &spoiler_group.forced_items,
                     placements,
                 ))
             })
             .collect::<Result<Vec<_>, _>>()?;
 
-        for (index, (reachable, forced_items, placements)) in spoiler_groups.into_iter().enumerate()
+        for (index, (reachable, paths, forced_items, placements)) in
+            spoiler_groups.into_iter().enumerate()
         {
             write!(f, "Step {index}")?;
 
@@ -150,6 +767,16 @@ impl Display for SeedSpoiler {
                         }
                         writeln!(f, ": {locations}")?;
                     }
+
+                    for path in paths.get(world_index).into_iter().flatten() {
+                        writeln!(
+                            f,
+                            "    {}: {} [{}]",
+                            path.location,
+                            path.anchors.join(" -> "),
+                            path.requirement
+                        )?;
+                    }
                 }
             }
 
@@ -172,6 +799,727 @@ impl Display for SeedSpoiler {
             writeln!(f)?;
         }
 
+        for (index, summary) in self.summaries.iter().enumerate() {
+            if multiworld {
+                writeln!(f, "Summary for World [{index}]")?;
+            } else {
+                writeln!(f, "Summary")?;
+            }
+
+            if !summary.zones.is_empty() {
+                writeln!(f, "  Zones")?;
+                for zone in &summary.zones {
+                    writeln!(
+                        f,
+                        "    {}: Skills {}, Shards {}, Keystones {}, Ore {}, Spirit Light {}",
+                        zone.zone,
+                        zone.skills,
+                        zone.shards,
+                        zone.keystones,
+                        zone.ore,
+                        zone.spirit_light
+                    )?;
+                }
+            }
+            if !summary.shops.is_empty() {
+                writeln!(f, "  Shops")?;
+                for shop in &summary.shops {
+                    writeln!(f, "    {}", shop.merchant)?;
+                    for placement in &shop.placements {
+                        write_placement_entry(f, "      ", placement, multiworld)?;
+                    }
+                }
+            }
+            for (title, placements) in [
+                ("Skills", &summary.skills),
+                ("Teleporters", &summary.teleporters),
+            ] {
+                if !placements.is_empty() {
+                    writeln!(f, "  {title}")?;
+                    for placement in placements {
+                        write_placement_entry(f, "    ", placement, multiworld)?;
+                    }
+                }
+            }
+
+            writeln!(f)?;
+        }
+
+        if let Some(playthrough) = &self.playthrough {
+            writeln!(f, "Playthrough")?;
+            writeln!(f)?;
+
+            for (index, sphere) in playthrough.spheres.iter().enumerate() {
+                writeln!(f, "  Sphere {index}")?;
+                for placement in sphere {
+                    write_placement_entry(f, "    ", placement, multiworld)?;
+                }
+                writeln!(f)?;
+            }
+        }
+
         Ok(())
     }
 }
+
+/// Writes a line like `Sword at MarshSpawn.ResilienceShard`, or `[1] Sword at [0] MarshSpawn.ResilienceShard` in multiworld
+fn write_placement_entry(
+    f: &mut fmt::Formatter,
+    indent: &str,
+    placement: &SpoilerPlacement,
+    multiworld: bool,
+) -> fmt::Result {
+    write!(f, "{indent}")?;
+    if multiworld {
+        write!(f, "[{}] ", placement.target_world_index)?;
+    }
+    write!(f, "{} at ", placement.item_name)?;
+    if multiworld {
+        write!(f, "[{}] ", placement.origin_world_index)?;
+    }
+    writeln!(f, "{}", placement.location.identifier)
+}
+
+impl FromStr for SeedSpoiler {
+    type Err = String;
+
+    /// Parses a spoiler written by the [`Display`] implementation
+    ///
+    /// Not everything is part of the text format, so some information can't be recovered:
+    /// - The [`Zone`]s of all locations and the [`Position`]s of locations outside of placements will be [`None`],
+    ///   use [`SeedSpoiler::locate`] to look them up in the logic
+    /// - The [`Item`]s are recovered from their names where possible. Unknown names, such as custom names provided by headers,
+    ///   result in an [`Item::Message`] displaying the name
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines().enumerate().map(|(index, line)| (index + 1, line));
+
+        let mut spawns = Vec::new();
+        let mut excluded_locations = Vec::new();
+        let mut spirit_light = Vec::new();
+        let mut relics = Vec::new();
+        for (line_number, line) in lines.by_ref().take_while(|(_, line)| !line.is_empty()) {
+            let error = |message: &str| format!("Line {line_number}: {message}");
+            if let Some((_, spawn)) = world_prefixed(line, "Spawn") {
+                spawns.push(spawn.to_string());
+            } else if let Some((index, locations)) = world_prefixed(line, "Excluded locations") {
+                excluded_locations.push((index, node_summaries(locations)));
+            } else if let Some((_, summary)) = world_prefixed(line, "Spirit Light") {
+                spirit_light.push(
+                    spirit_light_summary(summary)
+                        .ok_or_else(|| error("Invalid Spirit Light summary"))?,
+                );
+            } else if let Some((index, locations)) = world_prefixed(line, "Relics") {
+                relics.push((index, node_summaries(locations)));
+            } else {
+                return Err(error("Unknown header line"));
+            }
+        }
+
+        let world_count = spawns.len();
+        if world_count == 0 {
+            return Err("No spawn found".to_string());
+        }
+        let multiworld = world_count > 1;
+        let per_world = |nodes: Vec<(usize, Vec<NodeSummary>)>| {
+            let mut per_world = vec![Vec::new(); world_count];
+            for (index, nodes) in nodes {
+                *per_world
+                    .get_mut(index)
+                    .ok_or_else(|| format!("World index {index} out of bounds"))? = nodes;
+            }
+            Ok::<_, String>(per_world)
+        };
+        let excluded_locations = per_world(excluded_locations)?;
+        let relics = per_world(relics)?;
+
+        let mut groups = Vec::new();
+        let mut summaries = Vec::new();
+        let mut playthrough = None;
+        while let Some((line_number, line)) = lines.next() {
+            let error = |message: &str| format!("Line {line_number}: {message}");
+            if line.is_empty() {
+                continue;
+            }
+            if line == "Playthrough" {
+                playthrough = Some(parse_playthrough(&mut lines, multiworld)?);
+                break;
+            }
+            if let Some(world_index) = summary_header(line) {
+                if world_index != Some(summaries.len()) {
+                    return Err(error(&format!(
+                        "Expected the summary for World {}",
+                        summaries.len()
+                    )));
+                }
+                summaries.push(parse_summary(&mut lines, multiworld)?);
+                continue;
+            }
+
+            let step = line
+                .strip_prefix("Step ")
+                .ok_or_else(|| error("Expected a step"))?;
+            let (step, priority) = match step.strip_suffix(" (priority placements)") {
+                Some(step) => (step, true),
+                None => (step, false),
+            };
+            if step.parse() != Ok(groups.len()) {
+                return Err(error(&format!("Expected step {}", groups.len())));
+            }
+
+            let mut group = SpoilerGroup::default();
+            for (line_number, line) in lines.by_ref().take_while(|(_, line)| !line.is_empty()) {
+                let error = |message: &str| format!("Line {line_number}: {message}");
+                if let Some(forced_items) = line.strip_prefix("  Force placed: ") {
+                    group.forced_items = inventory(forced_items);
+                } else if let Some(path) = line.strip_prefix("    ") {
+                    let world_index = group
+                        .reachable
+                        .len()
+                        .checked_sub(1)
+                        .ok_or_else(|| error("Expected reachables before the logic path"))?;
+                    group.paths.resize_with(world_count, Vec::new);
+                    group.paths[world_index]
+                        .push(logic_path(path).ok_or_else(|| error("Invalid logic path"))?);
+                } else if let (false, Some(reachable)) = (priority, line.strip_prefix("  ")) {
+                    let reachable = if multiworld {
+                        let (index, reachable) = world_index_prefix(reachable, ": ")
+                            .ok_or_else(|| error("Expected a world index"))?;
+                        if index != group.reachable.len() {
+                            return Err(error(&format!(
+                                "Expected reachables for World {}",
+                                group.reachable.len()
+                            )));
+                        }
+                        reachable
+                    } else {
+                        reachable
+                    };
+                    group
+                        .reachable
+                        .push(reachables(reachable).ok_or_else(|| error("Invalid reachables"))?);
+                } else {
+                    return Err(error("Unknown step line"));
+                }
+            }
+
+            for (line_number, line) in lines.by_ref().take_while(|(_, line)| !line.is_empty()) {
+                group.placements.push(
+                    placement(line, multiworld)
+                        .ok_or_else(|| format!("Line {line_number}: Invalid placement"))?,
+                );
+            }
+
+            groups.push(group);
+        }
+
+        Ok(SeedSpoiler {
+            spawns,
+            groups,
+            excluded_locations,
+            spirit_light,
+            relics,
+            summaries,
+            playthrough,
+        })
+    }
+}
+
+fn parse_playthrough<'a>(
+    lines: &mut impl Iterator<Item = (usize, &'a str)>,
+    multiworld: bool,
+) -> Result<Playthrough, String> {
+    let mut spheres = Vec::new();
+    for (line_number, line) in lines {
+        let error = |message: &str| format!("Line {line_number}: {message}");
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(sphere) = line.strip_prefix("  Sphere ") {
+            if sphere.parse() != Ok(spheres.len()) {
+                return Err(error(&format!("Expected sphere {}", spheres.len())));
+            }
+            spheres.push(Vec::new());
+        } else {
+            let placement = line
+                .strip_prefix("    ")
+                .and_then(|placement| placement_entry(placement, multiworld))
+                .ok_or_else(|| error("Invalid playthrough placement"))?;
+            spheres
+                .last_mut()
+                .ok_or_else(|| error("Expected a sphere before the placement"))?
+                .push(placement);
+        }
+    }
+
+    Ok(Playthrough { spheres })
+}
+
+/// Parses the line introducing a [`WorldSummary`]
+///
+/// Returns `Some(None)` if the world index is invalid
+fn summary_header(line: &str) -> Option<Option<usize>> {
+    if line == "Summary" {
+        return Some(Some(0));
+    }
+    let world = line.strip_prefix("Summary for World ")?;
+    Some(world_index_prefix(world, "").map(|(index, _)| index))
+}
+fn parse_summary<'a>(
+    lines: &mut impl Iterator<Item = (usize, &'a str)>,
+    multiworld: bool,
+) -> Result<WorldSummary, String> {
+    let mut summary = WorldSummary::default();
+    let mut section = None;
+    for (line_number, line) in lines.take_while(|(_, line)| !line.is_empty()) {
+        let error = |message: &str| format!("Line {line_number}: {message}");
+        if let Some(title @ ("Zones" | "Shops" | "Skills" | "Teleporters")) =
+            line.strip_prefix("  ")
+        {
+            section = Some(title);
+            continue;
+        }
+
+        let entry = line
+            .strip_prefix("    ")
+            .ok_or_else(|| error("Unknown summary line"))?;
+        let placement = |entry: &str| {
+            placement_entry(entry, multiworld).ok_or_else(|| error("Invalid placement"))
+        };
+        match section {
+            Some("Zones") => summary
+                .zones
+                .push(zone_summary(entry).ok_or_else(|| error("Invalid zone summary"))?),
+            Some("Shops") => match entry.strip_prefix("  ") {
+                Some(entry) => summary
+                    .shops
+                    .last_mut()
+                    .ok_or_else(|| error("Expected a merchant before the placement"))?
+                    .placements
+                    .push(placement(entry)?),
+                None => summary.shops.push(ShopSummary {
+                    merchant: entry.to_string(),
+                    placements: Vec::new(),
+                }),
+            },
+            Some("Skills") => summary.skills.push(placement(entry)?),
+            Some("Teleporters") => summary.teleporters.push(placement(entry)?),
+            _ => return Err(error("Expected a summary section")),
+        }
+    }
+
+    Ok(summary)
+}
+
+/// Parses lines like `Spawn: ...` or `Spawn for World [1]: ...`
+fn world_prefixed<'a>(line: &'a str, prefix: &str) -> Option<(usize, &'a str)> {
+    let line = line.strip_prefix(prefix)?;
+    match line.strip_prefix(": ") {
+        Some(rest) => Some((0, rest)),
+        None => world_index_prefix(line.strip_prefix(" for World ")?, ": "),
+    }
+}
+/// Parses a world index like `[1]` followed by `separator`
+fn world_index_prefix<'a>(s: &'a str, separator: &str) -> Option<(usize, &'a str)> {
+    let (index, rest) = s.strip_prefix('[')?.split_once(']')?;
+    Some((index.parse().ok()?, rest.strip_prefix(separator)?))
+}
+fn node_summary(identifier: &str, position: Option<Position>) -> NodeSummary {
+    NodeSummary {
+        identifier: identifier.to_string(),
+        position,
+        zone: None,
+    }
+}
+fn node_summaries(identifiers: &str) -> Vec<NodeSummary> {
+    identifiers
+        .split(", ")
+        .map(|identifier| node_summary(identifier, None))
+        .collect()
+}
+fn spirit_light_summary(s: &str) -> Option<SpiritLightSummary> {
+    let (total, distribution) = s.split_once(" (")?;
+    let distribution = match distribution.strip_suffix(')')? {
+        "Increasing" => SpiritLightDistribution::Increasing,
+        "Flat" => SpiritLightDistribution::Flat,
+        "Zone weighted" => SpiritLightDistribution::ZoneWeighted,
+        capped => SpiritLightDistribution::Capped(capped.strip_prefix("Capped at ")?.parse().ok()?),
+    };
+    Some(SpiritLightSummary {
+        distribution,
+        total: total.parse().ok()?,
+    })
+}
+fn zone_summary(s: &str) -> Option<ZoneSummary> {
+    let (zone, counts) = s.split_once(": ")?;
+    let mut counts = counts.split(", ").map(|count| count.rsplit_once(' '));
+    let mut count = |label: &str| {
+        let (count_label, count) = counts.next()??;
+        (count_label == label).then(|| count.parse().ok())?
+    };
+    let summary = ZoneSummary {
+        zone: variant_from_name(zone)?,
+        skills: count("Skills")?,
+        shards: count("Shards")?,
+        keystones: count("Keystones")?,
+        ore: count("Ore")?,
+        spirit_light: count("Spirit Light")? as u32,
+    };
+    counts.next().is_none().then_some(summary)
+}
+fn reachables(s: &str) -> Option<Vec<NodeSummary>> {
+    if s == "No new reachables" {
+        return Some(Vec::new());
+    }
+    let (count, locations) = s.split_once(" new reachable")?;
+    let locations = locations
+        .strip_prefix("s: ")
+        .or_else(|| locations.strip_prefix(": "))?;
+    let locations = node_summaries(locations);
+    (count.parse() == Ok(locations.len())).then_some(locations)
+}
+fn logic_path(s: &str) -> Option<LogicPath> {
+    let (location, rest) = s.split_once(": ")?;
+    let (anchors, requirement) = rest.strip_suffix(']')?.split_once(" [")?;
+    let anchors = if anchors.is_empty() {
+        Vec::new()
+    } else {
+        anchors.split(" -> ").map(str::to_string).collect()
+    };
+    Some(LogicPath {
+        location: location.to_string(),
+        anchors,
+        requirement: requirement.to_string(),
+    })
+}
+fn inventory(s: &str) -> Inventory {
+    let mut inventory = Inventory::default();
+    for item in s.split(", ") {
+        let (item, amount) = item_from_name(item)
+            .map(|item| (item, 1))
+            .or_else(|| {
+                let (amount, item) = item.split_once(' ')?;
+                Some((item_from_name(item)?, amount.parse().ok()?))
+            })
+            .unwrap_or_else(|| (unknown_item(item), 1));
+        inventory.grant(item, amount);
+    }
+    inventory
+}
+/// Parses the item name and location of a placement, which are separated by at least two spaces for alignment
+fn placement(s: &str, multiworld: bool) -> Option<SpoilerPlacement> {
+    let mut parts = s.split("  ").map(str::trim).filter(|part| !part.is_empty());
+    let (item_name, location) = (parts.next()?, parts.next()?);
+    let position = match parts.next() {
+        Some(position) => {
+            let (x, y) = position.split_once(", ")?;
+            let (x, y) = (x.parse::<f32>().ok()?, y.parse::<f32>().ok()?);
+            if !x.is_finite() || !y.is_finite() {
+                return None;
+            }
+            Some(Position::new(x, y))
+        }
+        None => None,
+    };
+    if parts.next().is_some() {
+        return None;
+    }
+    spoiler_placement(item_name, location, position, multiworld)
+}
+fn placement_entry(s: &str, multiworld: bool) -> Option<SpoilerPlacement> {
+    let (item_name, location) = s.rsplit_once(" at ")?;
+    spoiler_placement(item_name, location, None, multiworld)
+}
+fn spoiler_placement(
+    item_name: &str,
+    location: &str,
+    position: Option<Position>,
+    multiworld: bool,
+) -> Option<SpoilerPlacement> {
+    let ((target_world_index, item_name), (origin_world_index, location)) = if multiworld {
+        (
+            world_index_prefix(item_name, " ")?,
+            world_index_prefix(location, " ")?,
+        )
+    } else {
+        ((0, item_name), (0, location))
+    };
+    Some(SpoilerPlacement {
+        origin_world_index,
+        target_world_index,
+        location: node_summary(location, position),
+        item: item_from_name(item_name).unwrap_or_else(|| unknown_item(item_name)),
+        item_name: item_name.to_string(),
+    })
+}
+
+/// Reverses the [`Display`] implementation of [`Item`] for the items commonly found in spoilers
+fn item_from_name(name: &str) -> Option<Item> {
+    if let Some(name) = name.strip_prefix("Remove ") {
+        return match item_from_name(name)? {
+            Item::SpiritLight(amount) => Some(Item::RemoveSpiritLight(amount)),
+            Item::Skill(skill) => Some(Item::RemoveSkill(skill)),
+            Item::Shard(shard) => Some(Item::RemoveShard(shard)),
+            Item::Teleporter(teleporter) => Some(Item::RemoveTeleporter(teleporter)),
+            Item::Water => Some(Item::RemoveWater),
+            _ => None,
+        };
+    }
+    if let Some(amount) = name.strip_suffix(" Spirit Light") {
+        return amount.parse().ok().map(Item::SpiritLight);
+    }
+    if let Some(teleporter) = name.strip_suffix(" TP") {
+        return variant_from_name(teleporter).map(Item::Teleporter);
+    }
+    if let Some(zone) = name.strip_suffix(" Relic") {
+        return variant_from_name(zone).map(Item::Relic);
+    }
+    if let Some(message) = name
+        .strip_prefix("Display \"")
+        .and_then(|message| message.strip_suffix('"'))
+    {
+        return Some(unknown_item(message));
+    }
+    if name == "Clean Water" {
+        return Some(Item::Water);
+    }
+
+    variant_from_name(name)
+        .map(Item::Resource)
+        .or_else(|| variant_from_name(name).map(Item::Skill))
+        .or_else(|| variant_from_name(name).map(Item::Shard))
+        .or_else(|| variant_from_name(name).map(Item::BonusItem))
+        .or_else(|| variant_from_name(name).map(Item::BonusUpgrade))
+}
+fn variant_from_name<T: TryFrom<u8> + Display>(name: &str) -> Option<T> {
+    (0..=u8::MAX)
+        .filter_map(|value| T::try_from(value).ok())
+        .find(|variant| variant.to_string() == name)
+}
+fn unknown_item(name: &str) -> Item {
+    Item::Message(Message::new(name.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        files::FILE_SYSTEM_ACCESS,
+        generate_seed,
+        settings::{UniverseSettings, WorldSettings},
+        test_util::test_graph,
+    };
+
+    fn placement(location: &str, item: Item) -> SpoilerPlacement {
+        SpoilerPlacement {
+            origin_world_index: 0,
+            target_world_index: 0,
+            location: NodeSummary {
+                identifier: location.to_string(),
+                position: Some(Position::new(-799.0, -4310.0)),
+                zone: Some(Zone::Marsh),
+            },
+            item_name: item.to_string(),
+            item,
+        }
+    }
+
+    fn spoiler() -> SeedSpoiler {
+        SeedSpoiler {
+            spawns: vec!["MarshSpawn.Main".to_string()],
+            groups: vec![
+                SpoilerGroup::default(),
+                SpoilerGroup {
+                    reachable: vec![vec![placement("MarshSpawn.RockHC", Item::Water).location]],
+                    forced_items: Inventory::default(),
+                    placements: vec![placement(
+                        "MarshSpawn.RockHC",
+                        Item::Resource(Resource::HealthFragment),
+                    )],
+                    paths: Vec::new(),
+                },
+            ],
+            excluded_locations: vec![Vec::new()],
+            spirit_light: Vec::new(),
+            relics: vec![Vec::new()],
+            summaries: Vec::new(),
+            playthrough: None,
+        }
+    }
+
+    /// Every location of both spoilers, in the same order
+    fn locations(spoiler: &SeedSpoiler) -> Vec<&NodeSummary> {
+        spoiler
+            .groups
+            .iter()
+            .flat_map(|group| {
+                group
+                    .reachable
+                    .iter()
+                    .flatten()
+                    .chain(group.placements.iter().map(|placement| &placement.location))
+            })
+            .chain(spoiler.excluded_locations.iter().flatten())
+            .chain(spoiler.relics.iter().flatten())
+            .collect()
+    }
+
+    fn assert_text_round_trip(spoiler: &SeedSpoiler, graph: &Graph) {
+        let text = spoiler.to_string();
+        let mut parsed = text.parse::<SeedSpoiler>().unwrap();
+        assert!(spoiler.diff(&parsed).is_empty());
+        assert!(locations(&parsed)
+            .iter()
+            .all(|location| location.zone.is_none()));
+
+        parsed.locate(graph);
+        for (location, parsed_location) in locations(spoiler).into_iter().zip(locations(&parsed)) {
+            assert_eq!(location.identifier, parsed_location.identifier);
+            assert_eq!(location.zone, parsed_location.zone);
+            assert_eq!(location.position, parsed_location.position);
+        }
+        for (group, parsed_group) in spoiler.groups.iter().zip(&parsed.groups) {
+            assert_eq!(group.forced_items, parsed_group.forced_items);
+        }
+
+        // Forced items are listed in no particular order
+        let parsed_text = parsed.to_string();
+        for (line, parsed_line) in text.lines().zip_eq(parsed_text.lines()) {
+            match (
+                line.strip_prefix("  Force placed: "),
+                parsed_line.strip_prefix("  Force placed: "),
+            ) {
+                (Some(items), Some(parsed_items)) => assert_eq!(
+                    items.split(", ").sorted().collect::<Vec<_>>(),
+                    parsed_items.split(", ").sorted().collect::<Vec<_>>()
+                ),
+                _ => assert_eq!(line, parsed_line),
+            }
+        }
+    }
+
+    #[test]
+    fn text_round_trip() {
+        let mut universe_settings = UniverseSettings {
+            seed: "text round trip".to_string(),
+            spoiler_paths: true,
+            ..UniverseSettings::default()
+        };
+        let graph = test_graph(&universe_settings);
+
+        let mut seed = generate_seed(&graph, &FILE_SYSTEM_ACCESS, &universe_settings).unwrap();
+        seed.spoiler.playthrough = seed.playthrough();
+        assert!(seed.spoiler.playthrough.is_some());
+        assert_text_round_trip(&seed.spoiler, &graph);
+
+        universe_settings.world_settings = vec![WorldSettings::default(); 2];
+        let seed = generate_seed(&graph, &FILE_SYSTEM_ACCESS, &universe_settings).unwrap();
+        assert_text_round_trip(&seed.spoiler, &graph);
+    }
+
+    #[test]
+    fn bincode_round_trip() {
+        let mut spoiler = spoiler();
+        let bytes = bincode::serialize(&spoiler).unwrap();
+        let deserialized = bincode::deserialize::<SeedSpoiler>(&bytes).unwrap();
+        assert_eq!(deserialized.to_string(), spoiler.to_string());
+        assert!(deserialized.playthrough.is_none());
+
+        spoiler.playthrough = Some(Playthrough {
+            spheres: vec![spoiler.groups[1].placements.clone()],
+        });
+        let bytes = bincode::serialize(&spoiler).unwrap();
+        let deserialized = bincode::deserialize::<SeedSpoiler>(&bytes).unwrap();
+        assert_eq!(deserialized.to_string(), spoiler.to_string());
+    }
+
+    #[test]
+    fn html_export() {
+        let mut spoiler = spoiler();
+        let mut tricky = placement("MarshSpawn.RockHC", Item::Water);
+        tricky.item_name = "<b>Fish & \"Chips\"</b>".to_string();
+        spoiler.groups[1].placements.push(tricky);
+
+        let html = spoiler.to_html();
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("MarshSpawn.Main"));
+        assert!(html.contains("&lt;b&gt;Fish &amp; &quot;Chips&quot;&lt;/b&gt;"));
+        assert!(!html.contains("<b>Fish"));
+        // Single world spoilers don't need tabs to switch between worlds
+        assert!(!html.contains("World 0</label>"));
+
+        spoiler.spawns.push("MarshSpawn.Main".to_string());
+        spoiler.groups[1].placements[0].target_world_index = 1;
+        let html = spoiler.to_html();
+        assert!(html.contains("<label for=\"world-1\">World 1</label>"));
+        assert!(html.contains("<span class=\"target\">(World 1)</span>"));
+    }
+
+    #[test]
+    fn csv_export() {
+        let mut spoiler = spoiler();
+        let mut tricky = placement("MarshSpawn.RockHC", Item::Water);
+        tricky.item_name = "Fish, \"Chips\"".to_string();
+        spoiler.groups[1].placements.push(tricky);
+
+        let csv = spoiler.to_csv();
+        let mut reader = csv::Reader::from_reader(csv.as_bytes());
+        assert_eq!(
+            reader.headers().unwrap(),
+            vec!["world", "step", "location", "zone", "x", "y", "item"]
+        );
+        let records = reader.records().map(Result::unwrap).collect::<Vec<_>>();
+        assert_eq!(records.len(), 2);
+        assert_eq!(
+            records[0],
+            vec![
+                "0",
+                "1",
+                "MarshSpawn.RockHC",
+                "Marsh",
+                "-799",
+                "-4310",
+                "Health Fragment"
+            ]
+        );
+        // Commas and quotes survive the round trip
+        assert_eq!(&records[1][6], "Fish, \"Chips\"");
+
+        spoiler.spawns.push("MarshSpawn.Main".to_string());
+        spoiler.groups[1].placements[1].target_world_index = 1;
+        let csv = spoiler.to_csv();
+        let mut reader = csv::Reader::from_reader(csv.as_bytes());
+        let record = reader.records().nth(1).unwrap().unwrap();
+        assert_eq!(&record[6], "[1] Fish, \"Chips\"");
+    }
+
+    #[test]
+    fn shop_summaries() {
+        let mut spoiler = spoiler();
+        spoiler.groups[1].placements.extend([
+            placement("OpherShop.Spike", Item::Water),
+            placement("LupoShop.HCMapIcon", Item::Resource(Resource::Keystone)),
+            placement("WestHollow.LupoMap", Item::Resource(Resource::GorlekOre)),
+        ]);
+
+        let shops = &spoiler.summarize()[0].shops;
+        let merchants = shops
+            .iter()
+            .map(|shop| {
+                let locations = shop
+                    .placements
+                    .iter()
+                    .map(|placement| placement.location.identifier.as_str())
+                    .collect::<Vec<_>>();
+                (shop.merchant.as_str(), locations)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            merchants,
+            vec![
+                ("LupoShop", vec!["LupoShop.HCMapIcon", "WestHollow.LupoMap"]),
+                ("OpherShop", vec!["OpherShop.Spike"]),
+            ]
+        );
+    }
+}
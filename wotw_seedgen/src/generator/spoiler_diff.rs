@@ -0,0 +1,500 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display},
+};
+
+use itertools::{EitherOrBoth, Itertools};
+use serde::{Deserialize, Serialize};
+
+use crate::Item;
+
+use super::{SeedSpoiler, SpoilerGroup, SpoilerPlacement};
+
+/// Differences between two [`SeedSpoiler`]s, see [`SeedSpoiler::diff`]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpoilerDiff {
+    /// The number of worlds in the larger of both spoilers
+    pub world_count: usize,
+    /// The worlds whose spawn changed
+    pub spawns: Vec<SpawnChange>,
+    /// The number of steps in the old spoiler
+    pub old_step_count: usize,
+    /// The number of steps in the new spoiler
+    pub new_step_count: usize,
+    /// The items that ended up on a different location, ordered by item name
+    pub moved_items: Vec<MovedItem>,
+    /// The steps where different locations became reachable
+    pub reachables: Vec<ReachableChange>,
+}
+/// A spawn that differs between two [`SeedSpoiler`]s
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpawnChange {
+    pub world_index: usize,
+    /// The old spawn, or [`None`] if the world didn't exist in the old spoiler
+    pub old: Option<String>,
+    /// The new spawn, or [`None`] if the world doesn't exist in the new spoiler
+    pub new: Option<String>,
+}
+/// An item that was placed on a different location
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MovedItem {
+    /// The world receiving the item
+    pub target_world_index: usize,
+    pub item_name: String,
+    /// Where the item was placed in the old spoiler, or [`None`] if it wasn't placed
+    pub old: Option<PlacementLocation>,
+    /// Where the item is placed in the new spoiler, or [`None`] if it isn't placed anymore
+    pub new: Option<PlacementLocation>,
+}
+/// Where an item was placed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlacementLocation {
+    /// The world holding the location
+    pub world_index: usize,
+    /// The location identifier
+    pub location: String,
+    /// The index of the step that placed the item
+    pub step: usize,
+}
+/// Differences in the new reachables of one step in one world
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReachableChange {
+    pub step: usize,
+    pub world_index: usize,
+    /// Identifiers of the locations that are only reachable on this step in the new spoiler
+    pub added: Vec<String>,
+    /// Identifiers of the locations that were only reachable on this step in the old spoiler
+    pub removed: Vec<String>,
+}
+
+impl SpoilerDiff {
+    /// Whether both spoilers place the same items on the same locations after reaching the same locations
+    pub fn is_empty(&self) -> bool {
+        self.spawns.is_empty()
+            && self.old_step_count == self.new_step_count
+            && self.moved_items.is_empty()
+            && self.reachables.is_empty()
+    }
+
+    /// Serialize into json format
+    pub fn to_json(&self) -> String {
+        // This is safe because the SpoilerDiff struct is known to serialize successfully
+        serde_json::to_string(&self).unwrap()
+    }
+}
+
+impl SeedSpoiler {
+    /// Compares the placements of two [`SeedSpoiler`]s
+    ///
+    /// This is meant for seeds generated with the same settings, to see the effects of changes to the logic or headers.
+    /// Items are told apart by their names and receiving worlds, so an item only counts as moved if no item with the same name took its place.
+    /// Spirit Light is compared regardless of the amounts, otherwise a slightly different distribution would move almost all of it
+    pub fn diff(&self, new: &SeedSpoiler) -> SpoilerDiff {
+        let spawns = self
+            .spawns
+            .iter()
+            .zip_longest(&new.spawns)
+            .enumerate()
+            .filter_map(|(world_index, spawns)| {
+                let (old, new) = match spawns {
+                    EitherOrBoth::Both(old, new) if old == new => return None,
+                    EitherOrBoth::Both(old, new) => (Some(old.clone()), Some(new.clone())),
+                    EitherOrBoth::Left(old) => (Some(old.clone()), None),
+                    EitherOrBoth::Right(new) => (None, Some(new.clone())),
+                };
+                Some(SpawnChange {
+                    world_index,
+                    old,
+                    new,
+                })
+            })
+            .collect();
+
+        let mut items = BTreeMap::<_, (Vec<_>, Vec<_>)>::new();
+        for (placement, location) in placement_locations(self) {
+            items.entry(placement).or_default().0.push(location);
+        }
+        for (placement, location) in placement_locations(new) {
+            items.entry(placement).or_default().1.push(location);
+        }
+        let moved_items = items
+            .into_iter()
+            .flat_map(|((item_name, target_world_index), (old, new))| {
+                let same_location = |location: &PlacementLocation, others: &[PlacementLocation]| {
+                    others.iter().any(|other| {
+                        other.world_index == location.world_index
+                            && other.location == location.location
+                    })
+                };
+                let old_only = old
+                    .iter()
+                    .filter(|location| !same_location(location, &new))
+                    .cloned()
+                    .collect::<Vec<_>>();
+                let new_only = new
+                    .iter()
+                    .filter(|location| !same_location(location, &old))
+                    .cloned()
+                    .collect::<Vec<_>>();
+                old_only
+                    .into_iter()
+                    .zip_longest(new_only)
+                    .map(move |locations| {
+                        let (old, new) = locations.map_any(Some, Some).or(None, None);
+                        MovedItem {
+                            target_world_index,
+                            item_name: item_name.to_string(),
+                            old,
+                            new,
+                        }
+                    })
+            })
+            .collect();
+
+        let world_count = self.spawns.len().max(new.spawns.len());
+        let mut reachables = Vec::new();
+        for (step, groups) in self.groups.iter().zip_longest(&new.groups).enumerate() {
+            let (old, new) = groups.map_any(Some, Some).or(None, None);
+            for world_index in 0..world_count {
+                let old = reachable_identifiers(old, world_index);
+                let new = reachable_identifiers(new, world_index);
+                let added = new
+                    .iter()
+                    .filter(|identifier| !old.contains(identifier))
+                    .map(|identifier| identifier.to_string())
+                    .collect::<Vec<_>>();
+                let removed = old
+                    .iter()
+                    .filter(|identifier| !new.contains(identifier))
+                    .map(|identifier| identifier.to_string())
+                    .collect::<Vec<_>>();
+                if !added.is_empty() || !removed.is_empty() {
+                    reachables.push(ReachableChange {
+                        step,
+                        world_index,
+                        added,
+                        removed,
+                    });
+                }
+            }
+        }
+
+        SpoilerDiff {
+            world_count,
+            spawns,
+            old_step_count: self.groups.len(),
+            new_step_count: new.groups.len(),
+            moved_items,
+            reachables,
+        }
+    }
+}
+
+fn reachable_identifiers(group: Option<&SpoilerGroup>, world_index: usize) -> Vec<&str> {
+    group
+        .and_then(|group| group.reachable.get(world_index))
+        .into_iter()
+        .flatten()
+        .map(|node| node.identifier.as_str())
+        .collect()
+}
+/// All placements of the spoiler, keyed by item name and receiving world
+///
+/// Any amount of Spirit Light is keyed as `"Spirit Light"`
+fn placement_locations(
+    spoiler: &SeedSpoiler,
+) -> impl Iterator<Item = ((&str, usize), PlacementLocation)> {
+    spoiler
+        .groups
+        .iter()
+        .enumerate()
+        .flat_map(|(step, group)| {
+            group
+                .placements
+                .iter()
+                .map(move |placement| (step, placement))
+        })
+        .map(|(step, placement): (usize, &SpoilerPlacement)| {
+            let item_name = match placement.item {
+                Item::SpiritLight(_) => "Spirit Light",
+                _ => placement.item_name.as_str(),
+            };
+            (
+                (item_name, placement.target_world_index),
+                PlacementLocation {
+                    world_index: placement.origin_world_index,
+                    location: placement.location.identifier.clone(),
+                    step,
+                },
+            )
+        })
+}
+
+impl Display for SpoilerDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "No differences");
+        }
+
+        let multiworld = self.world_count > 1;
+        let world = |world_index: usize| {
+            if multiworld {
+                format!("[{world_index}] ")
+            } else {
+                String::new()
+            }
+        };
+        let location = |location: &Option<PlacementLocation>| match location {
+            Some(location) => format!(
+                "{}{} (Step {})",
+                world(location.world_index),
+                location.location,
+                location.step
+            ),
+            None => "nowhere".to_string(),
+        };
+
+        for spawn in &self.spawns {
+            if multiworld {
+                write!(f, "Spawn for World [{}]: ", spawn.world_index)?;
+            } else {
+                write!(f, "Spawn: ")?;
+            }
+            writeln!(
+                f,
+                "{} -> {}",
+                spawn.old.as_deref().unwrap_or("none"),
+                spawn.new.as_deref().unwrap_or("none")
+            )?;
+        }
+        if self.old_step_count != self.new_step_count {
+            writeln!(
+                f,
+                "Steps: {} -> {}",
+                self.old_step_count, self.new_step_count
+            )?;
+        }
+
+        if !self.moved_items.is_empty() {
+            writeln!(f)?;
+            writeln!(f, "Moved items")?;
+            for item in &self.moved_items {
+                writeln!(
+                    f,
+                    "  {}{}: {} -> {}",
+                    world(item.target_world_index),
+                    item.item_name,
+                    location(&item.old),
+                    location(&item.new)
+                )?;
+            }
+        }
+
+        if !self.reachables.is_empty() {
+            writeln!(f)?;
+            writeln!(f, "Reachables")?;
+            for change in &self.reachables {
+                let changes = change
+                    .added
+                    .iter()
+                    .map(|identifier| format!("+{identifier}"))
+                    .chain(
+                        change
+                            .removed
+                            .iter()
+                            .map(|identifier| format!("-{identifier}")),
+                    )
+                    .join(", ");
+                write!(f, "  Step {}", change.step)?;
+                if multiworld {
+                    write!(f, " [{}]", change.world_index)?;
+                }
+                writeln!(f, ": {changes}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{generator::NodeSummary, item::Skill, util::Zone, Inventory};
+
+    fn node(identifier: &str) -> NodeSummary {
+        NodeSummary {
+            identifier: identifier.to_string(),
+            position: None,
+            zone: Some(Zone::Marsh),
+        }
+    }
+
+    fn placement(location: &str, item: Item) -> SpoilerPlacement {
+        SpoilerPlacement {
+            origin_world_index: 0,
+            target_world_index: 0,
+            location: node(location),
+            item_name: item.to_string(),
+            item,
+        }
+    }
+
+    fn group(reachable: &[&str], placements: Vec<SpoilerPlacement>) -> SpoilerGroup {
+        SpoilerGroup {
+            reachable: vec![reachable
+                .iter()
+                .map(|identifier| node(identifier))
+                .collect()],
+            forced_items: Inventory::default(),
+            placements,
+            paths: Vec::new(),
+        }
+    }
+
+    fn spoiler(spawn: &str, groups: Vec<SpoilerGroup>) -> SeedSpoiler {
+        SeedSpoiler {
+            spawns: vec![spawn.to_string()],
+            groups,
+            excluded_locations: vec![Vec::new()],
+            spirit_light: Vec::new(),
+            relics: vec![Vec::new()],
+            summaries: Vec::new(),
+            playthrough: None,
+        }
+    }
+
+    fn old() -> SeedSpoiler {
+        spoiler(
+            "MarshSpawn.Main",
+            vec![
+                group(
+                    &["MarshSpawn.RockHC", "MarshSpawn.CaveEX"],
+                    vec![
+                        placement("MarshSpawn.RockHC", Item::Skill(Skill::DoubleJump)),
+                        placement("MarshSpawn.CaveEX", Item::SpiritLight(50)),
+                    ],
+                ),
+                group(
+                    &["MarshSpawn.PoolsPathEX"],
+                    vec![placement("MarshSpawn.PoolsPathEX", Item::Water)],
+                ),
+            ],
+        )
+    }
+
+    #[test]
+    fn identical() {
+        let diff = old().diff(&old());
+        assert!(diff.is_empty());
+        assert_eq!(diff.to_string(), "No differences\n");
+    }
+
+    #[test]
+    fn moved_items() {
+        let mut new = old();
+        // A different amount of Spirit Light on the same location doesn't count as moved
+        new.groups[0].placements[1].item = Item::SpiritLight(75);
+        new.groups[0].placements[0].location = node("MarshSpawn.CaveEX");
+        new.groups[0].placements[1].location = node("MarshSpawn.RockHC");
+        new.groups[1].placements.clear();
+
+        let diff = old().diff(&new);
+        assert!(diff.spawns.is_empty());
+        assert!(diff.reachables.is_empty());
+        let moved = diff
+            .moved_items
+            .iter()
+            .map(|moved| {
+                (
+                    moved.item_name.as_str(),
+                    moved.old.as_ref().map(|old| old.location.as_str()),
+                    moved.new.as_ref().map(|new| new.location.as_str()),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            moved,
+            vec![
+                ("Clean Water", Some("MarshSpawn.PoolsPathEX"), None),
+                (
+                    "Double Jump",
+                    Some("MarshSpawn.RockHC"),
+                    Some("MarshSpawn.CaveEX")
+                ),
+                (
+                    "Spirit Light",
+                    Some("MarshSpawn.CaveEX"),
+                    Some("MarshSpawn.RockHC")
+                ),
+            ]
+        );
+        assert!(diff
+            .to_string()
+            .contains("Clean Water: MarshSpawn.PoolsPathEX (Step 1) -> nowhere"));
+
+        let mut new = old();
+        new.groups[0].placements[1].item = Item::SpiritLight(75);
+        assert!(old().diff(&new).is_empty());
+    }
+
+    #[test]
+    fn spawn_changes() {
+        let mut new = old();
+        new.spawns[0] = "GladesTown.Teleporter".to_string();
+        let diff = old().diff(&new);
+        assert_eq!(diff.spawns.len(), 1);
+        assert_eq!(diff.spawns[0].old.as_deref(), Some("MarshSpawn.Main"));
+        assert_eq!(diff.spawns[0].new.as_deref(), Some("GladesTown.Teleporter"));
+        assert!(diff
+            .to_string()
+            .starts_with("Spawn: MarshSpawn.Main -> GladesTown.Teleporter\n"));
+
+        // A world that only exists in one spoiler
+        let mut new = old();
+        new.spawns.push("MarshSpawn.Main".to_string());
+        let diff = old().diff(&new);
+        assert_eq!(diff.world_count, 2);
+        assert_eq!(diff.spawns.len(), 1);
+        assert_eq!(diff.spawns[0].world_index, 1);
+        assert!(diff.spawns[0].old.is_none());
+        assert!(diff
+            .to_string()
+            .contains("Spawn for World [1]: none -> MarshSpawn.Main"));
+    }
+
+    #[test]
+    fn reachable_changes() {
+        let mut new = old();
+        new.groups[0].reachable[0].pop();
+        new.groups[1].reachable[0].push(node("MarshSpawn.CaveEX"));
+        new.groups
+            .push(group(&["MarshSpawn.BurrowOre"], Vec::new()));
+
+        let diff = old().diff(&new);
+        assert_eq!(diff.old_step_count, 2);
+        assert_eq!(diff.new_step_count, 3);
+        let changes = diff
+            .reachables
+            .iter()
+            .map(|change| (change.step, change.added.clone(), change.removed.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            changes,
+            vec![
+                (0, vec![], vec!["MarshSpawn.CaveEX".to_string()]),
+                (1, vec!["MarshSpawn.CaveEX".to_string()], vec![]),
+                (2, vec!["MarshSpawn.BurrowOre".to_string()], vec![]),
+            ]
+        );
+        let text = diff.to_string();
+        assert!(text.contains("Steps: 2 -> 3"));
+        assert!(text.contains("  Step 0: -MarshSpawn.CaveEX"));
+        assert!(text.contains("  Step 2: +MarshSpawn.BurrowOre"));
+    }
+}
@@ -16,7 +16,7 @@ use std::str::FromStr;
 
 use rustc_hash::FxHashMap;
 use serde::{Deserialize, Serialize};
-use wotw_seedgen_derive::VVariant;
+use wotw_seedgen_derive::{Display, FromStr, VVariant};
 
 use crate::header::{parser, CodeDisplay};
 use crate::header::{vdisplay, VResolve, VString};
@@ -97,6 +97,38 @@ vdisplay! {
         }
     }
 }
+/// Broad groups of [`Item`]s, used to configure how whole kinds of items are treated
+#[derive(
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    FromStr,
+    Display,
+)]
+#[ParseFromIdentifier]
+pub enum ItemCategory {
+    SpiritLight,
+    HealthFragment,
+    EnergyFragment,
+    GorlekOre,
+    Keystone,
+    ShardSlot,
+    Skill,
+    Shard,
+    Teleporter,
+    CleanWater,
+    Relic,
+    Bonus,
+    Other,
+}
+
 impl FromStr for Item {
     type Err = String;
     fn from_str(input: &str) -> Result<Self, Self::Err> {
@@ -177,6 +209,26 @@ impl Item {
         }
     }
     #[inline]
+    pub fn category(&self) -> ItemCategory {
+        match self {
+            Item::SpiritLight(_) => ItemCategory::SpiritLight,
+            Item::Resource(resource) => match resource {
+                Resource::HealthFragment => ItemCategory::HealthFragment,
+                Resource::EnergyFragment => ItemCategory::EnergyFragment,
+                Resource::GorlekOre => ItemCategory::GorlekOre,
+                Resource::Keystone => ItemCategory::Keystone,
+                Resource::ShardSlot => ItemCategory::ShardSlot,
+            },
+            Item::Skill(_) => ItemCategory::Skill,
+            Item::Shard(_) => ItemCategory::Shard,
+            Item::Teleporter(_) => ItemCategory::Teleporter,
+            Item::Water => ItemCategory::CleanWater,
+            Item::Relic(_) => ItemCategory::Relic,
+            Item::BonusItem(_) | Item::BonusUpgrade(_) => ItemCategory::Bonus,
+            _ => ItemCategory::Other,
+        }
+    }
+    #[inline]
     pub fn is_multiworld_spread(&self) -> bool {
         // Note that requirement::solutions has logic based on spirit light not being multiworld spread (check_slot_limits)
         !matches!(self, Item::SpiritLight(_))
@@ -6,11 +6,12 @@ use super::{
     states::NamedState,
 };
 
-use crate::item::Skill;
+use crate::item::{Resource, Skill};
 use crate::{
     log,
-    settings::{Difficulty, Trick, UniverseSettings},
-    util::NodeKind,
+    settings::{Difficulty, KeystoneMode, Trick, UniverseSettings},
+    uber_state::UberIdentifier,
+    util::{constants::KEYSTONE_DOORS, NodeKind},
     world::{
         graph::{self, Graph, Node},
         requirement::Requirement,
@@ -22,6 +23,8 @@ struct EmitterContext<'a> {
     universe_settings: &'a UniverseSettings,
     node_map: FxHashMap<String, usize>,
     used_states: FxHashSet<&'a str>,
+    /// The UberState counting the dedicated keys if the connection currently being built leads to a keystone door
+    keystone_door: Option<UberIdentifier>,
 }
 
 fn build_trick_requirement(
@@ -87,6 +90,19 @@ fn build_boss_requirement(health: f32, context: &EmitterContext) -> Requirement
     }
 }
 
+fn build_resource_requirement(
+    resource: Resource,
+    amount: u32,
+    context: &EmitterContext,
+) -> Requirement {
+    match context.keystone_door {
+        Some(key_state) if resource == Resource::Keystone => {
+            Requirement::KeystoneDoor(key_state, amount)
+        }
+        _ => Requirement::Resource(resource, amount),
+    }
+}
+
 fn build_requirement<'a>(
     requirement: &parser::Requirement<'a>,
     region: bool,
@@ -110,7 +126,7 @@ fn build_requirement<'a>(
         }
         parser::RequirementValue::SpiritLight(amount) => Requirement::SpiritLight(*amount),
         parser::RequirementValue::Resource(resource, amount) => {
-            Requirement::Resource((*resource).into(), *amount)
+            build_resource_requirement((*resource).into(), *amount, context)
         }
         parser::RequirementValue::Shard(shard) => Requirement::Shard((*shard).into()),
         parser::RequirementValue::Teleporter(teleporter) => {
@@ -496,6 +512,18 @@ pub fn build(
         index += 1;
     }
     let state_end_index = index;
+    let keystone_door_keys = graph::keystone_door_keys(&nodes);
+    if !universe_settings.all_have_keystone_mode(KeystoneMode::KeysAnywhere) {
+        if let Some((door, _, _)) = KEYSTONE_DOORS
+            .iter()
+            .zip(&keystone_door_keys)
+            .find_map(|(door, key_state)| key_state.is_none().then_some(door))
+        {
+            return Err(format!(
+                "Couldn't find an entry for {door}Keys in the state table, which is needed to give keystone doors dedicated keys"
+            ));
+        }
+    }
     let mut used_states = FxHashSet::default();
     used_states.reserve(state_end_index - state_start_index);
 
@@ -508,6 +536,7 @@ pub fn build(
         universe_settings,
         node_map,
         used_states,
+        keystone_door: None,
     };
     for anchor in anchors {
         let region = regions.get(anchor.region());
@@ -542,6 +571,13 @@ pub fn build(
         let connections = connections
             .into_iter()
             .map(|connection| {
+                context.keystone_door = match connection.kind {
+                    NodeKind::State => KEYSTONE_DOORS
+                        .iter()
+                        .position(|&(door, _, _)| door == connection.identifier)
+                        .and_then(|door_index| keystone_door_keys[door_index]),
+                    _ => None,
+                };
                 let mut requirement =
                     build_requirement_group(&connection.requirements, false, &mut context);
                 if let Some(region_requirement) = &region_requirement {
@@ -567,6 +603,7 @@ pub fn build(
                 Ok(graph::Connection { to, requirement })
             })
             .collect::<Result<Vec<_>, String>>()?;
+        context.keystone_door = None;
 
         let node = Node::Anchor(graph::Anchor {
             identifier,
@@ -607,6 +644,7 @@ mod tests {
             universe_settings: &UniverseSettings::default(),
             node_map: FxHashMap::default(),
             used_states: FxHashSet::default(),
+            keystone_door: None,
         };
 
         let requirement = build_boss_requirement(100.0, &context);
@@ -628,4 +666,34 @@ mod tests {
             _ => panic!(),
         }
     }
+
+    #[test]
+    fn keystone_doors() {
+        let mut context = EmitterContext {
+            macros: &FxHashMap::default(),
+            universe_settings: &UniverseSettings::default(),
+            node_map: FxHashMap::default(),
+            used_states: FxHashSet::default(),
+            keystone_door: Some(UberIdentifier::new(9, 83)),
+        };
+
+        let requirement = build_resource_requirement(Resource::Keystone, 4, &context);
+        assert!(matches!(
+            requirement,
+            Requirement::KeystoneDoor(
+                UberIdentifier {
+                    uber_group: 9,
+                    uber_id: 83
+                },
+                4
+            )
+        ));
+
+        context.keystone_door = None;
+        let requirement = build_resource_requirement(Resource::Keystone, 2, &context);
+        assert!(matches!(
+            requirement,
+            Requirement::Resource(Resource::Keystone, 2)
+        ));
+    }
 }
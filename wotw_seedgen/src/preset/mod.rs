@@ -7,7 +7,13 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     files::FileAccess,
-    settings::{CreateGame, Difficulty, GoalModes, HeaderConfig, InlineHeader, Spawn, Trick},
+    item::{Item, ItemCategory},
+    settings::{
+        Accessibility, CreateGame, Difficulty, FillAlgorithm, GoalModes, HeaderConfig,
+        HintPlacement, InlineHeader, ItemPoolChange, KeystoneMode, LocationSelector,
+        PlacementConstraint, PlacementStrategy, PlandoPlacement, Spawn, SpiritLightDistribution,
+        Trick,
+    },
 };
 
 /// A collection of settings that can be applied to existing settings
@@ -75,6 +81,24 @@ pub struct UniversePreset {
     /// This exists for future compability, but does not have any effect currently
     #[serde(skip_serializing_if = "Option::is_none")]
     pub create_game: Option<CreateGame>,
+    /// How often and for how long the generator may retry if placing items fails
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry_policy: Option<RetryPolicyPreset>,
+    /// How items get distributed between the worlds of a multiworld seed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multiworld: Option<MultiworldPreset>,
+    /// How the generator decides which progression to place
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub placement_strategy: Option<PlacementStrategy>,
+    /// Which algorithm the generator uses to fill the locations with items
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fill_algorithm: Option<FillAlgorithm>,
+    /// Record in the spoiler how each newly reachable location was reached
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spoiler_paths: Option<bool>,
+    /// Which version of the generation algorithm to use
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub algorithm_version: Option<u32>,
 }
 
 impl UniversePreset {
@@ -105,6 +129,84 @@ impl UniversePreset {
     }
 }
 
+/// A collection of retry settings that can be applied to the existing [`RetryPolicy`](crate::settings::RetryPolicy)
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct RetryPolicyPreset {
+    /// How many attempts to generate placements will be made before giving up
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attempts: Option<u16>,
+    /// Whether to choose new spawn locations for every attempt
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repick_spawns: Option<bool>,
+    /// Stop retrying once this many milliseconds have passed since the first attempt started
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_budget_ms: Option<u64>,
+}
+
+/// A collection of multiworld settings that can be applied to the existing [`MultiworldSettings`](crate::settings::MultiworldSettings)
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct MultiworldPreset {
+    /// Item categories that always get placed in their own world
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub local_items: Option<Vec<ItemCategory>>,
+    /// Item categories that get placed in other worlds whenever possible
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shared_items: Option<Vec<ItemCategory>>,
+    /// The desired fraction of progression items that get placed in a different world than the one they belong to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub progression_balance: Option<f32>,
+}
+
+/// A collection of shop price settings that can be applied to the existing [`ShopPriceSettings`](crate::settings::ShopPriceSettings)
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ShopPricePreset {
+    /// The lowest factor a random shop price may be multiplied with
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_multiplier: Option<f32>,
+    /// The highest factor a random shop price may be multiplied with
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_multiplier: Option<f32>,
+    /// Limit the sum of all prices in each individual shop
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_total: Option<u32>,
+    /// Sell items that may be required by logic for free
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub free_progression: Option<bool>,
+}
+
+/// A collection of item pool settings that can be applied to the existing [`ItemPoolSettings`](crate::settings::ItemPoolSettings)
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ItemPoolPreset {
+    /// Items to add to or remove from the item pool
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub changes: Option<Vec<ItemPoolChange>>,
+    /// Replace the total amount of Spirit Light in the item pool
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spirit_light: Option<u32>,
+}
+
+/// A collection of hint settings that can be applied to the existing [`HintSettings`](crate::settings::HintSettings)
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct HintPreset {
+    /// How many zones to hint as holding at least one required item
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub way_of_the_hero: Option<usize>,
+    /// How many zones to hint as holding no required items
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub barren: Option<usize>,
+    /// How many required items to hint the exact location of
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub item_locations: Option<usize>,
+    /// Which locations reveal the hints when collected
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub placement: Option<HintPlacement>,
+}
+
 /// A collection of settings that can be applied to one world of the existing settings
 ///
 /// # Examples
@@ -176,6 +278,36 @@ pub struct WorldPreset {
     /// Inline header syntax
     #[serde(skip_serializing_if = "Option::is_none")]
     pub inline_headers: Option<Vec<InlineHeader>>,
+    /// Items that should be placed on specific locations
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plando: Option<Vec<PlandoPlacement>>,
+    /// Locations that should never hold progression
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub excluded_locations: Option<Vec<LocationSelector>>,
+    /// Restrictions on where and when specific items may be placed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub placement_constraints: Option<Vec<PlacementConstraint>>,
+    /// How the prices in shops are determined
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shop_prices: Option<ShopPricePreset>,
+    /// How the Spirit Light from the item pool is split across its pickups
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spirit_light_distribution: Option<SpiritLightDistribution>,
+    /// Which items open the keystone doors
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keystone_mode: Option<KeystoneMode>,
+    /// Which locations have to be reachable
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accessibility: Option<Accessibility>,
+    /// Changes to the item pool, applied after the changes from headers
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub item_pool: Option<ItemPoolPreset>,
+    /// Items the player receives when spawning
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub starting_items: Option<Vec<Item>>,
+    /// Hints generated from the placements
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hints: Option<HintPreset>,
 }
 
 impl WorldPreset {
@@ -5,9 +5,11 @@
 mod slugstrings;
 
 use std::fmt::{Display, Formatter};
+use std::num::NonZeroUsize;
 use std::ops::{Deref, DerefMut};
 use std::{collections::hash_map::DefaultHasher, error::Error, fmt, hash::Hasher, iter};
 
+use itertools::Itertools;
 use rand::distributions::{Distribution, Uniform};
 use rustc_hash::FxHashSet;
 use serde::de::Visitor;
@@ -15,11 +17,23 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use smallvec::{smallvec, SmallVec};
 use wotw_seedgen_derive::{Display, FromStr};
 
-use crate::item::Skill;
+use crate::item::{
+    Item, ItemCategory, Resource, Skill, UberStateItem, UberStateOperator, UberStateValue,
+};
 use crate::{
     files::FileAccess,
-    preset::{UniversePreset, WorldPreset},
-    util::constants::DEFAULT_SPAWN,
+    preset::{
+        HintPreset, ItemPoolPreset, MultiworldPreset, RetryPolicyPreset, ShopPricePreset,
+        UniversePreset, WorldPreset,
+    },
+    uber_state::{UberIdentifier, UberType},
+    util::{
+        constants::{
+            ALGORITHM_VERSION, DEFAULT_SPAWN, KEYSTONE_DOORS, PRICE_MODIFIER_RANGE, RELIC_ZONES,
+            RETRIES,
+        },
+        Zone,
+    },
 };
 
 use slugstrings::SLUGSTRINGS;
@@ -86,6 +100,36 @@ pub struct UniverseSettings {
     ///
     /// This exists for future compability, but does not have any effect currently
     pub create_game: CreateGame,
+    /// How often and for how long the generator may retry if placing items fails
+    #[serde(default)]
+    pub retry_policy: RetryPolicy,
+    /// How items get distributed between the worlds of a multiworld seed
+    #[serde(default)]
+    pub multiworld: MultiworldSettings,
+    /// How the generator decides which progression to place
+    #[serde(default)]
+    pub placement_strategy: PlacementStrategy,
+    /// Which algorithm the generator uses to fill the locations with items
+    #[serde(default)]
+    pub fill_algorithm: FillAlgorithm,
+    /// Record in the spoiler how each newly reachable location was reached
+    ///
+    /// This lists the anchors leading to the location and the part of the logic requirement that was met, which slows down generation
+    #[serde(default)]
+    pub spoiler_paths: bool,
+    /// Which version of the generation algorithm to use
+    ///
+    /// This pins the random number stream the generator draws from, so a new release of `rand` changing its default rng won't change the generated seeds.
+    /// The `rand` distributions used on that stream, such as shuffling and weighted choices, aren't pinned though,
+    /// so identical seeds are only guaranteed when using the same generator release and logic files
+    ///
+    /// Settings that don't specify a version use the current one
+    #[serde(default = "current_algorithm_version")]
+    pub algorithm_version: u32,
+}
+
+fn current_algorithm_version() -> u32 {
+    ALGORITHM_VERSION
 }
 
 impl UniverseSettings {
@@ -144,6 +188,12 @@ impl UniverseSettings {
             online,
             seed,
             create_game,
+            retry_policy,
+            multiworld,
+            placement_strategy,
+            fill_algorithm,
+            spoiler_paths,
+            algorithm_version,
         } = preset;
 
         if let Some(includes) = includes {
@@ -197,6 +247,24 @@ impl UniverseSettings {
         if let Some(create_game) = create_game {
             self.create_game = create_game;
         }
+        if let Some(retry_policy) = retry_policy {
+            self.retry_policy.apply_preset(retry_policy);
+        }
+        if let Some(multiworld) = multiworld {
+            self.multiworld.apply_preset(multiworld);
+        }
+        if let Some(placement_strategy) = placement_strategy {
+            self.placement_strategy = placement_strategy;
+        }
+        if let Some(fill_algorithm) = fill_algorithm {
+            self.fill_algorithm = fill_algorithm;
+        }
+        if let Some(spoiler_paths) = spoiler_paths {
+            self.spoiler_paths = spoiler_paths;
+        }
+        if let Some(algorithm_version) = algorithm_version {
+            self.algorithm_version = algorithm_version;
+        }
 
         Ok(())
     }
@@ -316,6 +384,13 @@ impl UniverseSettings {
     pub fn all_play_hard(&self) -> bool {
         self.world_settings.iter().all(|world| world.hard)
     }
+
+    /// Checks if all of the [`WorldSettings`]s use the [`KeystoneMode`]
+    pub fn all_have_keystone_mode(&self, keystone_mode: KeystoneMode) -> bool {
+        self.world_settings
+            .iter()
+            .all(|world| world.keystone_mode == keystone_mode)
+    }
 }
 
 impl Default for UniverseSettings {
@@ -326,6 +401,12 @@ impl Default for UniverseSettings {
             disable_logic_filter: false,
             online: false,
             create_game: CreateGame::default(),
+            retry_policy: RetryPolicy::default(),
+            multiworld: MultiworldSettings::default(),
+            placement_strategy: PlacementStrategy::default(),
+            fill_algorithm: FillAlgorithm::default(),
+            spoiler_paths: false,
+            algorithm_version: ALGORITHM_VERSION,
         }
     }
 }
@@ -366,6 +447,61 @@ pub struct WorldSettings {
     pub header_config: Vec<HeaderConfig>,
     /// Fully qualified header syntax
     pub inline_headers: Vec<InlineHeader>,
+    /// Items that should be placed on specific locations
+    ///
+    /// The generator takes these into account logically and will refuse to generate if a pinned item could never be collected.
+    /// Pinned items are taken out of the item pool, so to pin additional copies of an item add them through [`WorldSettings::item_pool`] first
+    #[serde(default)]
+    pub plando: Vec<PlandoPlacement>,
+    /// Locations that should never hold progression
+    ///
+    /// These will only be filled with Spirit Light or items that are never required by logic
+    #[serde(default)]
+    pub excluded_locations: Vec<LocationSelector>,
+    /// Restrictions on where and when specific items may be placed
+    #[serde(default)]
+    pub placement_constraints: Vec<PlacementConstraint>,
+    /// How the prices in shops are determined
+    #[serde(default)]
+    pub shop_prices: ShopPriceSettings,
+    /// How the Spirit Light from the item pool is split across its pickups
+    #[serde(default)]
+    pub spirit_light_distribution: SpiritLightDistribution,
+    /// Which items open the keystone doors
+    #[serde(default)]
+    pub keystone_mode: KeystoneMode,
+    /// Which locations have to be reachable
+    #[serde(default)]
+    pub accessibility: Accessibility,
+    /// Changes to the item pool, applied after the changes from headers
+    #[serde(default)]
+    pub item_pool: ItemPoolSettings,
+    /// Items the player receives when spawning
+    ///
+    /// These are taken out of the item pool and logic considers them available from the start.
+    /// List an item multiple times to receive multiple copies
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use wotw_seedgen::settings::WorldSettings;
+    /// use wotw_seedgen::item::{Item, Skill};
+    /// use wotw_seedgen::preset::WorldPreset;
+    /// use wotw_seedgen::files::NO_FILE_ACCESS;
+    ///
+    /// let mut world_settings = WorldSettings::default();
+    /// assert!(world_settings.starting_items.is_empty());
+    ///
+    /// let preset = WorldPreset::parse("{\"startingItems\":[\"2|0\",\"2|5\"]}").unwrap();
+    /// world_settings.apply_world_preset(preset, &NO_FILE_ACCESS).unwrap();
+    ///
+    /// assert_eq!(world_settings.starting_items, vec![Item::Skill(Skill::Bash), Item::Skill(Skill::DoubleJump)]);
+    /// ```
+    #[serde(default)]
+    pub starting_items: Vec<Item>,
+    /// Hints generated from the placements
+    #[serde(default)]
+    pub hints: HintSettings,
 }
 
 impl WorldSettings {
@@ -446,6 +582,16 @@ impl WorldSettings {
             headers,
             header_config,
             inline_headers,
+            plando,
+            excluded_locations,
+            placement_constraints,
+            shop_prices,
+            spirit_light_distribution,
+            keystone_mode,
+            accessibility,
+            item_pool,
+            starting_items,
+            hints,
         } = preset;
 
         if let Some(includes) = includes {
@@ -480,6 +626,37 @@ impl WorldSettings {
         if let Some(mut inline_headers) = inline_headers {
             self.inline_headers.append(&mut inline_headers);
         }
+        if let Some(mut plando) = plando {
+            self.plando.append(&mut plando);
+        }
+        if let Some(mut excluded_locations) = excluded_locations {
+            self.excluded_locations.append(&mut excluded_locations);
+        }
+        if let Some(mut placement_constraints) = placement_constraints {
+            self.placement_constraints
+                .append(&mut placement_constraints);
+        }
+        if let Some(shop_prices) = shop_prices {
+            self.shop_prices.apply_preset(shop_prices);
+        }
+        if let Some(spirit_light_distribution) = spirit_light_distribution {
+            self.spirit_light_distribution = spirit_light_distribution;
+        }
+        if let Some(keystone_mode) = keystone_mode {
+            self.keystone_mode = keystone_mode;
+        }
+        if let Some(accessibility) = accessibility {
+            self.accessibility = accessibility;
+        }
+        if let Some(item_pool) = item_pool {
+            self.item_pool.apply_preset(item_pool);
+        }
+        if let Some(mut starting_items) = starting_items {
+            self.starting_items.append(&mut starting_items);
+        }
+        if let Some(hints) = hints {
+            self.hints.apply_preset(hints);
+        }
 
         Ok(())
     }
@@ -744,6 +921,8 @@ pub enum Goal {
     /// Each zone of the game will have at most one Relic
     /// There are 11 zones that allow Relics, the specified chance represents how likely each single zone will have a relic
     RelicChance(f64),
+    /// Require collecting Relics that were placed according to the [`RelicRules`] before finishing the game
+    RelicRules(RelicRules),
 }
 impl Display for Goal {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -753,6 +932,7 @@ impl Display for Goal {
             Goal::Quests => "Quests".fmt(f),
             Goal::Relics(count) => write!(f, "{} Relics", count),
             Goal::RelicChance(chance) => write!(f, "{}% Relic chance", chance * 100.),
+            Goal::RelicRules(rules) => rules.fmt(f),
         }
     }
 }
@@ -765,12 +945,15 @@ impl Goal {
             Goal::Wisps => "All Wisps",
             Goal::Trees => "All Trees",
             Goal::Quests => "All Quests",
-            Goal::Relics(_) | Goal::RelicChance(_) => "Relics",
+            Goal::Relics(_) | Goal::RelicChance(_) | Goal::RelicRules(_) => "Relics",
         }
     }
 
     fn is_relic_goal(&self) -> bool {
-        matches!(self, Goal::Relics(_) | Goal::RelicChance(_))
+        matches!(
+            self,
+            Goal::Relics(_) | Goal::RelicChance(_) | Goal::RelicRules(_)
+        )
     }
 }
 
@@ -823,6 +1006,165 @@ impl DerefMut for GoalModes {
     }
 }
 
+/// Where and how many Relics to place for [`Goal::RelicRules`]
+///
+/// The rules are validated when they are created or parsed, so any [`RelicRules`] can be satisfied by at least one amount of Relics
+///
+/// # Examples
+///
+/// ```
+/// # use wotw_seedgen::settings::{Goal, WorldSettings};
+/// use wotw_seedgen::preset::WorldPreset;
+/// use wotw_seedgen::files::NO_FILE_ACCESS;
+/// use wotw_seedgen::util::Zone;
+///
+/// let mut world_settings = WorldSettings::default();
+///
+/// let preset = WorldPreset::parse("{\"goals\":[{\"RelicRules\":{\"zones\":[\"Marsh\",\"Glades\",\"Depths\"],\"min\":1,\"max\":2,\"avoidShopsAndQuests\":true}}]}").unwrap();
+/// world_settings.apply_world_preset(preset, &NO_FILE_ACCESS).unwrap();
+///
+/// let Goal::RelicRules(rules) = &world_settings.goals[0] else { unreachable!() };
+/// assert_eq!(rules.zones(), &[Zone::Marsh, Zone::Glades, Zone::Depths]);
+/// assert!(rules.avoid_shops_and_quests());
+/// assert!(!rules.avoid_late_locations());
+/// assert_eq!(world_settings.goals[0].to_string(), "1-2 Relics in Marsh, Glades, Depths");
+///
+/// // There is only room for one Relic per zone
+/// assert!(WorldPreset::parse("{\"goals\":[{\"RelicRules\":{\"zones\":[\"Marsh\"],\"min\":1,\"max\":2}}]}").is_err());
+/// // Shops can't hold Relics
+/// assert!(WorldPreset::parse("{\"goals\":[{\"RelicRules\":{\"zones\":[\"Shop\"]}}]}").is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", try_from = "RelicRulesInput")]
+pub struct RelicRules {
+    zones: Vec<Zone>,
+    min: usize,
+    max: usize,
+    avoid_shops_and_quests: bool,
+    avoid_late_locations: bool,
+}
+impl RelicRules {
+    /// Create [`RelicRules`] placing between `min` and `max` Relics, each in a different one of the `zones`
+    ///
+    /// Returns an error if the `zones` contain duplicates or zones that can't hold Relics, or if the range can't be satisfied with the given `zones`
+    pub fn new(zones: Vec<Zone>, min: usize, max: usize) -> Result<Self, String> {
+        if zones.is_empty() {
+            return Err("Relic rules need at least one zone".to_string());
+        }
+        for (index, zone) in zones.iter().enumerate() {
+            if !RELIC_ZONES.contains(zone) {
+                return Err(format!("{zone} can't hold Relics"));
+            }
+            if zones[..index].contains(zone) {
+                return Err(format!("Duplicate Relic zone {zone}"));
+            }
+        }
+        if min > max {
+            return Err(format!(
+                "The minimum of {min} Relics is greater than the maximum of {max} Relics"
+            ));
+        }
+        if max > zones.len() {
+            return Err(format!(
+                "Can't place {max} Relics in {} zones with at most one Relic each",
+                zones.len()
+            ));
+        }
+
+        Ok(Self {
+            zones,
+            min,
+            max,
+            avoid_shops_and_quests: false,
+            avoid_late_locations: false,
+        })
+    }
+    /// Don't place Relics in shops or on quest rewards
+    pub fn with_avoid_shops_and_quests(mut self, avoid_shops_and_quests: bool) -> Self {
+        self.avoid_shops_and_quests = avoid_shops_and_quests;
+        self
+    }
+    /// Don't place Relics on locations that are likely to be unreachable until late into the seed
+    ///
+    /// A location counts as late if none of several random halves of the item pool are enough to reach it
+    pub fn with_avoid_late_locations(mut self, avoid_late_locations: bool) -> Self {
+        self.avoid_late_locations = avoid_late_locations;
+        self
+    }
+
+    /// The zones that may hold a Relic
+    pub fn zones(&self) -> &[Zone] {
+        &self.zones
+    }
+    /// The least amount of Relics to place
+    pub fn min(&self) -> usize {
+        self.min
+    }
+    /// The greatest amount of Relics to place
+    pub fn max(&self) -> usize {
+        self.max
+    }
+    /// Whether Relics are kept out of shops and quest rewards
+    pub fn avoid_shops_and_quests(&self) -> bool {
+        self.avoid_shops_and_quests
+    }
+    /// Whether Relics are kept off locations that are likely to be unreachable until late into the seed
+    pub fn avoid_late_locations(&self) -> bool {
+        self.avoid_late_locations
+    }
+}
+impl Display for RelicRules {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.min == self.max {
+            write!(f, "{} Relics", self.max)?;
+        } else {
+            write!(f, "{}-{} Relics", self.min, self.max)?;
+        }
+        if self.zones.len() < RELIC_ZONES.len() {
+            write!(f, " in {}", self.zones.iter().format(", "))?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct RelicRulesInput {
+    #[serde(default = "all_relic_zones")]
+    zones: Vec<Zone>,
+    #[serde(default = "one")]
+    min: usize,
+    max: Option<usize>,
+    #[serde(default)]
+    avoid_shops_and_quests: bool,
+    #[serde(default)]
+    avoid_late_locations: bool,
+}
+fn all_relic_zones() -> Vec<Zone> {
+    RELIC_ZONES.to_vec()
+}
+fn one() -> usize {
+    1
+}
+impl TryFrom<RelicRulesInput> for RelicRules {
+    type Error = String;
+
+    fn try_from(input: RelicRulesInput) -> Result<Self, Self::Error> {
+        let RelicRulesInput {
+            zones,
+            min,
+            max,
+            avoid_shops_and_quests,
+            avoid_late_locations,
+        } = input;
+
+        let max = max.unwrap_or(zones.len());
+        Ok(RelicRules::new(zones, min, max)?
+            .with_avoid_shops_and_quests(avoid_shops_and_quests)
+            .with_avoid_late_locations(avoid_late_locations))
+    }
+}
+
 /// Different types of online games that can be automatically created when generating the seed
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromStr)]
 #[ParseFromIdentifier]
@@ -845,6 +1187,649 @@ impl Default for CreateGame {
     }
 }
 
+/// Controls how the generator retries if placing items fails
+///
+/// Retrying with a different spawn location often helps with settings that make placements hard
+///
+/// # Examples
+///
+/// ```
+/// # use wotw_seedgen::settings::{RetryPolicy, UniverseSettings};
+/// use wotw_seedgen::preset::{RetryPolicyPreset, UniversePreset};
+/// use wotw_seedgen::files::NO_FILE_ACCESS;
+///
+/// let mut universe_settings = UniverseSettings::default();
+/// assert_eq!(universe_settings.retry_policy, RetryPolicy::default());
+///
+/// let preset = UniversePreset::parse("{\"retryPolicy\":{\"attempts\":50,\"timeBudgetMs\":5000}}").unwrap();
+/// universe_settings.apply_preset(preset, &NO_FILE_ACCESS).unwrap();
+///
+/// assert_eq!(universe_settings.retry_policy.attempts, 50);
+/// assert_eq!(universe_settings.retry_policy.time_budget_ms, Some(5000));
+/// assert!(universe_settings.retry_policy.repick_spawns);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct RetryPolicy {
+    /// How many attempts to generate placements will be made before giving up
+    ///
+    /// At least one attempt is always made
+    pub attempts: u16,
+    /// Whether to choose new spawn locations for every attempt
+    ///
+    /// This only has an effect on random spawns
+    pub repick_spawns: bool,
+    /// Stop retrying once this many milliseconds have passed since the first attempt started
+    ///
+    /// Note that seeds generated with a time budget may not be reproducible, since slower machines will make fewer attempts
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_budget_ms: Option<u64>,
+}
+
+impl RetryPolicy {
+    /// Apply the settings from a [`RetryPolicyPreset`]
+    ///
+    /// Any [`None`] values of the preset will be ignored
+    pub fn apply_preset(&mut self, preset: RetryPolicyPreset) {
+        let RetryPolicyPreset {
+            attempts,
+            repick_spawns,
+            time_budget_ms,
+        } = preset;
+
+        if let Some(attempts) = attempts {
+            self.attempts = attempts;
+        }
+        if let Some(repick_spawns) = repick_spawns {
+            self.repick_spawns = repick_spawns;
+        }
+        if let Some(time_budget_ms) = time_budget_ms {
+            self.time_budget_ms = Some(time_budget_ms);
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> RetryPolicy {
+        RetryPolicy {
+            attempts: RETRIES,
+            repick_spawns: true,
+            time_budget_ms: None,
+        }
+    }
+}
+
+/// How the generator decides which progression to place
+///
+/// # Examples
+///
+/// ```
+/// # use wotw_seedgen::settings::{PlacementStrategy, UniverseSettings};
+/// use std::num::NonZeroUsize;
+/// use wotw_seedgen::preset::UniversePreset;
+/// use wotw_seedgen::files::NO_FILE_ACCESS;
+///
+/// let mut universe_settings = UniverseSettings::default();
+/// assert_eq!(universe_settings.placement_strategy, PlacementStrategy::Classic);
+///
+/// let preset = UniversePreset::parse("{\"placementStrategy\":{\"Balanced\":{\"targetStepSize\":12}}}").unwrap();
+/// universe_settings.apply_preset(preset, &NO_FILE_ACCESS).unwrap();
+///
+/// let target_step_size = NonZeroUsize::new(12).unwrap();
+/// assert_eq!(universe_settings.placement_strategy, PlacementStrategy::Balanced { target_step_size });
+/// assert_eq!(universe_settings.placement_strategy.to_string(), "Balanced (12 locations per step)");
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all_fields = "camelCase")]
+pub enum PlacementStrategy {
+    /// Mix random placements with forced progression, preferring cheap progression that unlocks many new locations
+    #[default]
+    Classic,
+    /// Only place progression when it's needed, preferring progression that unlocks close to `target_step_size` new locations
+    ///
+    /// This results in more evenly sized steps of progression
+    Balanced { target_step_size: NonZeroUsize },
+}
+impl Display for PlacementStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PlacementStrategy::Classic => "Classic".fmt(f),
+            PlacementStrategy::Balanced { target_step_size } => {
+                write!(f, "Balanced ({target_step_size} locations per step)")
+            }
+        }
+    }
+}
+
+/// Which algorithm the generator uses to fill the locations with items
+///
+/// # Examples
+///
+/// ```
+/// # use wotw_seedgen::settings::{FillAlgorithm, UniverseSettings};
+/// use wotw_seedgen::preset::UniversePreset;
+/// use wotw_seedgen::files::NO_FILE_ACCESS;
+///
+/// let mut universe_settings = UniverseSettings::default();
+/// assert_eq!(universe_settings.fill_algorithm, FillAlgorithm::Forward);
+///
+/// let preset = UniversePreset::parse("{\"fillAlgorithm\":\"Assumed\"}").unwrap();
+/// universe_settings.apply_preset(preset, &NO_FILE_ACCESS).unwrap();
+///
+/// assert_eq!(universe_settings.fill_algorithm, FillAlgorithm::Assumed);
+/// assert_eq!(universe_settings.fill_algorithm.to_string(), "Assumed Fill");
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum FillAlgorithm {
+    /// Walk forward from the spawn, placing progression whenever the generator gets stuck
+    ///
+    /// The [`PlacementStrategy`] decides which progression gets placed
+    #[default]
+    Forward,
+    /// Place progression items one by one, each on a location that is reachable while assuming all items placed after it are collected
+    ///
+    /// This copes better with heavily constrained settings, but ignores the [`PlacementStrategy`]
+    Assumed,
+}
+impl Display for FillAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FillAlgorithm::Forward => "Forward Fill".fmt(f),
+            FillAlgorithm::Assumed => "Assumed Fill".fmt(f),
+        }
+    }
+}
+
+/// Controls how items get distributed between the worlds of a multiworld seed
+///
+/// These settings have no effect on seeds with only one world
+///
+/// # Examples
+///
+/// ```
+/// # use wotw_seedgen::settings::{MultiworldSettings, UniverseSettings};
+/// use wotw_seedgen::item::ItemCategory;
+/// use wotw_seedgen::preset::UniversePreset;
+/// use wotw_seedgen::files::NO_FILE_ACCESS;
+///
+/// let mut universe_settings = UniverseSettings::default();
+/// assert_eq!(universe_settings.multiworld, MultiworldSettings::default());
+///
+/// let preset = UniversePreset::parse("{\"multiworld\":{\"localItems\":[\"Skill\"],\"sharedItems\":[\"Keystone\"],\"progressionBalance\":0.5}}").unwrap();
+/// universe_settings.apply_preset(preset, &NO_FILE_ACCESS).unwrap();
+///
+/// assert!(universe_settings.multiworld.is_local(ItemCategory::Skill));
+/// assert!(!universe_settings.multiworld.is_shared(ItemCategory::Skill));
+/// assert_eq!(universe_settings.multiworld.shared_items, vec![ItemCategory::Keystone]);
+/// assert_eq!(universe_settings.multiworld.progression_balance, Some(0.5));
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct MultiworldSettings {
+    /// Item categories that always get placed in their own world
+    #[serde(default)]
+    pub local_items: Vec<ItemCategory>,
+    /// Item categories that get placed in other worlds whenever possible
+    #[serde(default)]
+    pub shared_items: Vec<ItemCategory>,
+    /// The desired fraction of progression items that get placed in a different world than the one they belong to
+    ///
+    /// This should be between 0 and 1. If not set, the receiving world is picked at random
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub progression_balance: Option<f32>,
+}
+
+impl MultiworldSettings {
+    /// Apply the settings from a [`MultiworldPreset`]
+    ///
+    /// Any [`None`] values of the preset will be ignored and [`Vec`]s will be appended to the current contents
+    pub fn apply_preset(&mut self, preset: MultiworldPreset) {
+        let MultiworldPreset {
+            local_items,
+            shared_items,
+            progression_balance,
+        } = preset;
+
+        if let Some(mut local_items) = local_items {
+            self.local_items.append(&mut local_items);
+        }
+        if let Some(mut shared_items) = shared_items {
+            self.shared_items.append(&mut shared_items);
+        }
+        if let Some(progression_balance) = progression_balance {
+            self.progression_balance = Some(progression_balance);
+        }
+    }
+
+    /// Whether [`Item`]s of the [`ItemCategory`] have to stay in their own world
+    ///
+    /// See [`Graph::item_category`](crate::world::Graph::item_category) to categorize the dedicated keys of keystone doors
+    pub fn is_local(&self, category: ItemCategory) -> bool {
+        self.local_items.contains(&category)
+    }
+    /// Whether [`Item`]s of the [`ItemCategory`] should be sent to another world
+    pub fn is_shared(&self, category: ItemCategory) -> bool {
+        self.shared_items.contains(&category)
+    }
+}
+
+/// Controls how the prices in shops are determined
+///
+/// Only the shops of Opher, Twillen and Lupo have their prices set by the seed, including the budget of [`ShopPriceSettings::max_total`].
+/// Grom and Tuley aren't affected: they sell upgrades to the Glades rather than randomized items, so they have no locations in the logic and keep their vanilla prices
+///
+/// # Examples
+///
+/// ```
+/// # use wotw_seedgen::settings::{ShopPriceSettings, WorldSettings};
+/// use wotw_seedgen::preset::WorldPreset;
+/// use wotw_seedgen::files::NO_FILE_ACCESS;
+///
+/// let mut world_settings = WorldSettings::default();
+/// assert_eq!(world_settings.shop_prices, ShopPriceSettings::default());
+///
+/// let preset = WorldPreset::parse("{\"shopPrices\":{\"maxMultiplier\":2.0,\"maxTotal\":3000}}").unwrap();
+/// world_settings.apply_world_preset(preset, &NO_FILE_ACCESS).unwrap();
+///
+/// assert_eq!(world_settings.shop_prices.min_multiplier, 0.75);
+/// assert_eq!(world_settings.shop_prices.max_multiplier, 2.0);
+/// assert_eq!(world_settings.shop_prices.max_total, Some(3000));
+/// assert!(!world_settings.shop_prices.free_progression);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+pub struct ShopPriceSettings {
+    /// The lowest factor a random shop price may be multiplied with
+    pub min_multiplier: f32,
+    /// The highest factor a random shop price may be multiplied with
+    pub max_multiplier: f32,
+    /// Limit the sum of all prices in each individual shop
+    ///
+    /// If the prices of a shop add up to more, all of them are scaled down by the same factor
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_total: Option<u32>,
+    /// Sell items that may be required by logic for free
+    pub free_progression: bool,
+}
+
+impl ShopPriceSettings {
+    /// Apply the settings from a [`ShopPricePreset`]
+    ///
+    /// Any [`None`] values of the preset will be ignored
+    pub fn apply_preset(&mut self, preset: ShopPricePreset) {
+        let ShopPricePreset {
+            min_multiplier,
+            max_multiplier,
+            max_total,
+            free_progression,
+        } = preset;
+
+        if let Some(min_multiplier) = min_multiplier {
+            self.min_multiplier = min_multiplier;
+        }
+        if let Some(max_multiplier) = max_multiplier {
+            self.max_multiplier = max_multiplier;
+        }
+        if let Some(max_total) = max_total {
+            self.max_total = Some(max_total);
+        }
+        if let Some(free_progression) = free_progression {
+            self.free_progression = free_progression;
+        }
+    }
+}
+
+impl Default for ShopPriceSettings {
+    fn default() -> ShopPriceSettings {
+        let (min_multiplier, max_multiplier) = PRICE_MODIFIER_RANGE;
+        ShopPriceSettings {
+            min_multiplier,
+            max_multiplier,
+            max_total: None,
+            free_progression: false,
+        }
+    }
+}
+
+/// Changes to the item pool that can be configured without writing a header
+///
+/// These work like the `!!add` and `!!remove` header commands, but get applied after all headers.
+/// The generator will refuse to generate if the changes remove more items than the pool contains, or remove items that logic requires to reach some locations
+///
+/// The items to start with are configured separately through [`WorldSettings::starting_items`].
+/// They are taken out of the item pool after these changes, so a starting item may also be one that the changes added
+///
+/// # Examples
+///
+/// ```
+/// # use wotw_seedgen::settings::{ItemPoolChange, ItemPoolSettings, WorldSettings};
+/// use wotw_seedgen::item::{Item, Skill};
+/// use wotw_seedgen::preset::WorldPreset;
+/// use wotw_seedgen::files::NO_FILE_ACCESS;
+///
+/// let mut world_settings = WorldSettings::default();
+/// assert_eq!(world_settings.item_pool, ItemPoolSettings::default());
+///
+/// let preset = WorldPreset::parse("{\"itemPool\":{\"changes\":[{\"item\":\"2|5\",\"amount\":1}],\"spiritLight\":15000}}").unwrap();
+/// world_settings.apply_world_preset(preset, &NO_FILE_ACCESS).unwrap();
+///
+/// assert_eq!(world_settings.item_pool.changes, vec![ItemPoolChange { item: Item::Skill(Skill::DoubleJump), amount: 1 }]);
+/// assert_eq!(world_settings.item_pool.spirit_light, Some(15000));
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+pub struct ItemPoolSettings {
+    /// Items to add to or remove from the item pool
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub changes: Vec<ItemPoolChange>,
+    /// Replace the total amount of Spirit Light in the item pool
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spirit_light: Option<u32>,
+}
+
+impl ItemPoolSettings {
+    /// Apply the settings from an [`ItemPoolPreset`]
+    ///
+    /// Any [`None`] values of the preset will be ignored
+    pub fn apply_preset(&mut self, preset: ItemPoolPreset) {
+        let ItemPoolPreset {
+            changes,
+            spirit_light,
+        } = preset;
+
+        if let Some(mut changes) = changes {
+            self.changes.append(&mut changes);
+        }
+        if let Some(spirit_light) = spirit_light {
+            self.spirit_light = Some(spirit_light);
+        }
+    }
+}
+
+/// Adds an item to the item pool a number of times, or removes it if the amount is negative
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ItemPoolChange {
+    /// The item to add or remove
+    pub item: Item,
+    /// How many of the item to add, negative amounts remove the item instead
+    pub amount: i32,
+}
+
+impl Display for ItemPoolChange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:+} {}", self.amount, self.item)
+    }
+}
+
+/// Determines how the Spirit Light from the item pool is split into individual pickups
+///
+/// Regardless of the distribution, the amounts placed by the generator add up to exactly the Spirit Light in the item pool,
+/// unless a [`SpiritLightDistribution::Capped`] limit leaves too little space for it
+///
+/// # Examples
+///
+/// ```
+/// # use wotw_seedgen::settings::{SpiritLightDistribution, WorldSettings};
+/// use wotw_seedgen::preset::WorldPreset;
+/// use wotw_seedgen::files::NO_FILE_ACCESS;
+///
+/// let mut world_settings = WorldSettings::default();
+/// assert_eq!(world_settings.spirit_light_distribution, SpiritLightDistribution::Increasing);
+///
+/// let preset = WorldPreset::parse("{\"spiritLightDistribution\":{\"Capped\":250}}").unwrap();
+/// world_settings.apply_world_preset(preset, &NO_FILE_ACCESS).unwrap();
+///
+/// assert_eq!(world_settings.spirit_light_distribution, SpiritLightDistribution::Capped(250));
+/// assert_eq!(world_settings.spirit_light_distribution.to_string(), "Capped at 250");
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SpiritLightDistribution {
+    /// Pickups hold more Spirit Light the later they get reached
+    #[default]
+    Increasing,
+    /// Every pickup holds roughly the same amount of Spirit Light
+    Flat,
+    /// Pickups in zones that are usually reached later hold more Spirit Light
+    ZoneWeighted,
+    /// Like [`SpiritLightDistribution::Flat`], but no single pickup holds more than the specified amount
+    Capped(u32),
+}
+impl Display for SpiritLightDistribution {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SpiritLightDistribution::Increasing => "Increasing".fmt(f),
+            SpiritLightDistribution::Flat => "Flat".fmt(f),
+            SpiritLightDistribution::ZoneWeighted => "Zone weighted".fmt(f),
+            SpiritLightDistribution::Capped(max) => write!(f, "Capped at {}", max),
+        }
+    }
+}
+
+/// Determines which items open the keystone doors
+///
+/// # Examples
+///
+/// ```
+/// # use wotw_seedgen::settings::{KeystoneMode, WorldSettings};
+/// use wotw_seedgen::preset::WorldPreset;
+/// use wotw_seedgen::files::NO_FILE_ACCESS;
+///
+/// let mut world_settings = WorldSettings::default();
+/// assert_eq!(world_settings.keystone_mode, KeystoneMode::KeysAnywhere);
+///
+/// let preset = WorldPreset::parse("{\"keystoneMode\":\"KeyRing\"}").unwrap();
+/// world_settings.apply_world_preset(preset, &NO_FILE_ACCESS).unwrap();
+///
+/// assert_eq!(world_settings.keystone_mode, KeystoneMode::KeyRing);
+/// assert_eq!(world_settings.keystone_mode.to_string(), "Key Ring");
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum KeystoneMode {
+    /// Keystones can be used on any door
+    #[default]
+    KeysAnywhere,
+    /// Every door has its own keys which only open that door
+    Keysanity,
+    /// Every door has a single key ring which opens it right away
+    KeyRing,
+}
+impl Display for KeystoneMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            KeystoneMode::KeysAnywhere => "Keys anywhere".fmt(f),
+            KeystoneMode::Keysanity => "Keysanity".fmt(f),
+            KeystoneMode::KeyRing => "Key Ring".fmt(f),
+        }
+    }
+}
+impl KeystoneMode {
+    /// Returns the [`Item`] needed to open a keystone door and how many of it are needed
+    ///
+    /// `key_state` is the UberState counting the door's dedicated keys, see [`Graph::keystone_door_keys`](crate::world::Graph::keystone_door_keys).
+    /// `keystones` is the amount of keystones the door costs when keystones can be used on any door
+    pub fn door_key(self, key_state: UberIdentifier, keystones: u32) -> (Item, u32) {
+        match self {
+            KeystoneMode::KeysAnywhere => (Item::Resource(Resource::Keystone), keystones),
+            KeystoneMode::Keysanity => (door_key_item(key_state, true, 1), keystones),
+            KeystoneMode::KeyRing => (door_key_item(key_state, false, keystones), 1),
+        }
+    }
+
+    /// Returns the name of the [`Item`] returned by [`KeystoneMode::door_key`] for the door at `door_index` in [`KEYSTONE_DOORS`], if it needs a custom name
+    ///
+    /// # Panics
+    ///
+    /// Panics if `door_index` is out of bounds for [`KEYSTONE_DOORS`]
+    pub fn door_key_name(self, door_index: usize) -> Option<String> {
+        let (_, door_name, _) = KEYSTONE_DOORS[door_index];
+        match self {
+            KeystoneMode::KeysAnywhere => None,
+            KeystoneMode::Keysanity => Some(format!("{door_name} Key")),
+            KeystoneMode::KeyRing => Some(format!("{door_name} Key Ring")),
+        }
+    }
+}
+
+/// Keys count up the door's UberState, once it reaches the door's keystone cost the door opens
+fn door_key_item(key_state: UberIdentifier, signed: bool, amount: u32) -> Item {
+    Item::UberState(UberStateItem {
+        identifier: key_state,
+        uber_type: UberType::Int,
+        signed,
+        sign: signed,
+        operator: UberStateOperator::Value(UberStateValue::Number((amount as f32).into())),
+        skip: false,
+    })
+}
+
+/// Controls which hints the generator writes into the seed
+///
+/// Hints are derived from the generated placements and are always truthful.
+/// An item counts as required if the goals could not be reached without collecting it, Spirit Light is never considered required.
+/// A zone is only hinted barren if the goals can still be reached without collecting anything in it or in any other zone hinted barren.
+/// The goal zone, Willow's End, is never hinted barren.
+/// Each zone and location is hinted at most once
+///
+/// # Examples
+///
+/// ```
+/// # use wotw_seedgen::settings::{HintPlacement, HintSettings, WorldSettings};
+/// use wotw_seedgen::preset::WorldPreset;
+/// use wotw_seedgen::files::NO_FILE_ACCESS;
+///
+/// let mut world_settings = WorldSettings::default();
+/// assert_eq!(world_settings.hints, HintSettings::default());
+///
+/// let preset = WorldPreset::parse("{\"hints\":{\"wayOfTheHero\":3,\"barren\":2,\"placement\":\"Shrines\"}}").unwrap();
+/// world_settings.apply_world_preset(preset, &NO_FILE_ACCESS).unwrap();
+///
+/// assert_eq!(world_settings.hints.way_of_the_hero, 3);
+/// assert_eq!(world_settings.hints.barren, 2);
+/// assert_eq!(world_settings.hints.item_locations, 0);
+/// assert_eq!(world_settings.hints.placement, HintPlacement::Shrines);
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+pub struct HintSettings {
+    /// How many zones to hint as holding at least one required item
+    pub way_of_the_hero: usize,
+    /// How many zones to hint as not needed to reach the goals
+    pub barren: usize,
+    /// How many required items to hint the exact location of
+    pub item_locations: usize,
+    /// Which locations reveal the hints when collected
+    pub placement: HintPlacement,
+}
+
+impl HintSettings {
+    /// Apply the settings from a [`HintPreset`]
+    ///
+    /// Any [`None`] values of the preset will be ignored
+    pub fn apply_preset(&mut self, preset: HintPreset) {
+        let HintPreset {
+            way_of_the_hero,
+            barren,
+            item_locations,
+            placement,
+        } = preset;
+
+        if let Some(way_of_the_hero) = way_of_the_hero {
+            self.way_of_the_hero = way_of_the_hero;
+        }
+        if let Some(barren) = barren {
+            self.barren = barren;
+        }
+        if let Some(item_locations) = item_locations {
+            self.item_locations = item_locations;
+        }
+        if let Some(placement) = placement {
+            self.placement = placement;
+        }
+    }
+
+    /// Checks whether any hints should be generated
+    pub fn is_enabled(&self) -> bool {
+        self.way_of_the_hero > 0 || self.barren > 0 || self.item_locations > 0
+    }
+}
+
+/// The locations that reveal hints when collected
+///
+/// If there are more hints than locations, some locations will reveal multiple hints
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HintPlacement {
+    /// The maps sold by Lupo in each zone
+    #[default]
+    LupoMaps,
+    /// The Combat Shrines
+    Shrines,
+}
+impl HintPlacement {
+    /// Checks whether the location with this identifier reveals hints
+    pub fn matches(self, identifier: &str) -> bool {
+        match self {
+            HintPlacement::LupoMaps => identifier.ends_with(".LupoMap"),
+            HintPlacement::Shrines => identifier.ends_with(".CombatShrine"),
+        }
+    }
+}
+impl Display for HintPlacement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HintPlacement::LupoMaps => "Lupo Maps".fmt(f),
+            HintPlacement::Shrines => "Combat Shrines".fmt(f),
+        }
+    }
+}
+
+/// Determines which locations the generator guarantees to be reachable
+///
+/// # Examples
+///
+/// ```
+/// # use wotw_seedgen::settings::{Accessibility, WorldSettings};
+/// use wotw_seedgen::preset::WorldPreset;
+/// use wotw_seedgen::files::NO_FILE_ACCESS;
+///
+/// let mut world_settings = WorldSettings::default();
+/// assert_eq!(world_settings.accessibility, Accessibility::Locations);
+///
+/// let preset = WorldPreset::parse("{\"accessibility\":\"Beatable\"}").unwrap();
+/// world_settings.apply_world_preset(preset, &NO_FILE_ACCESS).unwrap();
+///
+/// assert_eq!(world_settings.accessibility, Accessibility::Beatable);
+/// assert_eq!(world_settings.accessibility.to_string(), "Beatable only");
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Accessibility {
+    /// Every location that can be reached on these settings will be reachable
+    ///
+    /// Locations that can never be reached on these settings will only hold Spirit Light
+    #[default]
+    Locations,
+    /// Every location will be reachable
+    ///
+    /// The generator will refuse to generate if the item pool lacks something needed to reach a location.
+    /// Locations that the logic can't reach on these settings even with the full default item pool are exempt, such as the two pickups that are out of reach on Moki
+    Full,
+    /// Only the goal will be reachable
+    ///
+    /// The generator stops placing progression once the final area and all locations required by the [`Goal`]s are reachable, any other location may hold anything.
+    /// Since the final escape isn't part of the logic, reaching the goal is approximated as reaching every pickup in Willow's End,
+    /// along with every Wisp, Tree, Quest or Relic location if a [`Goal`] asks for them
+    Beatable,
+}
+impl Display for Accessibility {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Accessibility::Locations => "Reachable locations".fmt(f),
+            Accessibility::Full => "All locations".fmt(f),
+            Accessibility::Beatable => "Beatable only".fmt(f),
+        }
+    }
+}
+
 /// Configuration parameter for a header
 #[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -866,6 +1851,137 @@ pub struct InlineHeader {
     pub content: String,
 }
 
+/// An item pinned to a specific location
+///
+/// # Examples
+///
+/// ```
+/// # use wotw_seedgen::settings::PlandoPlacement;
+/// use wotw_seedgen::item::{Item, Skill};
+///
+/// let json = "{\"location\":\"MarshSpawn.RockHC\",\"item\":\"2|5\"}";
+/// let placement: PlandoPlacement = serde_json::from_str(json).unwrap();
+///
+/// assert_eq!(placement.location, "MarshSpawn.RockHC");
+/// assert_eq!(placement.item, Item::Skill(Skill::DoubleJump));
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct PlandoPlacement {
+    /// The identifier of the location as used in the loc_data file, e.g. "MarshSpawn.RockHC"
+    pub location: String,
+    /// The item to place there
+    pub item: Item,
+}
+
+/// Selects either a single location or all locations in a [`Zone`]
+///
+/// # Examples
+///
+/// ```
+/// # use wotw_seedgen::settings::LocationSelector;
+/// use wotw_seedgen::util::Zone;
+///
+/// let selectors: Vec<LocationSelector> = serde_json::from_str("[{\"location\":\"WillowsEnd.UpperLeftEX\"},{\"zone\":\"Shop\"}]").unwrap();
+///
+/// assert!(selectors[0].matches("WillowsEnd.UpperLeftEX", Some(Zone::Willow)));
+/// assert!(!selectors[0].matches("WillowsEnd.UpperRightEX", Some(Zone::Willow)));
+/// assert!(selectors[1].matches("TwillenShop.Overcharge", Some(Zone::Shop)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LocationSelector {
+    /// The location with this identifier as used in the loc_data file
+    Location(String),
+    /// All locations in this [`Zone`]
+    Zone(Zone),
+}
+
+impl LocationSelector {
+    /// Checks if a location with the given identifier and [`Zone`] is selected
+    pub fn matches(&self, identifier: &str, zone: Option<Zone>) -> bool {
+        match self {
+            LocationSelector::Location(location) => location == identifier,
+            LocationSelector::Zone(selected_zone) => zone == Some(*selected_zone),
+        }
+    }
+}
+
+impl Display for LocationSelector {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            LocationSelector::Location(location) => write!(f, "{location}"),
+            LocationSelector::Zone(zone) => write!(f, "{zone}"),
+        }
+    }
+}
+
+/// Restricts where and when every copy of an item may be placed
+///
+/// Steps refer to the placement groups as they are numbered in the spoiler, step 0 only holds priority placements
+///
+/// # Examples
+///
+/// ```
+/// # use wotw_seedgen::settings::PlacementConstraint;
+/// use wotw_seedgen::item::{Item, Skill};
+/// use wotw_seedgen::util::Zone;
+///
+/// let json = "{\"item\":\"2|57\",\"zones\":[\"Wellspring\"],\"maxStep\":5}";
+/// let constraint: PlacementConstraint = serde_json::from_str(json).unwrap();
+///
+/// assert_eq!(constraint.item, Item::Skill(Skill::Grapple));
+/// assert!(constraint.allows(Some(Zone::Wellspring), 3));
+/// assert!(!constraint.allows(Some(Zone::Wellspring), 6));
+/// assert!(!constraint.allows(Some(Zone::Pools), 3));
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct PlacementConstraint {
+    /// The constrained item
+    pub item: Item,
+    /// If not empty, the item may only be placed in these zones
+    #[serde(default)]
+    pub zones: Vec<Zone>,
+    /// If set, the item has to be placed in this step or earlier
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_step: Option<usize>,
+}
+
+impl PlacementConstraint {
+    /// Checks if the constrained item may be placed on a location in the given [`Zone`] during the given step
+    pub fn allows(&self, zone: Option<Zone>, step: usize) -> bool {
+        let zone_allowed =
+            self.zones.is_empty() || zone.is_some_and(|zone| self.zones.contains(&zone));
+        let step_allowed = match self.max_step {
+            Some(max_step) => step <= max_step,
+            None => true,
+        };
+        zone_allowed && step_allowed
+    }
+}
+
+impl Display for PlacementConstraint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.item)?;
+        if !self.zones.is_empty() {
+            write!(
+                f,
+                " in {}",
+                self.zones
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(" or ")
+            )?;
+        }
+        if let Some(max_step) = self.max_step {
+            write!(f, " by step {max_step}")?;
+        }
+        Ok(())
+    }
+}
+
 pub fn world_index_from_seed(seed: &str) -> Option<Result<usize, String>> {
     seed.lines()
         .find_map(|line| line.strip_prefix("// This World: "))
@@ -915,4 +2031,28 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn missing_algorithm_version() {
+        let mut json = serde_json::to_value(UniverseSettings::default()).unwrap();
+        json.as_object_mut()
+            .unwrap()
+            .remove("algorithmVersion")
+            .unwrap();
+        let universe_settings = serde_json::from_value::<UniverseSettings>(json).unwrap();
+        assert_eq!(universe_settings.algorithm_version, ALGORITHM_VERSION);
+    }
+
+    #[test]
+    fn relic_rules_validation() {
+        assert!(RelicRules::new(vec![Zone::Marsh, Zone::Glades], 1, 2).is_ok());
+        assert!(RelicRules::new(vec![Zone::Marsh, Zone::Glades], 0, 0).is_ok());
+
+        assert!(RelicRules::new(Vec::new(), 0, 0).is_err());
+        assert!(RelicRules::new(vec![Zone::Shop], 1, 1).is_err());
+        assert!(RelicRules::new(vec![Zone::Spawn], 1, 1).is_err());
+        assert!(RelicRules::new(vec![Zone::Marsh, Zone::Marsh], 1, 1).is_err());
+        assert!(RelicRules::new(vec![Zone::Marsh, Zone::Glades], 2, 1).is_err());
+        assert!(RelicRules::new(vec![Zone::Marsh, Zone::Glades], 1, 3).is_err());
+    }
 }
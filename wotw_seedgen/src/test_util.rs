@@ -0,0 +1,28 @@
+use crate::{
+    files::{self, FILE_SYSTEM_ACCESS},
+    generate_seed,
+    generator::Seed,
+    logic,
+    settings::UniverseSettings,
+    world::Graph,
+};
+
+/// Parses the logic files with the given settings
+pub(crate) fn test_graph(universe_settings: &UniverseSettings) -> Graph {
+    let areas = files::read_file("areas", "wotw", "logic").unwrap();
+    let locations = files::read_file("loc_data", "csv", "logic").unwrap();
+    let states = files::read_file("state_data", "csv", "logic").unwrap();
+    logic::parse_logic(&areas, &locations, &states, universe_settings, false).unwrap()
+}
+
+/// Parses the logic files and generates a seed with the given settings
+///
+/// The settings and graph are leaked, so the seed borrowing them can be returned
+pub(crate) fn test_seed(
+    universe_settings: UniverseSettings,
+) -> (&'static Graph, Seed<'static, 'static>) {
+    let universe_settings = Box::leak(Box::new(universe_settings));
+    let graph = Box::leak(Box::new(test_graph(universe_settings)));
+    let seed = generate_seed(graph, &FILE_SYSTEM_ACCESS, universe_settings).unwrap();
+    (graph, seed)
+}
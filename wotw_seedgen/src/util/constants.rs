@@ -23,19 +23,19 @@ pub const RELIC_ZONES: &[Zone] = &[
     Zone::Willow,
     Zone::Burrows,
 ];
-pub const KEYSTONE_DOORS: &[(&str, u32)] = &[
-    ("MarshSpawn.KeystoneDoor", 2),
-    ("HowlsDen.KeystoneDoor", 2),
-    ("MarshPastOpher.EyestoneDoor", 2),
-    ("MidnightBurrows.KeystoneDoor", 4),
-    ("WoodsEntry.KeystoneDoor", 2),
-    ("WoodsMain.KeystoneDoor", 4),
-    ("LowerReach.KeystoneDoor", 4),
-    ("UpperReach.KeystoneDoor", 4),
-    ("UpperDepths.EntryKeystoneDoor", 2),
-    ("UpperDepths.CentralKeystoneDoor", 2),
-    ("UpperPools.KeystoneDoor", 4),
-    ("UpperWastes.KeystoneDoor", 2),
+pub const KEYSTONE_DOORS: &[(&str, &str, u32)] = &[
+    ("MarshSpawn.KeystoneDoor", "Marsh Spawn", 2),
+    ("HowlsDen.KeystoneDoor", "Howl's Den", 2),
+    ("MarshPastOpher.EyestoneDoor", "Eyestone", 2),
+    ("MidnightBurrows.KeystoneDoor", "Midnight Burrows", 4),
+    ("WoodsEntry.KeystoneDoor", "Woods Entry", 2),
+    ("WoodsMain.KeystoneDoor", "Silent Woods", 4),
+    ("LowerReach.KeystoneDoor", "Lower Reach", 4),
+    ("UpperReach.KeystoneDoor", "Upper Reach", 4),
+    ("UpperDepths.EntryKeystoneDoor", "Depths Entry", 2),
+    ("UpperDepths.CentralKeystoneDoor", "Central Depths", 2),
+    ("UpperPools.KeystoneDoor", "Luma Pools", 4),
+    ("UpperWastes.KeystoneDoor", "Windswept Wastes", 2),
 ];
 
 pub const WISP_STATES: &[UberIdentifier] = &[
@@ -46,13 +46,22 @@ pub const WISP_STATES: &[UberIdentifier] = &[
     UberIdentifier::new(10289, 22102),
 ];
 
+/// The uber group holding the states of the ability trees
+pub const TREE_UBER_GROUP: u16 = 0;
+/// The uber group multiworld items are sent in, which the multiworld server syncs between all worlds
+pub const MULTIWORLD_UBER_GROUP: u16 = 12;
+
 pub const SPAWN_SLOTS: usize = 7;
 pub const PREFERRED_SPAWN_SLOTS: usize = 3;
 const _: usize = SPAWN_SLOTS - PREFERRED_SPAWN_SLOTS; // check that SPAWN_SLOTS >= PREFERRED_SPAWN_SLOTS
 pub const RESERVE_SLOTS: usize = 1; // how many slots to reserve after random placements for the next iteration
 pub const PLACEHOLDER_SLOTS: usize = 25; // how many slots to keep as placeholders for bigger progressions
 pub const RETRIES: u16 = 10; // How many retries to allow when generating a seed
+pub const ALGORITHM_VERSION: u32 = 1; // The current generation algorithm, increment whenever the same settings would start generating different seeds
 pub const RANDOM_PROGRESSION: f64 = 0.4; // How likely to choose a progression item as random placement
+pub const BALANCED_DEVIATION_EXPONENT: i32 = 4; // How strongly the balanced placement strategy avoids progression that deviates from the target step size
+pub const EARLY_REACH_SAMPLES: usize = 8; // How many random halves of the item pool to try when estimating which locations are reachable early
+pub const PRICE_MODIFIER_RANGE: (f32, f32) = (0.75, 1.25); // How far random shop prices may deviate from the base price by default
 
 pub const HEADER_INDENT: usize = 24; // Which column to align header descriptions on
 pub const NAME_COLOUR: Colour = Colour::Yellow;
@@ -0,0 +1,356 @@
+use std::slice;
+
+use rustc_hash::FxHashSet;
+
+use crate::generator::is_goal_location;
+use crate::item::Item;
+use crate::settings::{Goal, UniverseSettings, WorldSettings};
+use crate::uber_state::{UberIdentifier, UberStateTrigger};
+use crate::util::{self, constants::MULTIWORLD_UBER_GROUP, Zone};
+use crate::world::graph::{Graph, Node};
+use crate::world::{Pool, World};
+use crate::{log, settings};
+
+/// Outcome of [`verify_seed`]
+#[derive(Debug, Clone)]
+pub struct SeedVerification<'graph> {
+    /// The results for each world, in order of their world index
+    pub worlds: Vec<WorldVerification<'graph>>,
+}
+/// Outcome of [`verify_seed`] for one world
+#[derive(Debug, Clone)]
+pub struct WorldVerification<'graph> {
+    /// Locations that can't be reached by collecting the seed's pickups, but would be reachable with the entire item pool
+    pub unreachable_locations: Vec<&'graph Node>,
+    /// Locations that can't be reached even with the entire item pool
+    ///
+    /// These are expected unless the seed was generated with [`Accessibility::Full`](crate::settings::Accessibility::Full)
+    pub out_of_logic_locations: Vec<&'graph Node>,
+    /// Requirements for finishing the game that can't be met by collecting the seed's pickups
+    pub unmet_goals: Vec<UnmetGoal<'graph>>,
+}
+/// A requirement for finishing the game that can't be met
+#[derive(Debug, Clone)]
+pub struct UnmetGoal<'graph> {
+    /// The [`Goal`] that can't be met, or [`None`] if the final area itself can't be reached
+    pub goal: Option<Goal>,
+    /// The locations belonging to the goal that couldn't be reached
+    pub missing_locations: Vec<&'graph Node>,
+}
+
+impl SeedVerification<'_> {
+    /// Whether every world can reach the end of the game and meet all its [`Goal`]s
+    pub fn is_beatable(&self) -> bool {
+        self.worlds.iter().all(|world| world.unmet_goals.is_empty())
+    }
+}
+
+/// Checks whether a seed can be beaten and which of its locations are reachable
+///
+/// `seed_files` should be seed files generated by [`generate_seed`](crate::generator::generate_seed), one for each world of the seed.
+/// Their pickups are read back into items and collected the way a player would, so hand-edited seeds can be verified as well.
+/// Pickups sent between the worlds of a multiworld seed are synced like the multiworld server would.
+///
+/// `graph` has to be parsed with the seed's [`UniverseSettings`]
+pub fn verify_seed<'graph>(
+    graph: &'graph Graph,
+    seed_files: &[&str],
+) -> Result<SeedVerification<'graph>, String> {
+    let first = seed_files.first().ok_or("No seed files provided")?;
+    let universe_settings = UniverseSettings::from_seed(first)
+        .unwrap_or_else(|| Err("Failed to read settings from seed".into()))?;
+    let world_count = universe_settings.world_count();
+    if seed_files.len() != world_count {
+        return Err(format!(
+            "The seed has {} worlds, but {} seed files were provided",
+            world_count,
+            seed_files.len()
+        ));
+    }
+
+    let mut ordered_seed_files = vec![None; world_count];
+    for seed_file in seed_files {
+        let world_index = if world_count == 1 {
+            0
+        } else {
+            settings::world_index_from_seed(seed_file)
+                .unwrap_or_else(|| Err("Failed to read world index from seed".into()))?
+        };
+        let slot = ordered_seed_files
+            .get_mut(world_index)
+            .ok_or_else(|| format!("World index {world_index} out of bounds"))?;
+        if slot.replace(*seed_file).is_some() {
+            return Err(format!(
+                "Multiple seed files provided for World {world_index}"
+            ));
+        }
+    }
+    // All slots are filled since there are as many seed files as worlds and none of them share an index
+    let seed_files = ordered_seed_files.into_iter().flatten().collect::<Vec<_>>();
+
+    let world_settings = seed_files
+        .iter()
+        .map(|seed_file| {
+            WorldSettings::from_seed(seed_file, universe_settings.clone())
+                .unwrap_or_else(|| Err("Failed to read world index from seed".into()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut worlds = Vec::with_capacity(world_count);
+    let mut spawns = Vec::with_capacity(world_count);
+    let mut goal_locations = Vec::with_capacity(world_count);
+    for (seed_file, world_settings) in seed_files.iter().zip(&world_settings) {
+        let mut world = World::new_spawn(graph, world_settings);
+        let mut relic_locations = Vec::new();
+        for (trigger, item) in parse_pickups(seed_file) {
+            if matches!(item, Item::Relic(_)) {
+                relic_locations.extend(
+                    graph
+                        .nodes
+                        .iter()
+                        .filter(|node| node.can_place() && node.trigger() == Some(&trigger)),
+                );
+            }
+            world.preplace(trigger, item);
+        }
+        apply_sets(&mut world, seed_file);
+
+        let spawn = graph.find_spawn(&util::spawn_from_seed(seed_file)?)?;
+        let goals = goals_from_seed(seed_file, relic_locations.len());
+        goal_locations.push((goals, relic_locations));
+        worlds.push(world);
+        spawns.push(spawn);
+    }
+
+    let mut finished_worlds = worlds.clone();
+    for finished_world in &mut finished_worlds {
+        let pool = Pool::preset(finished_world.player.settings, graph);
+        for (item, amount) in pool.inventory.items {
+            if item.is_progression(finished_world.player.settings.difficulty) {
+                finished_world.grant_player(item, amount);
+            }
+        }
+        finished_world.grant_player(Item::SpiritLight(1), pool.spirit_light);
+    }
+
+    let reached = collect_all(&mut worlds, &spawns);
+    let finished_reached = collect_all(&mut finished_worlds, &spawns);
+
+    let worlds = reached
+        .into_iter()
+        .zip(finished_reached)
+        .zip(goal_locations)
+        .map(|((reached, finished_reached), (goals, relic_locations))| {
+            let (unreachable_locations, out_of_logic_locations) = graph
+                .nodes
+                .iter()
+                .filter(|node| node.can_place() && !reached.contains(&node.index()))
+                .partition::<Vec<_>, _>(|node| finished_reached.contains(&node.index()));
+
+            let missing_locations = |goal: Option<&Goal>| {
+                graph
+                    .nodes
+                    .iter()
+                    .filter(|node| !reached.contains(&node.index()))
+                    .filter(|node| match goal {
+                        None => node.zone() == Some(Zone::Willow),
+                        Some(goal) => {
+                            node.zone() != Some(Zone::Willow)
+                                && is_goal_location(node, slice::from_ref(goal), &relic_locations)
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            };
+            let unmet_goals = std::iter::once(None)
+                .chain(goals.into_iter().map(Some))
+                .filter_map(|goal| {
+                    let missing_locations = missing_locations(goal.as_ref());
+                    if missing_locations.is_empty() {
+                        None
+                    } else {
+                        Some(UnmetGoal {
+                            goal,
+                            missing_locations,
+                        })
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            WorldVerification {
+                unreachable_locations,
+                out_of_logic_locations,
+                unmet_goals,
+            }
+        })
+        .collect();
+
+    Ok(SeedVerification { worlds })
+}
+
+/// Reads all lines of the seed file that grant an item on an UberState trigger
+///
+/// Lines that aren't pickups, such as header commands, are skipped
+fn parse_pickups(seed_file: &str) -> Vec<(UberStateTrigger, Item)> {
+    seed_file
+        .lines()
+        .filter_map(|line| {
+            let line = line.split("//").next().unwrap_or_default().trim();
+            let mut parts = line.splitn(3, '|');
+            let trigger = format!("{}|{}", parts.next()?, parts.next()?);
+            let item = parts.next()?;
+            let item = item.strip_suffix("|mute").unwrap_or(item);
+
+            let trigger = trigger.parse().ok()?;
+            let item = item.parse().ok()?;
+            Some((trigger, item))
+        })
+        .collect()
+}
+
+fn apply_sets(world: &mut World, seed_file: &str) {
+    let sets = seed_file
+        .lines()
+        .find_map(|line| line.strip_prefix("// Sets: "))
+        .unwrap_or_default();
+    for identifier in sets.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+        match world
+            .graph
+            .nodes
+            .iter()
+            .find(|node| node.identifier() == identifier)
+        {
+            Some(node) => world.sets.push(node.index()),
+            None => log::warning!("State {} not found", identifier),
+        }
+    }
+}
+
+/// Reads the [`Goal`]s back from the seed's flags
+///
+/// The flags are compared against [`Goal::flag_name`], which wrote them.
+/// The exact relic goal can't be recovered, but the placed Relics are all that matter to reach it
+fn goals_from_seed(seed_file: &str, relic_count: usize) -> Vec<Goal> {
+    let flags = seed_file
+        .lines()
+        .find_map(|line| line.strip_prefix("Flags: "))
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .collect::<Vec<_>>();
+    [
+        Goal::Wisps,
+        Goal::Trees,
+        Goal::Quests,
+        Goal::Relics(relic_count),
+    ]
+    .into_iter()
+    .filter(|goal| flags.contains(&goal.flag_name()))
+    .collect()
+}
+
+/// Collects everything in logic for all worlds until no new locations become reachable
+///
+/// Returns the indices of the reached nodes for each world
+fn collect_all(worlds: &mut [World], spawns: &[&Node]) -> Vec<FxHashSet<usize>> {
+    let mut reached_nodes = vec![FxHashSet::default(); worlds.len()];
+    for world in worlds.iter_mut() {
+        world.set_uber_state(UberIdentifier::spawn(), 1.);
+    }
+
+    loop {
+        let mut progress = false;
+
+        for ((world, spawn), world_reached) in worlds.iter_mut().zip(spawns).zip(&mut reached_nodes)
+        {
+            let mut reached = world.graph.reached_locations(
+                &world.player,
+                spawn,
+                world.uber_states(),
+                &world.sets,
+            );
+            reached.retain(|node| node.trigger().is_some() && world_reached.insert(node.index()));
+            // Quest steps that unlock at the same time have to be set in order
+            reached
+                .sort_unstable_by_key(|node| node.trigger().map_or(0, UberStateTrigger::set_value));
+
+            for node in reached {
+                progress = true;
+                let trigger = node.trigger().unwrap();
+                world.set_incremental_uber_state(trigger.identifier, trigger.set_value() as f32);
+            }
+        }
+
+        if worlds.len() > 1 {
+            let sent = worlds
+                .iter()
+                .flat_map(|world| world.uber_states())
+                .filter(|(identifier, _)| identifier.uber_group == MULTIWORLD_UBER_GROUP)
+                .map(|(identifier, value)| (*identifier, *value))
+                .collect::<Vec<_>>();
+            for world in worlds.iter_mut() {
+                for &(identifier, value) in &sent {
+                    if world.get_uber_state(identifier) < value {
+                        progress = true;
+                        world.set_uber_state(identifier, value);
+                    }
+                }
+            }
+        }
+
+        if !progress {
+            break;
+        }
+    }
+
+    reached_nodes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::test_seed;
+
+    #[test]
+    fn verify() {
+        let universe_settings = UniverseSettings {
+            seed: "verify".to_string(),
+            ..UniverseSettings::default()
+        };
+        let (graph, seed) = test_seed(universe_settings);
+        let seed_files = seed.seed_files().unwrap();
+        let seed_file = seed_files[0].as_str();
+
+        let verification = verify_seed(graph, &[seed_file]).unwrap();
+        assert!(verification.is_beatable());
+        assert!(verification.worlds[0].unreachable_locations.is_empty());
+
+        assert!(verify_seed(graph, &[]).is_err());
+        assert!(verify_seed(graph, &[seed_file, seed_file]).is_err());
+
+        // Without any skills the seed can't be beaten anymore
+        let broken = seed_file
+            .lines()
+            .filter(|line| {
+                !parse_pickups(line)
+                    .iter()
+                    .any(|(_, item)| matches!(item, Item::Skill(_)))
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_ne!(broken.lines().count(), seed_file.lines().count());
+        let verification = verify_seed(graph, &[&broken]).unwrap();
+        assert!(!verification.is_beatable());
+        let world = &verification.worlds[0];
+        assert!(!world.unmet_goals.is_empty());
+        assert!(!world.unreachable_locations.is_empty());
+    }
+
+    #[test]
+    fn goals_from_flags() {
+        let goals = [Goal::Wisps, Goal::Trees, Goal::Quests, Goal::Relics(3)];
+        let flags = goals.iter().map(Goal::flag_name).collect::<Vec<_>>();
+        let seed_file = format!("Flags: {}, No Hints\n", flags.join(", "));
+        assert_eq!(goals_from_seed(&seed_file, 3), goals);
+        assert!(goals_from_seed("Flags: No Hints\n", 0).is_empty());
+    }
+}
@@ -4,10 +4,11 @@ use rustc_hash::{FxHashMap, FxHashSet};
 use smallvec::smallvec;
 
 use super::{player::Player, requirement::Requirement};
-use crate::generator::NodeSummary;
+use crate::generator::{LogicPath, NodeSummary};
+use crate::item::{Item, ItemCategory};
 use crate::uber_state::{UberIdentifier, UberStateTrigger};
 use crate::util::{
-    constants::TP_ANCHOR,
+    constants::{KEYSTONE_DOORS, TP_ANCHOR},
     orbs::{self, OrbVariants},
     NodeKind, Position, RefillValue, Zone,
 };
@@ -158,6 +159,8 @@ struct ReachContext<'a, 'b, 'c> {
     world_state: FxHashMap<usize, OrbVariants>,
     reached: Vec<&'a Node>,
     progressions: Vec<(&'a Requirement, OrbVariants)>,
+    /// If recording paths, the node each reached node was reached from and the met part of the connecting requirement
+    parents: Option<FxHashMap<usize, (usize, Requirement)>>,
 }
 impl<'b, 'c> ReachContext<'_, 'b, 'c> {
     fn new(player: &'b Player<'c>, progression_check: bool, states: FxHashSet<usize>) -> Self {
@@ -169,14 +172,72 @@ impl<'b, 'c> ReachContext<'_, 'b, 'c> {
             world_state: Default::default(),
             reached: Default::default(),
             progressions: Default::default(),
+            parents: None,
+        }
+    }
+
+    fn record_parent(&mut self, from: usize, connection: &Connection, orb_variants: OrbVariants) {
+        if let Some(parents) = &mut self.parents {
+            let branch = connection
+                .requirement
+                .met_branch(self.player, &self.states, orb_variants);
+            parents.insert(connection.to, (from, branch));
         }
     }
 }
 
+/// How the nodes found by [`Graph::reach_paths`] were reached
+#[derive(Debug)]
+pub struct ReachPaths<'a> {
+    graph: &'a Graph,
+    parents: FxHashMap<usize, (usize, Requirement)>,
+}
+impl ReachPaths<'_> {
+    /// Returns the chain of anchors leading to `node` and the requirement that was met to reach it from the last anchor
+    ///
+    /// Returns [`None`] if `node` wasn't reached through a connection, for instance because it is where the search started
+    pub fn path(&self, node: &Node) -> Option<LogicPath> {
+        let (mut from, requirement) = self.parents.get(&node.index())?;
+        let requirement = requirement.describe(&self.graph.nodes);
+
+        let mut anchors = vec![self.graph.nodes[from].identifier().to_string()];
+        while let Some((parent, _)) = self.parents.get(&from) {
+            from = *parent;
+            anchors.push(self.graph.nodes[from].identifier().to_string());
+        }
+        anchors.reverse();
+
+        Some(LogicPath {
+            location: node.identifier().to_string(),
+            anchors,
+            requirement,
+        })
+    }
+}
+
+/// Finds the UberStates counting the dedicated keys of each door in [`KEYSTONE_DOORS`]
+///
+/// They are read from the state table, where they are named after their door with a `Keys` suffix
+pub(crate) fn keystone_door_keys(nodes: &[Node]) -> Vec<Option<UberIdentifier>> {
+    KEYSTONE_DOORS
+        .iter()
+        .map(|(door, _, _)| {
+            let identifier = format!("{door}Keys");
+            nodes
+                .iter()
+                .find(|node| node.identifier() == identifier)
+                .and_then(Node::trigger)
+                .map(|trigger| trigger.identifier)
+        })
+        .collect()
+}
+
 #[derive(Debug)]
 pub struct Graph {
     pub nodes: Vec<Node>,
     pub spawn_pickup_node: Node,
+    /// The UberStates counting the dedicated keys of each door in [`KEYSTONE_DOORS`], see [`keystone_door_keys`]
+    pub keystone_door_keys: Vec<Option<UberIdentifier>>,
 }
 impl Graph {
     pub fn new(nodes: Vec<Node>) -> Graph {
@@ -191,9 +252,27 @@ impl Graph {
             position: None,
             map_position: None,
         });
+        let keystone_door_keys = keystone_door_keys(&nodes);
         Graph {
             nodes,
             spawn_pickup_node,
+            keystone_door_keys,
+        }
+    }
+
+    /// Checks whether the [`Item`] counts up the dedicated keys of a keystone door
+    pub fn is_keystone_door_key(&self, item: &Item) -> bool {
+        match item {
+            Item::UberState(command) => self.keystone_door_keys.contains(&Some(command.identifier)),
+            _ => false,
+        }
+    }
+    /// Returns the [`ItemCategory`] of the [`Item`], counting dedicated keys of keystone doors as [`ItemCategory::Keystone`]
+    pub fn item_category(&self, item: &Item) -> ItemCategory {
+        if self.is_keystone_door_key(item) {
+            ItemCategory::Keystone
+        } else {
+            item.category()
         }
     }
 
@@ -214,6 +293,7 @@ impl Graph {
                     context.world_state[&from].clone(),
                 );
                 if !target_orbs.is_empty() {
+                    context.record_parent(from, connection, context.world_state[&from].clone());
                     self.reach_recursion(&self.nodes[connection.to], target_orbs, context);
                 }
             }
@@ -290,6 +370,7 @@ impl Graph {
                             }
                         }
                     } else {
+                        context.record_parent(anchor.index, connection, best_orbs.clone());
                         self.reach_recursion(&self.nodes[connection.to], target_orbs, context);
                     }
                 }
@@ -391,6 +472,28 @@ impl Graph {
 
         context.reached
     }
+    /// Finds the same nodes as [`Graph::reached_locations`], but keeps track of how each of them was reached
+    ///
+    /// This is slower than [`Graph::reached_locations`] and meant for spoilers and debugging
+    pub fn reach_paths<'a>(
+        &'a self,
+        player: &Player,
+        spawn: &'a Node,
+        extra_states: &FxHashMap<UberIdentifier, f32>,
+        sets: &[usize],
+    ) -> ReachPaths<'a> {
+        let mut context =
+            ReachContext::new(player, false, self.collect_extra_states(extra_states, sets));
+        context.parents = Some(FxHashMap::default());
+
+        self.reach_recursion(spawn, smallvec![player.max_orbs()], &mut context);
+        self.reached_by_teleporter(&mut context);
+
+        ReachPaths {
+            graph: self,
+            parents: context.parents.unwrap_or_default(),
+        }
+    }
     pub fn reached_and_progressions<'a>(
         &'a self,
         player: &Player,
@@ -13,7 +13,7 @@ use rustc_hash::FxHashMap;
 use crate::header::ItemDetails;
 use crate::item::{Item, Resource};
 use crate::log;
-use crate::settings::{Goal, WorldSettings};
+use crate::settings::{Goal, KeystoneMode, WorldSettings};
 use crate::uber_state::{UberIdentifier, UberStateTrigger};
 use crate::util::constants::WISP_STATES;
 
@@ -60,7 +60,14 @@ impl World<'_, '_> {
 
     pub fn grant_player(&mut self, item: Item, amount: u32) {
         match item {
-            Item::UberState(command) => {
+            Item::UberState(ref command) => {
+                // Keys for keystone doors are tracked in the inventory so the logic can count them
+                if self.player.settings.keystone_mode != KeystoneMode::KeysAnywhere
+                    && self.graph.is_keystone_door_key(&item)
+                {
+                    log::trace!("Granting player {}", item);
+                    self.player.inventory.grant(item.clone(), amount);
+                }
                 for _ in 0..amount {
                     let new = command.do_the_math(&self.uber_states);
                     let old = self.uber_states.insert(command.identifier, new);
@@ -264,7 +271,8 @@ mod tests {
         let graph =
             logic::parse_logic(&areas, &locations, &states, &universe_settings, false).unwrap();
         let mut world = World::new(&graph, &universe_settings.world_settings[0]);
-        world.player.inventory = Pool::preset().inventory;
+        world.player.inventory =
+            Pool::preset(&universe_settings.world_settings[0], &graph).inventory;
         world.player.inventory.grant(Item::SpiritLight(1), 10000);
 
         let spawn = world.graph.find_spawn("MarshSpawn.Main").unwrap();
@@ -4,6 +4,9 @@ use rustc_hash::FxHashMap;
 use crate::inventory::Inventory;
 use crate::item::{BonusUpgrade, Item, Resource, Shard, Skill};
 use crate::log;
+use crate::settings::{KeystoneMode, WorldSettings};
+use crate::util::constants::KEYSTONE_DOORS;
+use crate::world::Graph;
 
 // TODO the pool could be an inventory probably
 #[derive(Default, Debug, Clone)]
@@ -12,13 +15,30 @@ pub struct Pool {
     pub spirit_light: u32,
 }
 impl Pool {
-    pub fn preset() -> Pool {
+    /// The default item pool for the given [`WorldSettings`]
+    ///
+    /// The [`Graph`] provides the dedicated keys of keystone doors, if the [`KeystoneMode`] asks for them
+    pub fn preset(settings: &WorldSettings, graph: &Graph) -> Pool {
         let mut items = FxHashMap::default();
 
         items.insert(Item::Resource(Resource::HealthFragment), 24);
         items.insert(Item::Resource(Resource::EnergyFragment), 24);
         items.insert(Item::Resource(Resource::GorlekOre), 40);
-        items.insert(Item::Resource(Resource::Keystone), 34);
+        match settings.keystone_mode {
+            KeystoneMode::KeysAnywhere => {
+                items.insert(Item::Resource(Resource::Keystone), 34);
+            }
+            keystone_mode => {
+                for (&(_, _, keystones), key_state) in
+                    KEYSTONE_DOORS.iter().zip(&graph.keystone_door_keys)
+                {
+                    if let Some(key_state) = key_state {
+                        let (key, amount) = keystone_mode.door_key(*key_state, keystones);
+                        items.insert(key, amount);
+                    }
+                }
+            }
+        }
         items.insert(Item::Resource(Resource::ShardSlot), 5);
         items.insert(Item::Skill(Skill::Bash), 1);
         items.insert(Item::Skill(Skill::DoubleJump), 1);
@@ -116,10 +136,19 @@ impl Pool {
     }
 
     pub fn choose_random(&self, multiworld_spread: bool, rng: &mut impl Rng) -> Option<&Item> {
+        self.choose_random_filtered(
+            |item| !multiworld_spread || item.is_multiworld_spread(),
+            rng,
+        )
+    }
+    /// Like [`Pool::choose_random`], but only considers items for which `filter` returns `true`
+    pub fn choose_random_filtered(
+        &self,
+        filter: impl Fn(&Item) -> bool,
+        rng: &mut impl Rng,
+    ) -> Option<&Item> {
         let mut items = self.inventory.items.iter().collect::<Vec<_>>();
-        if multiworld_spread {
-            items.retain(|&(item, _)| item.is_multiworld_spread());
-        }
+        items.retain(|&(item, _)| filter(item));
 
         loop {
             let (item, _) = *items.choose_weighted(rng, |&(_, amount)| amount).ok()?;
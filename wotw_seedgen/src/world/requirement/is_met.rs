@@ -56,7 +56,18 @@ impl Requirement {
                 }
             }
             Requirement::Resource(resource, amount) => {
-                if player.inventory.has(&Item::Resource(*resource), *amount) {
+                if ignores_resource(player.settings, *resource)
+                    || player.inventory.has(&Item::Resource(*resource), *amount)
+                {
+                    return orb_variants;
+                }
+            }
+            Requirement::KeystoneDoor(key_state, keystones) => {
+                let (key, amount) = player
+                    .settings
+                    .keystone_mode
+                    .door_key(*key_state, *keystones);
+                if player.inventory.has(&key, amount) {
                     return orb_variants;
                 }
             }
@@ -228,6 +239,37 @@ impl Requirement {
         }
         smallvec![]
     }
+
+    /// Returns the part of this [`Requirement`] that was met, leaving out the alternatives that weren't needed
+    ///
+    /// If the [`Requirement`] isn't met, returns it unchanged
+    pub(crate) fn met_branch(
+        &self,
+        player: &Player,
+        states: &FxHashSet<usize>,
+        mut orb_variants: OrbVariants,
+    ) -> Requirement {
+        match self {
+            Requirement::And(requirements) => Requirement::And(
+                requirements
+                    .iter()
+                    .map(|and| {
+                        let branch = and.met_branch(player, states, orb_variants.clone());
+                        orb_variants = and.is_met(player, states, orb_variants.clone());
+                        branch
+                    })
+                    .collect(),
+            ),
+            Requirement::Or(requirements) => requirements
+                .iter()
+                .find(|or| !or.is_met(player, states, orb_variants.clone()).is_empty())
+                .map_or_else(
+                    || self.clone(),
+                    |or| or.met_branch(player, states, orb_variants),
+                ),
+            _ => self.clone(),
+        }
+    }
 }
 
 #[must_use]
@@ -10,9 +10,12 @@ use std::slice;
 use smallvec::SmallVec;
 
 use crate::item::{Resource, Shard, Skill, Teleporter};
-use crate::settings::{Difficulty, Trick, WorldSettings};
+use crate::settings::{Difficulty, KeystoneMode, Trick, WorldSettings};
+use crate::uber_state::UberIdentifier;
 use crate::util::Enemy;
 
+use super::graph::Node;
+
 #[derive(Debug, Clone)]
 pub enum Requirement {
     Free,
@@ -25,6 +28,10 @@ pub enum Requirement {
     NonConsumingEnergySkill(Skill),
     SpiritLight(u32),
     Resource(Resource, u32),
+    /// Keystones needed to open a door in [`KEYSTONE_DOORS`](crate::util::constants::KEYSTONE_DOORS), along with the UberState counting its dedicated keys
+    ///
+    /// Which items count as keystones depends on the [`KeystoneMode`](crate::settings::KeystoneMode)
+    KeystoneDoor(UberIdentifier, u32),
     Shard(Shard),
     Teleporter(Teleporter),
     Water,
@@ -58,6 +65,78 @@ impl Requirement {
         }
     }
 
+    /// Returns how much Spirit Light this [`Requirement`] expects the player to have
+    ///
+    /// If there are alternatives, the cheapest one is assumed
+    pub(crate) fn spirit_light_needed(&self) -> u32 {
+        match self {
+            Requirement::SpiritLight(amount) => *amount,
+            Requirement::And(nested) => nested
+                .iter()
+                .map(Requirement::spirit_light_needed)
+                .max()
+                .unwrap_or_default(),
+            Requirement::Or(nested) => nested
+                .iter()
+                .map(Requirement::spirit_light_needed)
+                .min()
+                .unwrap_or_default(),
+            _ => 0,
+        }
+    }
+
+    /// Formats the [`Requirement`] similar to the logic file syntax, using the identifiers of `nodes` for states
+    pub(crate) fn describe(&self, nodes: &[Node]) -> String {
+        match self {
+            Requirement::Free => "free".to_string(),
+            Requirement::Impossible => "Impossible".to_string(),
+            Requirement::Difficulty(difficulty) => format!("{difficulty:?}"),
+            Requirement::NormalGameDifficulty => "NormalGameDifficulty".to_string(),
+            Requirement::Trick(trick) => format!("{trick:?}"),
+            Requirement::Skill(skill) | Requirement::NonConsumingEnergySkill(skill) => {
+                format!("{skill:?}")
+            }
+            Requirement::EnergySkill(skill, amount) => format!("{skill:?}={amount}"),
+            Requirement::SpiritLight(amount) => format!("SpiritLight={amount}"),
+            Requirement::Resource(resource, amount) => format!("{resource:?}={amount}"),
+            Requirement::KeystoneDoor(_, amount) => format!("Keystone={amount}"),
+            Requirement::Shard(shard) => format!("{shard:?}"),
+            Requirement::Teleporter(teleporter) => format!("{teleporter:?}TP"),
+            Requirement::Water => "Water".to_string(),
+            Requirement::State(index) => nodes.get(*index).map_or_else(
+                || format!("State {index}"),
+                |node| node.identifier().to_string(),
+            ),
+            Requirement::Damage(amount) => format!("Damage={amount}"),
+            Requirement::Danger(amount) => format!("Danger={amount}"),
+            Requirement::Combat(enemies) => format!(
+                "Combat={}",
+                enemies
+                    .iter()
+                    .map(|(enemy, amount)| format!("{amount}x{enemy:?}"))
+                    .collect::<Vec<_>>()
+                    .join("+")
+            ),
+            Requirement::Boss(health) => format!("Boss={health}"),
+            Requirement::BreakWall(health) => format!("BreakWall={health}"),
+            Requirement::ShurikenBreak(health) => format!("ShurikenBreak={health}"),
+            Requirement::SentryBreak(health) => format!("SentryBreak={health}"),
+            Requirement::And(nested) => nested
+                .iter()
+                .map(|requirement| match requirement {
+                    Requirement::Or(_) => format!("({})", requirement.describe(nodes)),
+                    _ => requirement.describe(nodes),
+                })
+                .collect::<Vec<_>>()
+                .join(", "),
+            Requirement::Or(nested) => nested
+                .iter()
+                .map(|requirement| requirement.describe(nodes))
+                .collect::<Vec<_>>()
+                .join(" OR "),
+        }
+    }
+
     pub(crate) fn contained_requirements<'a, 'b>(
         &'a self,
         settings: &'b WorldSettings,
@@ -66,6 +145,11 @@ impl Requirement {
     }
 }
 
+/// Keystones outside of doors only steer the generator away from keylocks, which can't happen if doors have dedicated keys
+fn ignores_resource(settings: &WorldSettings, resource: Resource) -> bool {
+    resource == Resource::Keystone && settings.keystone_mode != KeystoneMode::KeysAnywhere
+}
+
 pub(crate) struct ContainedRequirements<'a, 'b> {
     nested: Vec<slice::Iter<'a, Requirement>>,
     settings: &'b WorldSettings,
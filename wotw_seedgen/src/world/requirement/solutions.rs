@@ -92,7 +92,16 @@ impl Requirement {
                 require_missing(solutions, Item::SpiritLight(1), *amount)
             }
             Requirement::Resource(resource, amount) => {
-                require_missing(solutions, Item::Resource(*resource), *amount)
+                if !ignores_resource(player.settings, *resource) {
+                    require_missing(solutions, Item::Resource(*resource), *amount)
+                }
+            }
+            Requirement::KeystoneDoor(key_state, keystones) => {
+                let (key, amount) = player
+                    .settings
+                    .keystone_mode
+                    .door_key(*key_state, *keystones);
+                require_missing(solutions, key, amount)
             }
             Requirement::Shard(shard) => require(solutions, Item::Shard(*shard)),
             Requirement::Teleporter(teleporter) => {
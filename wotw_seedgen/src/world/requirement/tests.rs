@@ -2168,3 +2168,88 @@ fn solutions() {
         ]
     );
 }
+
+#[test]
+fn met_branch() {
+    let world_settings = WorldSettings::default();
+    let mut player = Player::new(&world_settings);
+    player.inventory.grant(Item::Skill(Skill::Dash), 1);
+    let states = FxHashSet::default();
+
+    let requirement = Requirement::And(vec![
+        Requirement::Or(vec![
+            Requirement::Skill(Skill::DoubleJump),
+            Requirement::Skill(Skill::Dash),
+        ]),
+        Requirement::Or(vec![Requirement::Skill(Skill::Bash), Requirement::Free]),
+    ]);
+    let branch = requirement.met_branch(&player, &states, smallvec![player.max_orbs()]);
+    assert_eq!(branch.describe(&[]), "Dash, free");
+    assert_eq!(
+        requirement.describe(&[]),
+        "(DoubleJump OR Dash), (Bash OR free)"
+    );
+
+    let unmet = Requirement::Or(vec![
+        Requirement::Skill(Skill::Bash),
+        Requirement::Skill(Skill::Launch),
+    ]);
+    let branch = unmet.met_branch(&player, &states, smallvec![player.max_orbs()]);
+    assert_eq!(branch.describe(&[]), "Bash OR Launch");
+}
+
+#[test]
+fn keystone_modes() {
+    let keys_anywhere = WorldSettings::default();
+    let keysanity = WorldSettings {
+        keystone_mode: KeystoneMode::Keysanity,
+        ..WorldSettings::default()
+    };
+    let key_ring = WorldSettings {
+        keystone_mode: KeystoneMode::KeyRing,
+        ..WorldSettings::default()
+    };
+    let states = FxHashSet::default();
+    let key_state = UberIdentifier::new(9, 83);
+    let door = Requirement::KeystoneDoor(key_state, 4);
+    let outside_door = Requirement::Resource(Resource::Keystone, 2);
+    let is_met = |requirement: &Requirement, player: &Player| {
+        !requirement
+            .is_met(player, &states, smallvec![player.max_orbs()])
+            .is_empty()
+    };
+
+    // Mixed multiworlds share one graph, so every player has to be checked with their own settings
+    let mut player = Player::new(&keys_anywhere);
+    assert!(!is_met(&outside_door, &player));
+    assert!(!is_met(&door, &player));
+    player
+        .inventory
+        .grant(Item::Resource(Resource::Keystone), 4);
+    assert!(is_met(&outside_door, &player));
+    assert!(is_met(&door, &player));
+
+    let mut player = Player::new(&keysanity);
+    assert!(is_met(&outside_door, &player));
+    assert!(!is_met(&door, &player));
+    let (key, amount) = KeystoneMode::Keysanity.door_key(key_state, 4);
+    assert_eq!(amount, 4);
+    player.inventory.grant(key.clone(), 3);
+    assert!(!is_met(&door, &player));
+    player.inventory.grant(key, 1);
+    assert!(is_met(&door, &player));
+
+    let mut player = Player::new(&key_ring);
+    assert!(!is_met(&door, &player));
+    let solutions = door.solutions(&player, &states, smallvec![player.max_orbs()], 10, 10);
+    let (key, amount) = KeystoneMode::KeyRing.door_key(key_state, 4);
+    assert_eq!(amount, 1);
+    assert_eq!(solutions.len(), 1);
+    assert_eq!(solutions[0].get(&key), 1);
+    player.inventory.grant(key, 1);
+    assert!(is_met(&door, &player));
+    assert!(outside_door
+        .solutions(&player, &states, smallvec![player.max_orbs()], 10, 10)
+        .iter()
+        .all(|solution| solution.item_count() == 0));
+}
@@ -5,13 +5,21 @@ use std::ops::Deref;
 use std::path::PathBuf;
 use std::str::FromStr;
 
+use itertools::izip;
 use rustc_hash::FxHashSet;
 use structopt::StructOpt;
 
-use wotw_seedgen::item::{Shard, Skill, Teleporter};
-use wotw_seedgen::preset::{PresetGroup, PresetInfo, UniversePreset, WorldPreset};
-use wotw_seedgen::settings::{Difficulty, Goal, HeaderConfig, InlineHeader, Spawn, Trick};
-use wotw_seedgen::util::Zone;
+use wotw_seedgen::item::{Item, ItemCategory, Shard, Skill, Teleporter};
+use wotw_seedgen::preset::{
+    HintPreset, ItemPoolPreset, MultiworldPreset, PresetGroup, PresetInfo, RetryPolicyPreset,
+    ShopPricePreset, UniversePreset, WorldPreset,
+};
+use wotw_seedgen::settings::{
+    Accessibility, Difficulty, FillAlgorithm, Goal, HeaderConfig, HintPlacement, InlineHeader,
+    ItemPoolChange, KeystoneMode, LocationSelector, PlacementConstraint, PlacementStrategy,
+    PlandoPlacement, RelicRules, Spawn, SpiritLightDistribution, Trick,
+};
+use wotw_seedgen::util::{constants::RELIC_ZONES, Zone};
 
 #[derive(StructOpt)]
 /// Generate seeds for the Ori 2 randomizer.
@@ -64,6 +72,21 @@ pub enum SeedGenCommand {
         #[structopt(flatten)]
         args: ReachCheckArgs,
     },
+    /// Check whether a seed can be beaten by collecting its pickups
+    ///
+    /// Works on hand-edited and plando seeds as well. Reports locations that can't be reached and goals that can't be met
+    Verify {
+        #[structopt(flatten)]
+        args: VerifyArgs,
+    },
+    /// Compare the spoilers of two seeds
+    ///
+    /// Reports moved items, changed spawns, changed step counts and differences in what became reachable on each step.
+    /// This is useful to see the effects of logic or header changes on seeds generated with the same settings
+    Diff {
+        #[structopt(flatten)]
+        args: DiffArgs,
+    },
     /// Inspect the available headers
     Headers {
         /// headers to look at in detail
@@ -121,6 +144,16 @@ pub struct SeedMetaArgs {
     /// If --tostdout is disabled, only spoilers will be written as json files.
     #[structopt(long)]
     pub json: bool,
+    /// format of the spoiler, one of text, json, html or csv
+    ///
+    /// Defaults to json if --json is enabled and text otherwise. If --tostdout and --json are enabled, the spoiler is always part of the json object
+    #[structopt(long)]
+    pub spoiler_format: Option<SpoilerFormat>,
+    /// include the minimal playthrough in the spoiler
+    ///
+    /// This lists only the placements required to reach the goals, grouped into spheres. It can take a while to compute
+    #[structopt(long)]
+    pub playthrough: bool,
     /// launch the seed after generating
     #[structopt(short, long)]
     pub launch: bool,
@@ -311,6 +344,19 @@ impl FromStr for GoalsOpt {
                             return Err(format!("Invalid chance in details string for goal {s}"));
                         }
                         Goal::RelicChance(chance / 100.0)
+                    } else if let Some((min, max)) = details.split_once('-') {
+                        let parse_amount = |amount: &str| {
+                            amount.parse::<usize>().map_err(|_| {
+                                format!("Invalid range in details string for goal {s}")
+                            })
+                        };
+                        let rules = RelicRules::new(
+                            RELIC_ZONES.to_vec(),
+                            parse_amount(min)?,
+                            parse_amount(max)?,
+                        )
+                        .map_err(|err| format!("{err} in details string for goal {s}"))?;
+                        Goal::RelicRules(rules)
                     } else {
                         let amount = details.parse().map_err(|_| {
                             format!(
@@ -378,6 +424,379 @@ impl FromStr for InlineHeaderOpt {
     }
 }
 
+/// Newtype to parse plando placements
+#[derive(Clone)]
+pub struct PlandoOpt(PlandoPlacement);
+impl PlandoOpt {
+    fn into_inner(self) -> PlandoPlacement {
+        self.0
+    }
+}
+impl FromStr for PlandoOpt {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (location, item) = s
+            .split_once('=')
+            .ok_or_else(|| format!("Expected <location>=<item> in plando placement {s}"))?;
+
+        let plando_placement = PlandoPlacement {
+            location: location.to_string(),
+            item: item.parse()?,
+        };
+
+        Ok(PlandoOpt(plando_placement))
+    }
+}
+
+/// Newtype to parse item pool changes
+#[derive(Clone)]
+pub struct ItemPoolChangeOpt(ItemPoolChange);
+impl ItemPoolChangeOpt {
+    fn into_inner(self) -> ItemPoolChange {
+        self.0
+    }
+}
+impl FromStr for ItemPoolChangeOpt {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (item, amount) = s
+            .split_once('=')
+            .ok_or_else(|| format!("Expected <item>=<amount> in item pool change {s}"))?;
+
+        let item_pool_change = ItemPoolChange {
+            item: item.parse()?,
+            amount: amount
+                .parse()
+                .map_err(|_| format!("Invalid amount {amount} in item pool change {s}"))?,
+        };
+
+        Ok(ItemPoolChangeOpt(item_pool_change))
+    }
+}
+
+/// Newtype to parse location selectors
+#[derive(Clone)]
+pub struct LocationSelectorOpt(LocationSelector);
+impl LocationSelectorOpt {
+    fn into_inner(self) -> LocationSelector {
+        self.0
+    }
+}
+impl FromStr for LocationSelectorOpt {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Location identifiers always contain an area name, so anything else is interpreted as zone
+        let location_selector = if s.contains('.') {
+            LocationSelector::Location(s.to_string())
+        } else {
+            let zone = serde_json::from_value(serde_json::Value::String(s.to_string()))
+                .map_err(|_| format!("Unknown zone {s}"))?;
+            LocationSelector::Zone(zone)
+        };
+
+        Ok(LocationSelectorOpt(location_selector))
+    }
+}
+
+/// Newtype to parse placement constraints
+#[derive(Clone)]
+pub struct PlacementConstraintOpt(PlacementConstraint);
+impl PlacementConstraintOpt {
+    fn into_inner(self) -> PlacementConstraint {
+        self.0
+    }
+}
+impl FromStr for PlacementConstraintOpt {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Some items contain '=' themselves, the constraints never do
+        let (item, constraints) = s.rsplit_once('=').ok_or_else(|| {
+            format!("Expected <item>=<constraint>[,<constraint>...] in placement constraint {s}")
+        })?;
+
+        let mut placement_constraint = PlacementConstraint {
+            item: item.parse()?,
+            zones: vec![],
+            max_step: None,
+        };
+
+        for constraint in constraints.split(',') {
+            if let Some(max_step) = constraint.strip_prefix("step") {
+                let max_step = max_step
+                    .parse()
+                    .map_err(|_| format!("Invalid step in placement constraint {s}"))?;
+                placement_constraint.max_step = Some(max_step);
+            } else {
+                let zone =
+                    serde_json::from_value(serde_json::Value::String(constraint.to_string()))
+                        .map_err(|_| format!("Unknown zone {constraint}"))?;
+                placement_constraint.zones.push(zone);
+            }
+        }
+
+        Ok(PlacementConstraintOpt(placement_constraint))
+    }
+}
+
+/// Newtype to parse shop price multiplier ranges
+#[derive(Clone)]
+pub struct PriceRangeOpt(f32, f32);
+impl FromStr for PriceRangeOpt {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (min, max) = s
+            .split_once("..")
+            .ok_or_else(|| format!("Expected <min>..<max> in price range {s}"))?;
+        let parse = |multiplier: &str| {
+            multiplier
+                .parse()
+                .map_err(|_| format!("Invalid multiplier {multiplier} in price range {s}"))
+        };
+
+        Ok(PriceRangeOpt(parse(min)?, parse(max)?))
+    }
+}
+
+/// Newtype to parse spirit light distribution flag
+#[derive(Clone)]
+pub struct SpiritLightDistributionOpt(SpiritLightDistribution);
+impl SpiritLightDistributionOpt {
+    fn into_inner(self) -> SpiritLightDistribution {
+        self.0
+    }
+}
+impl FromStr for SpiritLightDistributionOpt {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (identifier, details) = s.split_once(':').unwrap_or((s, ""));
+
+        let distribution = match &identifier.to_lowercase()[..] {
+            "increasing" => SpiritLightDistribution::Increasing,
+            "flat" => SpiritLightDistribution::Flat,
+            "zoneweighted" => SpiritLightDistribution::ZoneWeighted,
+            "capped" => {
+                let max = details
+                    .parse()
+                    .map_err(|_| format!("Expected capped:<amount> in {s}"))?;
+                SpiritLightDistribution::Capped(max)
+            }
+            _ => return Err(format!("Unknown Spirit Light distribution {s}")),
+        };
+        if !details.is_empty() && !matches!(distribution, SpiritLightDistribution::Capped(_)) {
+            return Err(format!("Unexpected details string in {s}"));
+        }
+
+        Ok(SpiritLightDistributionOpt(distribution))
+    }
+}
+
+/// Newtype to parse keystone mode flag
+#[derive(Clone)]
+pub struct KeystoneModeOpt(KeystoneMode);
+impl KeystoneModeOpt {
+    fn into_inner(self) -> KeystoneMode {
+        self.0
+    }
+}
+impl FromStr for KeystoneModeOpt {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let keystone_mode = match &s.to_lowercase()[..] {
+            "anywhere" => KeystoneMode::KeysAnywhere,
+            "keysanity" => KeystoneMode::Keysanity,
+            "keyring" => KeystoneMode::KeyRing,
+            _ => return Err(format!("Unknown keystone mode {s}")),
+        };
+
+        Ok(KeystoneModeOpt(keystone_mode))
+    }
+}
+
+/// Newtype to parse accessibility flag
+#[derive(Clone)]
+pub struct AccessibilityOpt(Accessibility);
+impl AccessibilityOpt {
+    fn into_inner(self) -> Accessibility {
+        self.0
+    }
+}
+impl FromStr for AccessibilityOpt {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let accessibility = match &s.to_lowercase()[..] {
+            "locations" => Accessibility::Locations,
+            "full" => Accessibility::Full,
+            "beatable" => Accessibility::Beatable,
+            _ => return Err(format!("Unknown accessibility {s}")),
+        };
+
+        Ok(AccessibilityOpt(accessibility))
+    }
+}
+
+/// Output format of the spoiler
+#[derive(Clone, Copy)]
+pub enum SpoilerFormat {
+    Text,
+    Json,
+    Html,
+    Csv,
+}
+impl FromStr for SpoilerFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let format = match &s.to_lowercase()[..] {
+            "text" | "txt" => SpoilerFormat::Text,
+            "json" => SpoilerFormat::Json,
+            "html" => SpoilerFormat::Html,
+            "csv" => SpoilerFormat::Csv,
+            _ => return Err(format!("Unknown spoiler format {s}")),
+        };
+
+        Ok(format)
+    }
+}
+
+/// Newtype to parse the hint placement flag
+#[derive(Clone)]
+pub struct HintPlacementOpt(HintPlacement);
+impl HintPlacementOpt {
+    fn into_inner(self) -> HintPlacement {
+        self.0
+    }
+}
+impl FromStr for HintPlacementOpt {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let placement = match &s.to_lowercase()[..] {
+            "lupo" => HintPlacement::LupoMaps,
+            "shrines" => HintPlacement::Shrines,
+            _ => return Err(format!("Unknown hint placement {s}")),
+        };
+
+        Ok(HintPlacementOpt(placement))
+    }
+}
+
+const DEFAULT_TARGET_STEP_SIZE: usize = 12;
+/// Newtype to parse the placement strategy flag
+pub struct PlacementStrategyOpt(PlacementStrategy);
+impl PlacementStrategyOpt {
+    pub fn into_inner(self) -> PlacementStrategy {
+        self.0
+    }
+}
+impl FromStr for PlacementStrategyOpt {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (identifier, details) = s.split_once(':').unwrap_or((s, ""));
+
+        let placement_strategy = match &identifier.to_lowercase()[..] {
+            "classic" => PlacementStrategy::Classic,
+            "balanced" => {
+                let target_step_size = if details.is_empty() {
+                    NonZeroUsize::new(DEFAULT_TARGET_STEP_SIZE).unwrap()
+                } else {
+                    details.parse().map_err(|_| {
+                        format!(
+                            "Invalid target step size in details string for placement strategy {s}"
+                        )
+                    })?
+                };
+                PlacementStrategy::Balanced { target_step_size }
+            }
+            _ => return Err(format!("Unknown placement strategy {s}")),
+        };
+
+        Ok(PlacementStrategyOpt(placement_strategy))
+    }
+}
+/// Newtype to parse the fill algorithm flag
+pub struct FillAlgorithmOpt(FillAlgorithm);
+impl FillAlgorithmOpt {
+    fn into_inner(self) -> FillAlgorithm {
+        self.0
+    }
+}
+impl FromStr for FillAlgorithmOpt {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let fill_algorithm = match &s.to_lowercase()[..] {
+            "forward" => FillAlgorithm::Forward,
+            "assumed" => FillAlgorithm::Assumed,
+            _ => return Err(format!("Unknown fill algorithm {s}")),
+        };
+
+        Ok(FillAlgorithmOpt(fill_algorithm))
+    }
+}
+fn shop_price_preset(
+    price_range: Option<PriceRangeOpt>,
+    max_total: Option<u32>,
+    free_progression: Option<bool>,
+) -> Option<ShopPricePreset> {
+    if price_range.is_none() && max_total.is_none() && free_progression.is_none() {
+        return None;
+    }
+
+    let (min_multiplier, max_multiplier) = price_range
+        .map(|PriceRangeOpt(min, max)| (min, max))
+        .unzip();
+    Some(ShopPricePreset {
+        min_multiplier,
+        max_multiplier,
+        max_total,
+        free_progression,
+    })
+}
+fn hint_preset(
+    way_of_the_hero: Option<usize>,
+    barren: Option<usize>,
+    item_locations: Option<usize>,
+    placement: Option<HintPlacementOpt>,
+) -> Option<HintPreset> {
+    if way_of_the_hero.is_none()
+        && barren.is_none()
+        && item_locations.is_none()
+        && placement.is_none()
+    {
+        return None;
+    }
+
+    Some(HintPreset {
+        way_of_the_hero,
+        barren,
+        item_locations,
+        placement: placement.map(HintPlacementOpt::into_inner),
+    })
+}
+
+fn item_pool_preset(
+    changes: Option<Vec<ItemPoolChange>>,
+    spirit_light: Option<u32>,
+) -> Option<ItemPoolPreset> {
+    if changes.is_none() && spirit_light.is_none() {
+        return None;
+    }
+
+    Some(ItemPoolPreset {
+        changes,
+        spirit_light,
+    })
+}
+
 #[derive(StructOpt)]
 pub struct SeedSettings {
     /// Derive the settings from one or more presets
@@ -415,7 +834,7 @@ pub struct SeedSettings {
     pub hard: Option<Vec<WorldOpt<bool>>>,
     /// Goal Requirements before finishing the game
     ///
-    /// Available goals are trees, wisps, quests, relics. Relics can further configure the chance per area to have a relic, default is relics:60%
+    /// Available goals are trees, wisps, quests, relics. Relics can further configure the chance per area to have a relic, default is relics:60%, or a range of relics to place such as relics:2-5
     #[structopt(short, long)]
     pub goals: Vec<WorldOpt<GoalsOpt>>,
     /// Names of headers that will be used when generating the seed
@@ -431,6 +850,74 @@ pub struct SeedSettings {
     /// Inline header syntax
     #[structopt(short, long = "inline")]
     pub inline_headers: Vec<WorldOpt<InlineHeaderOpt>>,
+    /// Place items on specific locations
+    ///
+    /// Format for one placement: <location>=<item>, using location names from the loc_data file and header item syntax
+    #[structopt(long)]
+    pub plando: Vec<WorldOpt<PlandoOpt>>,
+    /// Locations that should never hold progression
+    ///
+    /// Use location names from the loc_data file or zone names such as "Shop" to exclude a whole zone
+    #[structopt(long)]
+    pub exclude: Vec<WorldOpt<LocationSelectorOpt>>,
+    /// Restrict where or how early an item may be placed
+    ///
+    /// Format for one constraint: <item>=<constraint>[,<constraint>...], where each constraint is a zone name such as "Wellspring" or a step limit such as "step3"
+    #[structopt(long = "constraint")]
+    pub placement_constraints: Vec<WorldOpt<PlacementConstraintOpt>>,
+    /// Range of the random multiplier applied to shop prices
+    ///
+    /// Format: <min>..<max>, default is 0.75..1.25
+    #[structopt(long)]
+    pub price_range: Vec<WorldOpt<PriceRangeOpt>>,
+    /// Limit the sum of all prices in each individual shop
+    #[structopt(long)]
+    pub max_shop_total: Vec<WorldOpt<u32>>,
+    /// Sell items that may be required by logic for free
+    #[structopt(long)]
+    pub free_progression: Option<Vec<WorldOpt<bool>>>,
+    /// How the Spirit Light from the item pool is split across its pickups
+    ///
+    /// Available distributions are "increasing", "flat", "zoneweighted" and "capped:<amount>", default is increasing
+    #[structopt(long)]
+    pub spirit_light: Vec<WorldOpt<SpiritLightDistributionOpt>>,
+    /// Which items open the keystone doors
+    ///
+    /// Available modes are "anywhere" (keystones work on any door), "keysanity" (every door has its own keys) and "keyring" (every door has a single key ring), default is anywhere
+    #[structopt(long)]
+    pub keystones: Vec<WorldOpt<KeystoneModeOpt>>,
+    /// Which locations have to be reachable
+    ///
+    /// Available modes are "locations" (every location that can be reached on these settings), "full" (every location, refuses to generate otherwise) and "beatable" (only the goal), default is locations
+    #[structopt(long)]
+    pub accessibility: Vec<WorldOpt<AccessibilityOpt>>,
+    /// Add items to or remove items from the item pool
+    ///
+    /// Format for one change: <item>=<amount>, using header item syntax. Negative amounts remove the item
+    #[structopt(long = "pool")]
+    pub item_pool_changes: Vec<WorldOpt<ItemPoolChangeOpt>>,
+    /// Replace the total amount of Spirit Light in the item pool
+    #[structopt(long)]
+    pub pool_spirit_light: Vec<WorldOpt<u32>>,
+    /// Items to receive when spawning, using header item syntax
+    ///
+    /// These are taken out of the item pool
+    #[structopt(long = "start-with")]
+    pub starting_items: Vec<WorldOpt<Item>>,
+    /// How many zones to hint as holding an item required to reach the goals
+    #[structopt(long = "woth-hints")]
+    pub way_of_the_hero_hints: Vec<WorldOpt<usize>>,
+    /// How many zones to hint as holding no items required to reach the goals
+    #[structopt(long)]
+    pub barren_hints: Vec<WorldOpt<usize>>,
+    /// How many required items to hint the exact location of
+    #[structopt(long = "item-hints")]
+    pub item_location_hints: Vec<WorldOpt<usize>>,
+    /// Which locations reveal the generated hints
+    ///
+    /// Available placements are "lupo" (the maps sold by Lupo) and "shrines" (the Combat Shrines), default is lupo
+    #[structopt(long)]
+    pub hint_placement: Vec<WorldOpt<HintPlacementOpt>>,
     /// Disallow the use of the In-Logic filter while playing the seed
     #[structopt(short = "L", long)]
     pub disable_logic_filter: bool,
@@ -444,6 +931,50 @@ pub struct SeedSettings {
     /// Without this flag, the rng seed will be randomly generated
     #[structopt(long)]
     pub seed: Option<String>,
+    /// How many attempts to generate placements will be made before giving up
+    #[structopt(long)]
+    pub attempts: Option<u16>,
+    /// Keep the same spawn locations when retrying after a failed attempt
+    #[structopt(long)]
+    pub keep_spawns: bool,
+    /// Stop retrying once this many milliseconds have passed
+    ///
+    /// Note that seeds generated with a time budget may not be reproducible
+    #[structopt(long)]
+    pub time_budget: Option<u64>,
+    /// Item categories that always get placed in their own world
+    ///
+    /// Available categories are "spiritlight", "healthfragment", "energyfragment", "gorlekore", "keystone", "shardslot", "skill", "shard", "teleporter", "cleanwater", "relic", "bonus", "other"
+    #[structopt(long)]
+    pub local_items: Vec<ItemCategory>,
+    /// Item categories that get placed in other worlds whenever possible
+    ///
+    /// Available categories are the same as for --local-items
+    #[structopt(long)]
+    pub shared_items: Vec<ItemCategory>,
+    /// The desired fraction of progression items sent to other worlds, between 0 and 1
+    #[structopt(long)]
+    pub progression_balance: Option<f32>,
+    /// How the generator decides which progression to place
+    ///
+    /// Available strategies are "classic" and "balanced". Balanced can further configure how many new locations each step should aim for, default is balanced:12
+    #[structopt(long)]
+    pub placement_strategy: Option<PlacementStrategyOpt>,
+    /// Which algorithm the generator uses to fill the locations with items
+    ///
+    /// Available algorithms are "forward" and "assumed". Assumed fill copes better with heavily constrained settings, but ignores the placement strategy
+    #[structopt(long)]
+    pub fill_algorithm: Option<FillAlgorithmOpt>,
+    /// Record in the spoiler how each newly reachable location was reached
+    ///
+    /// For every location this lists the anchors leading to it and the part of the logic requirement that was met. Slows down generation
+    #[structopt(long)]
+    pub spoiler_paths: bool,
+    /// Which version of the generation algorithm to use
+    ///
+    /// Defaults to the newest version. Seeds generated on other releases may still differ, since only the random number stream is pinned
+    #[structopt(long)]
+    pub algorithm_version: Option<u32>,
 }
 
 fn slice_in_option<T, S: Deref<Target = [T]>>(slice: S) -> Option<S> {
@@ -468,9 +999,35 @@ impl SeedSettings {
             headers,
             header_config,
             inline_headers,
+            plando,
+            exclude,
+            placement_constraints,
+            price_range,
+            max_shop_total,
+            free_progression,
+            spirit_light,
+            keystones,
+            accessibility,
+            item_pool_changes,
+            pool_spirit_light,
+            starting_items,
+            way_of_the_hero_hints,
+            barren_hints,
+            item_location_hints,
+            hint_placement,
             disable_logic_filter,
             online,
             seed,
+            attempts,
+            keep_spawns,
+            time_budget,
+            local_items,
+            shared_items,
+            progression_balance,
+            placement_strategy,
+            fill_algorithm,
+            spoiler_paths,
+            algorithm_version,
         } = self;
 
         let world_presets = resolve_world_opts(world_presets, worlds)?;
@@ -482,6 +1039,25 @@ impl SeedSettings {
         let world_headers = resolve_world_opts(headers, worlds)?;
         let world_header_configs = resolve_world_opts(header_config, worlds)?;
         let world_inline_headers = resolve_world_opts(inline_headers, worlds)?;
+        let world_plando = resolve_world_opts(plando, worlds)?;
+        let world_excluded_locations = resolve_world_opts(exclude, worlds)?;
+        let world_placement_constraints = resolve_world_opts(placement_constraints, worlds)?;
+        let world_price_ranges = resolve_nonduplicate_world_opts(price_range, worlds)?;
+        let world_max_shop_totals = resolve_nonduplicate_world_opts(max_shop_total, worlds)?;
+        let world_free_progression_flags = resolve_flag_world_opts(free_progression, worlds)?;
+        let world_spirit_light_distributions =
+            resolve_nonduplicate_world_opts(spirit_light, worlds)?;
+        let world_keystone_modes = resolve_nonduplicate_world_opts(keystones, worlds)?;
+        let world_accessibilities = resolve_nonduplicate_world_opts(accessibility, worlds)?;
+        let world_item_pool_changes = resolve_world_opts(item_pool_changes, worlds)?;
+        let world_pool_spirit_light = resolve_nonduplicate_world_opts(pool_spirit_light, worlds)?;
+        let world_starting_items = resolve_world_opts(starting_items, worlds)?;
+        let world_way_of_the_hero_hints =
+            resolve_nonduplicate_world_opts(way_of_the_hero_hints, worlds)?;
+        let world_barren_hints = resolve_nonduplicate_world_opts(barren_hints, worlds)?;
+        let world_item_location_hints =
+            resolve_nonduplicate_world_opts(item_location_hints, worlds)?;
+        let world_hint_placements = resolve_nonduplicate_world_opts(hint_placement, worlds)?;
 
         let disable_logic_filter = if disable_logic_filter {
             Some(true)
@@ -489,52 +1065,147 @@ impl SeedSettings {
             None
         };
         let online = if online { Some(true) } else { None };
+        let spoiler_paths = if spoiler_paths { Some(true) } else { None };
+        let repick_spawns = if keep_spawns { Some(false) } else { None };
+        let retry_policy = if attempts.is_some() || repick_spawns.is_some() || time_budget.is_some()
+        {
+            Some(RetryPolicyPreset {
+                attempts,
+                repick_spawns,
+                time_budget_ms: time_budget,
+            })
+        } else {
+            None
+        };
+        let local_items = slice_in_option(local_items);
+        let shared_items = slice_in_option(shared_items);
+        let multiworld =
+            if local_items.is_some() || shared_items.is_some() || progression_balance.is_some() {
+                Some(MultiworldPreset {
+                    local_items,
+                    shared_items,
+                    progression_balance,
+                })
+            } else {
+                None
+            };
 
-        let yes_fun = world_presets
-            .into_iter()
-            .zip(world_spawns)
-            .zip(world_difficulties)
-            .zip(world_tricks)
-            .zip(world_hard_flags)
-            .zip(world_goals)
-            .zip(world_headers)
-            .zip(world_header_configs)
-            .zip(world_inline_headers)
-            .map(
-                |(
-                    (
-                        ((((((world_presets, spawn), difficulty), tricks), hard), goals), headers),
-                        header_config,
+        let yes_fun = izip!(
+            world_presets,
+            world_spawns,
+            world_difficulties,
+            world_tricks,
+            world_hard_flags,
+            world_goals,
+            world_headers,
+            world_header_configs,
+            world_inline_headers,
+            world_plando,
+            world_excluded_locations,
+            world_placement_constraints,
+            world_price_ranges,
+            world_max_shop_totals,
+            world_free_progression_flags,
+            world_spirit_light_distributions,
+            world_keystone_modes,
+            world_accessibilities,
+            world_item_pool_changes,
+            world_pool_spirit_light,
+            world_starting_items,
+            world_way_of_the_hero_hints,
+            world_barren_hints,
+            world_item_location_hints,
+            world_hint_placements,
+        )
+        .map(
+            |(
+                world_presets,
+                spawn,
+                difficulty,
+                tricks,
+                hard,
+                goals,
+                headers,
+                header_config,
+                inline_headers,
+                plando,
+                excluded_locations,
+                placement_constraints,
+                price_range,
+                max_shop_total,
+                free_progression,
+                spirit_light_distribution,
+                keystone_mode,
+                accessibility,
+                item_pool_changes,
+                pool_spirit_light,
+                starting_items,
+                way_of_the_hero_hints,
+                barren_hints,
+                item_location_hints,
+                hint_placement,
+            )| {
+                WorldPreset {
+                    info: None,
+                    includes: slice_in_option(world_presets).map(FxHashSet::from_iter),
+                    spawn: spawn.map(SpawnOpt::into_inner),
+                    difficulty,
+                    tricks: slice_in_option(tricks).map(FxHashSet::from_iter),
+                    goals: slice_in_option(goals.into_iter().map(GoalsOpt::into_inner).collect()),
+                    hard,
+                    headers: slice_in_option(headers).map(FxHashSet::from_iter),
+                    header_config: slice_in_option(
+                        header_config
+                            .into_iter()
+                            .map(HeaderConfigOpt::into_inner)
+                            .collect(),
                     ),
-                    inline_headers,
-                )| {
-                    WorldPreset {
-                        info: None,
-                        includes: slice_in_option(world_presets).map(FxHashSet::from_iter),
-                        spawn: spawn.map(SpawnOpt::into_inner),
-                        difficulty,
-                        tricks: slice_in_option(tricks).map(FxHashSet::from_iter),
-                        goals: slice_in_option(
-                            goals.into_iter().map(GoalsOpt::into_inner).collect(),
-                        ),
-                        hard,
-                        headers: slice_in_option(headers).map(FxHashSet::from_iter),
-                        header_config: slice_in_option(
-                            header_config
-                                .into_iter()
-                                .map(HeaderConfigOpt::into_inner)
-                                .collect(),
-                        ),
-                        inline_headers: slice_in_option(
-                            inline_headers
+                    inline_headers: slice_in_option(
+                        inline_headers
+                            .into_iter()
+                            .map(InlineHeaderOpt::into_inner)
+                            .collect(),
+                    ),
+                    plando: slice_in_option(
+                        plando.into_iter().map(PlandoOpt::into_inner).collect(),
+                    ),
+                    excluded_locations: slice_in_option(
+                        excluded_locations
+                            .into_iter()
+                            .map(LocationSelectorOpt::into_inner)
+                            .collect(),
+                    ),
+                    placement_constraints: slice_in_option(
+                        placement_constraints
+                            .into_iter()
+                            .map(PlacementConstraintOpt::into_inner)
+                            .collect(),
+                    ),
+                    shop_prices: shop_price_preset(price_range, max_shop_total, free_progression),
+                    spirit_light_distribution: spirit_light_distribution
+                        .map(SpiritLightDistributionOpt::into_inner),
+                    keystone_mode: keystone_mode.map(KeystoneModeOpt::into_inner),
+                    accessibility: accessibility.map(AccessibilityOpt::into_inner),
+                    item_pool: item_pool_preset(
+                        slice_in_option(
+                            item_pool_changes
                                 .into_iter()
-                                .map(InlineHeaderOpt::into_inner)
+                                .map(ItemPoolChangeOpt::into_inner)
                                 .collect(),
                         ),
-                    }
-                },
-            )
-            .collect::<Vec<_>>();
+                        pool_spirit_light,
+                    ),
+                    starting_items: slice_in_option(starting_items),
+                    hints: hint_preset(
+                        way_of_the_hero_hints,
+                        barren_hints,
+                        item_location_hints,
+                        hint_placement,
+                    ),
+                }
+            },
+        )
+        .collect::<Vec<_>>();
 
         Ok(UniversePreset {
             info: None,
@@ -544,6 +1215,12 @@ impl SeedSettings {
             seed,
             online,
             create_game: None,
+            retry_policy,
+            multiworld,
+            placement_strategy: placement_strategy.map(PlacementStrategyOpt::into_inner),
+            fill_algorithm: fill_algorithm.map(FillAlgorithmOpt::into_inner),
+            spoiler_paths,
+            algorithm_version,
         })
     }
 }
@@ -587,7 +1264,7 @@ pub struct WorldPresetSettings {
     pub hard: bool,
     /// Goal Requirements before finishing the game
     ///
-    /// Available goals are trees, wisps, quests, relics. Relics can further configure the chance per area to have a relic, default is relics:60%
+    /// Available goals are trees, wisps, quests, relics. Relics can further configure the chance per area to have a relic, default is relics:60%, or a range of relics to place such as relics:2-5
     #[structopt(short, long)]
     pub goals: Option<Vec<GoalsOpt>>,
     /// Names of headers that will be used when generating the seed
@@ -603,6 +1280,74 @@ pub struct WorldPresetSettings {
     /// Inline header syntax
     #[structopt(short, long = "inline")]
     pub inline_headers: Option<Vec<InlineHeaderOpt>>,
+    /// Place items on specific locations
+    ///
+    /// Format for one placement: <location>=<item>, using location names from the loc_data file and header item syntax
+    #[structopt(long)]
+    pub plando: Option<Vec<PlandoOpt>>,
+    /// Locations that should never hold progression
+    ///
+    /// Use location names from the loc_data file or zone names such as "Shop" to exclude a whole zone
+    #[structopt(long)]
+    pub exclude: Option<Vec<LocationSelectorOpt>>,
+    /// Restrict where or how early an item may be placed
+    ///
+    /// Format for one constraint: <item>=<constraint>[,<constraint>...], where each constraint is a zone name such as "Wellspring" or a step limit such as "step3"
+    #[structopt(long = "constraint")]
+    pub placement_constraints: Option<Vec<PlacementConstraintOpt>>,
+    /// Range of the random multiplier applied to shop prices
+    ///
+    /// Format: <min>..<max>, default is 0.75..1.25
+    #[structopt(long)]
+    pub price_range: Option<PriceRangeOpt>,
+    /// Limit the sum of all prices in each individual shop
+    #[structopt(long)]
+    pub max_shop_total: Option<u32>,
+    /// Sell items that may be required by logic for free
+    #[structopt(long)]
+    pub free_progression: bool,
+    /// How the Spirit Light from the item pool is split across its pickups
+    ///
+    /// Available distributions are "increasing", "flat", "zoneweighted" and "capped:<amount>", default is increasing
+    #[structopt(long)]
+    pub spirit_light: Option<SpiritLightDistributionOpt>,
+    /// Which items open the keystone doors
+    ///
+    /// Available modes are "anywhere" (keystones work on any door), "keysanity" (every door has its own keys) and "keyring" (every door has a single key ring), default is anywhere
+    #[structopt(long)]
+    pub keystones: Option<KeystoneModeOpt>,
+    /// Which locations have to be reachable
+    ///
+    /// Available modes are "locations" (every location that can be reached on these settings), "full" (every location, refuses to generate otherwise) and "beatable" (only the goal), default is locations
+    #[structopt(long)]
+    pub accessibility: Option<AccessibilityOpt>,
+    /// Add items to or remove items from the item pool
+    ///
+    /// Format for one change: <item>=<amount>, using header item syntax. Negative amounts remove the item
+    #[structopt(long = "pool")]
+    pub item_pool_changes: Option<Vec<ItemPoolChangeOpt>>,
+    /// Replace the total amount of Spirit Light in the item pool
+    #[structopt(long)]
+    pub pool_spirit_light: Option<u32>,
+    /// Items to receive when spawning, using header item syntax
+    ///
+    /// These are taken out of the item pool
+    #[structopt(long = "start-with")]
+    pub starting_items: Option<Vec<Item>>,
+    /// How many zones to hint as holding an item required to reach the goals
+    #[structopt(long = "woth-hints")]
+    pub way_of_the_hero_hints: Option<usize>,
+    /// How many zones to hint as holding no items required to reach the goals
+    #[structopt(long)]
+    pub barren_hints: Option<usize>,
+    /// How many required items to hint the exact location of
+    #[structopt(long = "item-hints")]
+    pub item_location_hints: Option<usize>,
+    /// Which locations reveal the generated hints
+    ///
+    /// Available placements are "lupo" (the maps sold by Lupo) and "shrines" (the Combat Shrines), default is lupo
+    #[structopt(long)]
+    pub hint_placement: Option<HintPlacementOpt>,
 }
 
 impl WorldPresetSettings {
@@ -618,6 +1363,22 @@ impl WorldPresetSettings {
             headers,
             header_config,
             inline_headers,
+            plando,
+            exclude,
+            placement_constraints,
+            price_range,
+            max_shop_total,
+            free_progression,
+            spirit_light,
+            keystones,
+            accessibility,
+            item_pool_changes,
+            pool_spirit_light,
+            starting_items,
+            way_of_the_hero_hints,
+            barren_hints,
+            item_location_hints,
+            hint_placement,
         } = self;
 
         WorldPreset {
@@ -641,6 +1402,43 @@ impl WorldPresetSettings {
                     .map(InlineHeaderOpt::into_inner)
                     .collect()
             }),
+            plando: plando.map(|plando| plando.into_iter().map(PlandoOpt::into_inner).collect()),
+            excluded_locations: exclude.map(|exclude| {
+                exclude
+                    .into_iter()
+                    .map(LocationSelectorOpt::into_inner)
+                    .collect()
+            }),
+            placement_constraints: placement_constraints.map(|placement_constraints| {
+                placement_constraints
+                    .into_iter()
+                    .map(PlacementConstraintOpt::into_inner)
+                    .collect()
+            }),
+            shop_prices: shop_price_preset(
+                price_range,
+                max_shop_total,
+                if free_progression { Some(true) } else { None },
+            ),
+            spirit_light_distribution: spirit_light.map(SpiritLightDistributionOpt::into_inner),
+            keystone_mode: keystones.map(KeystoneModeOpt::into_inner),
+            accessibility: accessibility.map(AccessibilityOpt::into_inner),
+            item_pool: item_pool_preset(
+                item_pool_changes.map(|item_pool_changes| {
+                    item_pool_changes
+                        .into_iter()
+                        .map(ItemPoolChangeOpt::into_inner)
+                        .collect()
+                }),
+                pool_spirit_light,
+            ),
+            starting_items,
+            hints: hint_preset(
+                way_of_the_hero_hints,
+                barren_hints,
+                item_location_hints,
+                hint_placement,
+            ),
         }
     }
 }
@@ -692,6 +1490,18 @@ pub struct StatsArgs {
     /// cleans the cache for the provided settings and generates new seeds from scratch
     #[structopt(short = "O", long)]
     pub overwrite_cache: bool,
+    /// Generate the stats once for each of these placement strategies and compare them side by side
+    ///
+    /// This overrides --placement-strategy. Available strategies are the same as for --placement-strategy
+    ///
+    /// For instance, "--analyzers step-size --compare-strategies classic balanced:12" will create a "Size of progression steps by placement strategy.csv"
+    #[structopt(long)]
+    pub compare_strategies: Vec<PlacementStrategyOpt>,
+    /// Analyze these text spoilers instead of generating seeds
+    ///
+    /// The locations in the spoilers are looked up in the logic files, so they should be the same ones the seeds were generated with
+    #[structopt(parse(from_os_str), long)]
+    pub spoilers: Vec<PathBuf>,
     #[structopt(flatten)]
     pub settings: SeedSettings,
 }
@@ -807,6 +1617,35 @@ pub struct ReachCheckArgs {
     pub items: Vec<ReachData>,
 }
 
+#[derive(StructOpt)]
+pub struct VerifyArgs {
+    /// the seed files to verify, for multiworld seeds one for each world
+    #[structopt(parse(from_os_str), required = true)]
+    pub seed_files: Vec<PathBuf>,
+    /// the input file representing the logic
+    #[structopt(parse(from_os_str), default_value = "areas.wotw", short, long)]
+    pub areas: PathBuf,
+    /// the input file representing pickup locations
+    #[structopt(parse(from_os_str), default_value = "loc_data.csv", short, long)]
+    pub locations: PathBuf,
+    /// the input file representing state namings
+    #[structopt(parse(from_os_str), default_value = "state_data.csv", short, long)]
+    pub uber_states: PathBuf,
+}
+
+#[derive(StructOpt)]
+pub struct DiffArgs {
+    /// the old spoiler, either as text or json
+    #[structopt(parse(from_os_str))]
+    pub old: PathBuf,
+    /// the new spoiler, either as text or json
+    #[structopt(parse(from_os_str))]
+    pub new: PathBuf,
+    /// write the differences to stdout in json format
+    #[structopt(long)]
+    pub json: bool,
+}
+
 pub enum ReachData {
     Skill(Skill),
     Teleporter(Teleporter),
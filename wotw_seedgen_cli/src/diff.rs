@@ -0,0 +1,36 @@
+use super::cli;
+
+use std::fs;
+use std::path::Path;
+
+use wotw_seedgen::generator::SeedSpoiler;
+
+pub fn diff(args: cli::DiffArgs) -> Result<(), String> {
+    let old = read_spoiler(&args.old)?;
+    let new = read_spoiler(&args.new)?;
+
+    let diff = old.diff(&new);
+    if args.json {
+        println!("{}", diff.to_json());
+    } else {
+        print!("{diff}");
+    }
+
+    Ok(())
+}
+
+/// Reads a spoiler written by the seed command, json spoilers are recognized by their extension
+fn read_spoiler(path: &Path) -> Result<SeedSpoiler, String> {
+    let contents = fs::read_to_string(path)
+        .map_err(|err| format!("Failed to read {}: {}", path.display(), err))?;
+
+    if path
+        .extension()
+        .is_some_and(|extension| extension == "json")
+    {
+        serde_json::from_str(&contents).map_err(|err| err.to_string())
+    } else {
+        contents.parse()
+    }
+    .map_err(|err| format!("Failed to parse {}: {}", path.display(), err))
+}
@@ -1,4 +1,5 @@
 mod cli;
+mod diff;
 mod headers;
 mod log_init;
 mod play;
@@ -6,6 +7,7 @@ mod reach_check;
 mod seed;
 mod stats;
 mod universe_preset;
+mod verify;
 mod world_preset;
 
 use std::process::ExitCode;
@@ -30,11 +32,13 @@ fn main() -> ExitCode {
         cli::SeedGenCommand::WorldPreset { args } => world_preset::create_world_preset(args),
         cli::SeedGenCommand::Stats { args } => stats::generate_stats(args),
         cli::SeedGenCommand::CleanStatsCache => stats::clean_stats_cache(),
+        cli::SeedGenCommand::Diff { args } => diff::diff(args),
         cli::SeedGenCommand::Headers {
             headers,
             subcommand,
         } => headers::headers(headers, subcommand),
         cli::SeedGenCommand::ReachCheck { args } => reach_check::reach_check(args),
+        cli::SeedGenCommand::Verify { args } => verify::verify(args),
         cli::SeedGenCommand::Regenerate { args } => seed::regenerate_seed(args),
     }
     .map_or_else(
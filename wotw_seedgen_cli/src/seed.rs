@@ -1,5 +1,6 @@
 use crate::cli::RegenerateArgs;
 use crate::cli::SeedMetaArgs;
+use crate::cli::SpoilerFormat;
 
 use super::cli;
 use super::log_init;
@@ -14,7 +15,7 @@ use log::LevelFilter;
 use serde::{Deserialize, Serialize};
 
 use wotw_seedgen::files::FILE_SYSTEM_ACCESS;
-use wotw_seedgen::generator::{Seed, SeedSpoiler};
+use wotw_seedgen::generator::{FailedAttempt, GenerationError, Seed, SeedSpoiler};
 use wotw_seedgen::logic;
 use wotw_seedgen::settings;
 use wotw_seedgen::settings::UniverseSettings;
@@ -41,7 +42,7 @@ pub fn generate_seeds(args: cli::SeedArgs) -> Result<(), String> {
     let graph = read_input_files(&args.meta, &universe_settings)?;
 
     let seed = wotw_seedgen::generate_seed(&graph, &FILE_SYSTEM_ACCESS, &universe_settings)
-        .map_err(|err| format!("Error generating seed: {}", err))?;
+        .map_err(|err| generation_error(err, &args.meta))?;
 
     let worlds = universe_settings.world_count();
     if worlds == 1 {
@@ -118,13 +119,28 @@ fn read_input_files(
     Ok(graph)
 }
 
-fn write_seeds(args: SeedMetaArgs, seed: Seed<'_, '_>) -> Result<(), String> {
+fn write_seeds(args: SeedMetaArgs, mut seed: Seed<'_, '_>) -> Result<(), String> {
+    if args.playthrough {
+        let now = Instant::now();
+        seed.spoiler.playthrough = seed.playthrough();
+        match seed.spoiler.playthrough {
+            Some(_) => log::info!("Computed playthrough in {:?}", now.elapsed()),
+            None => log::warn!("Couldn't compute a playthrough because the seed isn't beatable"),
+        }
+    }
+
+    let spoiler_format = args.spoiler_format.unwrap_or(if args.json {
+        SpoilerFormat::Json
+    } else {
+        SpoilerFormat::Text
+    });
+
     if args.tostdout {
-        write_seeds_to_stdout(seed, args.json)?;
+        write_seeds_to_stdout(seed, args.json, spoiler_format)?;
     } else {
         let filename = args.filename.unwrap_or_else(|| String::from("seed"));
 
-        write_seeds_to_files(&seed, &filename, args.seed_folder, args.json)?;
+        write_seeds_to_files(&seed, &filename, args.seed_folder, spoiler_format)?;
     }
 
     if args.launch {
@@ -142,9 +158,9 @@ fn write_seeds_to_files(
     seed: &Seed,
     filename: &str,
     mut folder: PathBuf,
-    json_spoiler: bool,
+    spoiler_format: SpoilerFormat,
 ) -> Result<(), String> {
-    let seeds = seed.seed_files()?;
+    let seeds = seed.seed_files().map_err(|err| err.to_string())?;
     let multiworld = seeds.len() > 1;
 
     if multiworld {
@@ -182,16 +198,14 @@ fn write_seeds_to_files(
     let mut path = folder;
     path.push(format!("{filename}_spoiler"));
 
-    let contents = match json_spoiler {
-        true => {
-            path.set_extension("json");
-            seed.spoiler.to_json()
-        }
-        false => {
-            path.set_extension("txt");
-            seed.spoiler.to_string()
-        }
+    let extension = match spoiler_format {
+        SpoilerFormat::Text => "txt",
+        SpoilerFormat::Json => "json",
+        SpoilerFormat::Html => "html",
+        SpoilerFormat::Csv => "csv",
     };
+    path.set_extension(extension);
+    let contents = render_spoiler(&seed.spoiler, spoiler_format);
 
     let file =
         create_seedfile(path, &contents).map_err(|err| format!("Error writing spoiler: {err}"))?;
@@ -200,6 +214,15 @@ fn write_seeds_to_files(
     Ok(())
 }
 
+fn render_spoiler(spoiler: &SeedSpoiler, format: SpoilerFormat) -> String {
+    match format {
+        SpoilerFormat::Text => spoiler.to_string(),
+        SpoilerFormat::Json => spoiler.to_json(),
+        SpoilerFormat::Html => spoiler.to_html(),
+        SpoilerFormat::Csv => spoiler.to_csv(),
+    }
+}
+
 fn create_seedfile(path: PathBuf, contents: &str) -> Result<PathBuf, io::Error> {
     let mut index = 0;
     loop {
@@ -248,8 +271,20 @@ fn create_multiworld_folder(path: PathBuf) -> Result<PathBuf, io::Error> {
     }
 }
 
-fn write_seeds_to_stdout(seed: Seed, json: bool) -> Result<(), String> {
-    let files = seed.seed_files()?;
+fn write_seeds_to_stdout(
+    seed: Seed,
+    json: bool,
+    spoiler_format: SpoilerFormat,
+) -> Result<(), String> {
+    let files = match seed.seed_files() {
+        Ok(files) => files,
+        Err(err) if json => {
+            let output = SeedgenCliJsonErrorOutput::new(err);
+            println!("{}", output.to_json());
+            return Err(output.error_text);
+        }
+        Err(err) => return Err(err.to_string()),
+    };
 
     if json {
         let spoiler_text = seed.spoiler.to_string();
@@ -257,6 +292,7 @@ fn write_seeds_to_stdout(seed: Seed, json: bool) -> Result<(), String> {
             seed_files: files,
             spoiler: seed.spoiler,
             spoiler_text,
+            failed_attempts: seed.failed_attempts,
         };
 
         println!("{}", output.to_json())
@@ -272,7 +308,7 @@ fn write_seeds_to_stdout(seed: Seed, json: bool) -> Result<(), String> {
 
         println!();
         println!("======= Spoiler =======");
-        println!("{}", seed.spoiler);
+        println!("{}", render_spoiler(&seed.spoiler, spoiler_format));
     }
 
     Ok(())
@@ -288,6 +324,8 @@ struct SeedgenCliJsonOutput {
     pub spoiler: SeedSpoiler,
     /// Text representation of the spoiler
     pub spoiler_text: String,
+    /// Attempts that failed before the seed was generated successfully
+    pub failed_attempts: Vec<FailedAttempt>,
 }
 
 impl SeedgenCliJsonOutput {
@@ -297,6 +335,39 @@ impl SeedgenCliJsonOutput {
     }
 }
 
+/// Struct that is used for JSON output to stdout if generation failed
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SeedgenCliJsonErrorOutput {
+    /// The reason why the seed could not be generated
+    pub error: GenerationError,
+    /// Text representation of the error
+    pub error_text: String,
+}
+
+impl SeedgenCliJsonErrorOutput {
+    pub fn new(error: GenerationError) -> Self {
+        let error_text = error.to_string();
+        Self { error, error_text }
+    }
+
+    /// Serialize into json format
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(&self).unwrap()
+    }
+}
+
+/// Formats a [`GenerationError`] for the log and writes it to stdout if json output was requested
+fn generation_error(err: GenerationError, args: &SeedMetaArgs) -> String {
+    let message = format!("Error generating seed: {err}");
+
+    if args.json && args.tostdout {
+        println!("{}", SeedgenCliJsonErrorOutput::new(err).to_json());
+    }
+
+    message
+}
+
 pub fn regenerate_seed(args: RegenerateArgs) -> Result<(), String> {
     let now = Instant::now();
 
@@ -313,7 +384,7 @@ pub fn regenerate_seed(args: RegenerateArgs) -> Result<(), String> {
     let graph = read_input_files(&args.meta, &universe_settings)?;
 
     let seed = wotw_seedgen::generate_seed(&graph, &FILE_SYSTEM_ACCESS, &universe_settings)
-        .map_err(|err| format!("Error generating seed: {}", err))?;
+        .map_err(|err| generation_error(err, &args.meta))?;
 
     match verify_seed(&model, &seed) {
         Ok(()) => log::info!("Seed passed verification. Yay!"),
@@ -332,24 +403,44 @@ fn verify_generator_version(model: &str) -> Result<(), String> {
         .ok_or("No generator version found in seed")?;
 
     if model_version == wotw_seedgen::VERSION {
-        Ok(())
-    } else {
-        Err(format!(
+        return Ok(());
+    }
+
+    // Seeds from before algorithm versions existed only regenerate reliably on the same generator version
+    match model
+        .lines()
+        .find_map(|line| line.strip_prefix("// Algorithm Version: "))
+    {
+        Some(algorithm_version) => {
+            log::info!(
+                "Seed was generated on version {}, regenerating with algorithm version {}",
+                model_version,
+                algorithm_version
+            );
+            Ok(())
+        }
+        None => Err(format!(
             "Attempting to regenerate on version {}. but the seed was generated on {}",
             wotw_seedgen::VERSION,
             model_version,
-        ))
+        )),
     }
 }
 
 fn verify_seed(model: &str, seed: &Seed) -> Result<(), String> {
-    let seeds = seed.seed_files()?;
+    let seeds = seed.seed_files().map_err(|err| err.to_string())?;
     let world = settings::world_index_from_seed(&model).ok_or("no world index found in seed")??;
     let world_seed = seeds.get(world).ok_or("world index out of bounds")?;
 
-    if model == world_seed {
+    if without_generator_version(model).eq(without_generator_version(world_seed)) {
         Ok(())
     } else {
         Err("Regenerated seed does not match".to_string())
     }
 }
+
+/// Lines of a seed file, except for the generator version which is expected to differ when regenerating on a different release
+fn without_generator_version(seed: &str) -> impl Iterator<Item = &str> {
+    seed.lines()
+        .filter(|line| !line.starts_with("// Generator Version: "))
+}
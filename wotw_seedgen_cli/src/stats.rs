@@ -29,6 +29,8 @@ pub fn generate_stats(args: cli::StatsArgs) -> Result<(), String> {
         tolerated_errors,
         error_message_limit,
         overwrite_cache,
+        compare_strategies,
+        spoilers,
         settings: settings_args,
     } = args;
 
@@ -152,7 +154,37 @@ pub fn generate_stats(args: cli::StatsArgs) -> Result<(), String> {
         error_message_limit: Some(error_message_limit),
         overwrite_seed_storage: overwrite_cache,
     };
-    let stats = wotw_seedgen_stats::stats::<FileSystemAccess>(args)?;
+    let stats = if !spoilers.is_empty() {
+        let spoilers = spoilers
+            .iter()
+            .map(|path| {
+                fs::read_to_string(path)
+                    .map_err(|err| format!("Failed to read {}: {}", path.display(), err))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        wotw_seedgen_stats::stats_from_text_spoilers(
+            spoilers.iter().map(String::as_str),
+            args.analyzers,
+            &graph,
+        )?
+        .into_iter()
+        .map(|stats| (stats.title(), stats.csv()))
+        .collect()
+    } else if compare_strategies.is_empty() {
+        wotw_seedgen_stats::stats::<FileSystemAccess>(args)?
+            .into_iter()
+            .map(|stats| (stats.title(), stats.csv()))
+            .collect::<Vec<_>>()
+    } else {
+        let strategies = compare_strategies
+            .into_iter()
+            .map(cli::PlacementStrategyOpt::into_inner)
+            .collect::<Vec<_>>();
+        wotw_seedgen_stats::compare_strategies::<FileSystemAccess>(args, &strategies)?
+            .into_iter()
+            .map(|comparison| (comparison.title(), comparison.csv()))
+            .collect()
+    };
 
     fs::create_dir_all(&path).map_err(|err| {
         format!(
@@ -161,10 +193,9 @@ pub fn generate_stats(args: cli::StatsArgs) -> Result<(), String> {
             err
         )
     })?; // It might've been a while ago that we created this folder, lets check if we need to recreate it in case the user deleted it in the meantime
-    for stats in stats {
-        let csv = stats.csv();
+    for (title, csv) in stats {
         let mut path = path.clone();
-        path.push(format!("{}.csv", sanitize(stats.title())));
+        path.push(format!("{}.csv", sanitize(title)));
         fs::write(&path, csv).map_err(|err| {
             format!(
                 "failed to write statistics to \"{}\": {}",
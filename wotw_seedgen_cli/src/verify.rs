@@ -0,0 +1,107 @@
+use super::cli;
+use super::log_init;
+
+use std::fs;
+
+use itertools::Itertools;
+use log::LevelFilter;
+
+use wotw_seedgen::logic;
+use wotw_seedgen::settings::{Accessibility, UniverseSettings};
+use wotw_seedgen::world::graph::Node;
+
+pub fn verify(args: cli::VerifyArgs) -> Result<(), String> {
+    log_init::initialize_log(None, LevelFilter::Info, false)
+        .unwrap_or_else(|err| eprintln!("Failed to initialize log: {}", err));
+
+    let seed_files = args
+        .seed_files
+        .into_iter()
+        .map(|mut path| {
+            path.set_extension("wotwr");
+            fs::read_to_string(&path)
+                .map_err(|err| format!("Error reading {}: {}", path.display(), err))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let universe_settings = UniverseSettings::from_seed(&seed_files[0])
+        .unwrap_or_else(|| Err("No settings found in seed".into()))
+        .map_err(|err| format!("Error reading settings: {err}"))?;
+
+    let areas = fs::read_to_string(&args.areas)
+        .map_err(|err| format!("Failed to read {}: {}", args.areas.display(), err))?;
+    let locations = fs::read_to_string(&args.locations)
+        .map_err(|err| format!("Failed to read {}: {}", args.locations.display(), err))?;
+    let states = fs::read_to_string(&args.uber_states)
+        .map_err(|err| format!("Failed to read {}: {}", args.uber_states.display(), err))?;
+    let graph = logic::parse_logic(&areas, &locations, &states, &universe_settings, false)?;
+
+    let seed_files = seed_files.iter().map(String::as_str).collect::<Vec<_>>();
+    let verification = wotw_seedgen::verify_seed(&graph, &seed_files)?;
+
+    let multiworld = verification.worlds.len() > 1;
+    let mut passed = true;
+    for (world_index, (world, world_settings)) in verification
+        .worlds
+        .iter()
+        .zip(&universe_settings.world_settings)
+        .enumerate()
+    {
+        let prefix = if multiworld {
+            format!("(World {world_index}): ")
+        } else {
+            String::new()
+        };
+
+        for unmet_goal in &world.unmet_goals {
+            passed = false;
+            let goal = unmet_goal.goal.as_ref().map_or_else(
+                || "Reaching the end of the game".to_string(),
+                ToString::to_string,
+            );
+            log::error!(
+                "{prefix}{goal} is impossible, missing {}",
+                identifiers(&unmet_goal.missing_locations)
+            );
+        }
+
+        if !world.unreachable_locations.is_empty() {
+            let message = format!(
+                "{prefix}{} locations can't be reached: {}",
+                world.unreachable_locations.len(),
+                identifiers(&world.unreachable_locations)
+            );
+            if world_settings.accessibility == Accessibility::Beatable {
+                log::info!("{message}");
+            } else {
+                passed = false;
+                log::error!("{message}");
+            }
+        }
+
+        if !world.out_of_logic_locations.is_empty() {
+            let message = format!(
+                "{prefix}{} locations are never in logic: {}",
+                world.out_of_logic_locations.len(),
+                identifiers(&world.out_of_logic_locations)
+            );
+            if world_settings.accessibility == Accessibility::Full {
+                passed = false;
+                log::error!("{message}");
+            } else {
+                log::info!("{message}");
+            }
+        }
+    }
+
+    if passed {
+        log::info!("Seed passed verification");
+        Ok(())
+    } else {
+        Err("Seed failed verification".to_string())
+    }
+}
+
+fn identifiers(nodes: &[&Node]) -> String {
+    nodes.iter().map(|node| node.identifier()).join(", ")
+}
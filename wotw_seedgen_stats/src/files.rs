@@ -1,6 +1,9 @@
 use std::iter;
 
-use wotw_seedgen::{generator::SeedSpoiler, settings::UniverseSettings};
+use wotw_seedgen::{
+    generator::SeedSpoiler,
+    settings::{FillAlgorithm, PlacementStrategy, UniverseSettings},
+};
 
 use crate::Result;
 
@@ -233,6 +236,13 @@ mod fs_access {
         let bytes =
             bincode::serialize(&settings.world_settings).expect("Failed to serialize settings"); // We deliberately ignore the seed
         bytes.hash(&mut hasher);
+        // Only hashed if relevant so seed storages from before placement strategies and fill algorithms existed stay valid
+        if settings.placement_strategy != PlacementStrategy::Classic {
+            settings.placement_strategy.hash(&mut hasher);
+        }
+        if settings.fill_algorithm != FillAlgorithm::Forward {
+            settings.fill_algorithm.hash(&mut hasher);
+        }
         hasher.finish()
     }
     fn print_feedback_for_existing_seeds(seeds: HandleErrorsReadDir) {
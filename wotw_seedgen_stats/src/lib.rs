@@ -3,13 +3,17 @@ pub mod files;
 mod handle_errors;
 mod seed_storage;
 
-use std::{cmp::Ordering, fmt::Write, sync::Arc, time::Instant};
+use std::{cmp::Ordering, fmt::Write, iter, sync::Arc, time::Instant};
 
 use analyzers::Analyzer;
 use files::FileAccess;
 use itertools::Itertools;
 use rustc_hash::FxHashMap;
-use wotw_seedgen::{settings::UniverseSettings, world::Graph};
+use wotw_seedgen::{
+    generator::SeedSpoiler,
+    settings::{PlacementStrategy, UniverseSettings},
+    world::Graph,
+};
 
 type Result<T> = std::result::Result<T, String>;
 
@@ -52,26 +56,14 @@ pub struct Stats {
 }
 impl Stats {
     pub fn title(&self) -> String {
-        self.analyzers
-            .iter()
-            .map(|analyzer| analyzer.title())
-            .join(" and ")
+        title(&self.analyzers)
     }
     pub fn csv(&self) -> String {
         let mut csv = self.title();
         csv.push_str(", Count\n");
 
         let mut data = self.data.iter().collect::<Vec<_>>();
-        data.sort_unstable_by(|(a, _), (b, _)| {
-            for ((x, y), analyzer) in a.iter().zip(b.iter()).zip(self.analyzers.iter()) {
-                match analyzer.compare_keys()(x, y) {
-                    Ordering::Equal => (),
-                    non_eq => return non_eq,
-                }
-            }
-
-            Ordering::Equal
-        });
+        data.sort_unstable_by(|(a, _), (b, _)| compare_keys(&self.analyzers, a, b));
 
         csv.extend(Itertools::intersperse_with(
             data.into_iter().map(|(keys, value)| {
@@ -86,6 +78,70 @@ impl Stats {
     }
 }
 
+/// The same [`Analyzer`]s applied to seeds generated with different [`PlacementStrategy`]s
+///
+/// See [`compare_strategies`]
+pub struct StrategyComparison {
+    analyzers: ChainedAnalyzers,
+    /// The compared [`PlacementStrategy`]s
+    pub strategies: Vec<PlacementStrategy>,
+    /// The data for each of the `strategies`, in the same order
+    pub data: Vec<FxHashMap<Vec<Arc<String>>, u32>>,
+}
+impl StrategyComparison {
+    pub fn title(&self) -> String {
+        format!("{} by placement strategy", title(&self.analyzers))
+    }
+    /// Writes one column of counts for each [`PlacementStrategy`] next to each other
+    pub fn csv(&self) -> String {
+        let mut csv = title(&self.analyzers);
+        for strategy in &self.strategies {
+            write!(csv, ", {strategy}").unwrap();
+        }
+        csv.push('\n');
+
+        let mut keys = self
+            .data
+            .iter()
+            .flat_map(FxHashMap::keys)
+            .unique()
+            .collect::<Vec<_>>();
+        keys.sort_unstable_by(|a, b| compare_keys(&self.analyzers, a, b));
+
+        csv.extend(Itertools::intersperse_with(
+            keys.into_iter().map(|keys| {
+                let mut data_line = keys.iter().join(", ");
+                for data in &self.data {
+                    let count = data.get(keys).copied().unwrap_or_default();
+                    write!(data_line, ", {count}").unwrap();
+                }
+                data_line
+            }),
+            || "\n".to_string(),
+        ));
+
+        csv
+    }
+}
+
+fn title(analyzers: &[Box<dyn Analyzer>]) -> String {
+    analyzers
+        .iter()
+        .map(|analyzer| analyzer.title())
+        .join(" and ")
+}
+
+fn compare_keys(analyzers: &[Box<dyn Analyzer>], a: &[Arc<String>], b: &[Arc<String>]) -> Ordering {
+    for ((x, y), analyzer) in a.iter().zip(b.iter()).zip(analyzers.iter()) {
+        match analyzer.compare_keys()(x, y) {
+            Ordering::Equal => (),
+            non_eq => return non_eq,
+        }
+    }
+
+    Ordering::Equal
+}
+
 /// Generates a set of stats
 ///
 /// See [`StatsArgs`] for more details on the passed arguments
@@ -131,3 +187,93 @@ pub fn stats<F: FileAccess>(args: StatsArgs) -> Result<Vec<Stats>> {
 
     Ok(stats)
 }
+
+/// Generates a set of stats from text spoilers instead of generating new seeds
+///
+/// The spoilers are parsed with [`SeedSpoiler::from_str`] and their locations are looked up in the `graph` with [`SeedSpoiler::locate`],
+/// so analyzers relying on zones work the same as they do for generated seeds
+pub fn stats_from_text_spoilers<'a>(
+    spoilers: impl IntoIterator<Item = &'a str>,
+    analyzers: Vec<ChainedAnalyzers>,
+    graph: &Graph,
+) -> Result<Vec<Stats>> {
+    let mut data = iter::repeat_with(FxHashMap::default)
+        .take(analyzers.len())
+        .collect::<Vec<_>>();
+    for (index, spoiler) in spoilers.into_iter().enumerate() {
+        let mut spoiler = spoiler
+            .parse::<SeedSpoiler>()
+            .map_err(|err| format!("Failed to parse spoiler {index}: {err}"))?;
+        if spoiler.spawns.len() > 1 {
+            return Err("Multiworld seeds aren't well supported yet".to_string());
+        }
+        spoiler.locate(graph);
+        seed_storage::analyze_seed(&spoiler, &analyzers, &mut data);
+    }
+
+    let stats = data
+        .into_iter()
+        .zip(analyzers)
+        .map(|(data, analyzers)| Stats { analyzers, data })
+        .collect();
+
+    Ok(stats)
+}
+
+/// Generates a set of stats for each of the given [`PlacementStrategy`]s, to compare them head to head
+///
+/// This calls [`stats`] once for each of the `strategies`, replacing the `placement_strategy` of the [`StatsArgs`] settings.
+/// Every strategy uses its own seed storage
+pub fn compare_strategies<F: FileAccess>(
+    args: StatsArgs,
+    strategies: &[PlacementStrategy],
+) -> Result<Vec<StrategyComparison>> {
+    let StatsArgs {
+        mut settings,
+        sample_size,
+        mut analyzers,
+        graph,
+        tolerated_errors,
+        error_message_limit,
+        overwrite_seed_storage,
+    } = args;
+
+    let mut data = iter::repeat_with(Vec::new)
+        .take(analyzers.len())
+        .collect::<Vec<_>>();
+    for &strategy in strategies {
+        eprintln!("Generating stats for the {strategy} placement strategy");
+        settings.placement_strategy = strategy;
+
+        let strategy_stats = stats::<F>(StatsArgs {
+            settings: settings.clone(),
+            sample_size,
+            analyzers,
+            graph,
+            tolerated_errors,
+            error_message_limit,
+            overwrite_seed_storage,
+        })?;
+        // The analyzers get handed back so the next strategy can reuse them
+        analyzers = strategy_stats
+            .into_iter()
+            .zip(&mut data)
+            .map(|(stats, data)| {
+                data.push(stats.data);
+                stats.analyzers
+            })
+            .collect();
+    }
+
+    let comparisons = data
+        .into_iter()
+        .zip(analyzers)
+        .map(|(data, analyzers)| StrategyComparison {
+            analyzers,
+            strategies: strategies.to_vec(),
+            data,
+        })
+        .collect();
+
+    Ok(comparisons)
+}
@@ -83,7 +83,7 @@ pub(crate) fn analyze<F: FileAccess>(
                                     let mut error_messages_lock = error_messages.lock().expect(ANOTHER_THREAD_PANICKED);
 
                                     if error_messages_lock.len() < error_message_limit {
-                                        error_messages_lock.push(err);
+                                        error_messages_lock.push(err.to_string());
                                     }
                                     let errors = errors.fetch_add(1, Ordering::Relaxed) + 1;
                                     if errors > tolerated_errors {
@@ -149,7 +149,11 @@ fn analyze_existing_seeds<F: FileAccess>(
 
     Ok(existing_amount)
 }
-fn analyze_seed(seed: &SeedSpoiler, analyzers: &[ChainedAnalyzers], data: &mut [SeedData]) {
+pub(crate) fn analyze_seed(
+    seed: &SeedSpoiler,
+    analyzers: &[ChainedAnalyzers],
+    data: &mut [SeedData],
+) {
     for (data, chained_analyzers) in data.iter_mut().zip(analyzers.iter()) {
         chained_analyzers
             .iter()